@@ -0,0 +1,110 @@
+use std::fs;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use cursive::Vec2;
+use scrabbler::{
+    bag::Distribution,
+    board::{Alignment, Board},
+    dictionary::Dictionary,
+    gaddag::Gaddag,
+    solver::Solver,
+    tile::Tile,
+};
+
+const RACK: &str = "AEILNRS";
+
+fn words() -> Vec<String> {
+    fs::read_to_string("dict_en.txt")
+        .expect("benchmarks are run from the crate root")
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+fn place(board: &mut Board, word: &str, start: Vec2, alignment: Alignment) {
+    for (i, letter) in word.chars().enumerate() {
+        let pos = board.offset(&start, alignment, i as isize).unwrap();
+        if board.letter_at(&pos).is_none() {
+            board.place_at(Distribution::default().tile(letter), &pos);
+        }
+    }
+}
+
+// A handful of crossing words around the center, enough to give the solver
+// anchors and crosschecks in every direction.
+fn mid_game(dict: &Gaddag) -> Board {
+    let mut board = Board::new(15);
+    for (word, start, alignment) in [
+        ("JOUSTED", Vec2::new(4, 7), Alignment::Horizontal),
+        ("OVERLAY", Vec2::new(5, 7), Alignment::Vertical),
+        ("EXIT", Vec2::new(8, 4), Alignment::Vertical),
+        ("RAZE", Vec2::new(5, 10), Alignment::Horizontal),
+    ] {
+        place(&mut board, word, start, alignment);
+        board.commit_tentative(dict);
+    }
+    board
+}
+
+fn gaddag_build(c: &mut Criterion) {
+    let words = words().into_iter().take(20_000).collect::<Vec<String>>();
+    let mut group = c.benchmark_group("gaddag");
+    group.sample_size(10);
+    group.bench_function("build 20k words", |b| {
+        b.iter(|| Gaddag::from_words(black_box(words.clone())).unwrap())
+    });
+    group.finish();
+}
+
+fn gaddag_accepts(c: &mut Criterion) {
+    let dict = Gaddag::from_words(words()).unwrap();
+    c.bench_function("gaddag accepts", |b| {
+        b.iter(|| {
+            for word in ["QUIXOTIC", "JOUSTED", "ZZZZ", "AA", "OVERLAYS"] {
+                black_box(dict.accepts(black_box(word)));
+            }
+        })
+    });
+}
+
+fn crosschecks(c: &mut Criterion) {
+    let dict = Gaddag::from_words(words()).unwrap();
+    let board = mid_game(&dict);
+    c.bench_function("crosscheck update", |b| {
+        b.iter_batched(
+            || {
+                let mut board = board.clone();
+                place(&mut board, "LINERS", Vec2::new(9, 8), Alignment::Vertical);
+                board
+            },
+            |mut board| board.commit_tentative(&dict),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn move_generation(c: &mut Criterion) {
+    let dict = Gaddag::from_words(words()).unwrap();
+    let empty = Board::new(15);
+    let board = mid_game(&dict);
+    let distribution = Distribution::default();
+    let rack = RACK
+        .chars()
+        .map(|letter| distribution.tile(letter))
+        .collect::<Vec<Tile>>();
+    c.bench_function("moves opening", |b| {
+        b.iter(|| Solver::new(&dict, &empty).moves(black_box(&rack)))
+    });
+    c.bench_function("moves mid-game", |b| {
+        b.iter(|| Solver::new(&dict, &board).moves(black_box(&rack)))
+    });
+}
+
+criterion_group!(
+    benches,
+    gaddag_build,
+    gaddag_accepts,
+    crosschecks,
+    move_generation
+);
+criterion_main!(benches);
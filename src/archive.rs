@@ -0,0 +1,319 @@
+//! Finished games, kept in a SQLite database to be listed and replayed later. Every
+//! game is a row with its date and lexicon, and its moves as the GCG record
+//! `gcg::write` makes of them, and each of its players a row of the seats table with
+//! their final score. Games can be looked up by the words played in them, which are
+//! kept in a table of their own, or by their scores, see `Query`.
+
+use std::{collections::BTreeSet, path::PathBuf};
+
+use rusqlite::{params, params_from_iter, types::Value, Connection};
+
+use crate::{error::Result, gcg};
+
+const SCHEMA: &str = "PRAGMA foreign_keys = ON;
+CREATE TABLE IF NOT EXISTS games (
+    id INTEGER PRIMARY KEY,
+    played TEXT NOT NULL DEFAULT (datetime('now')),
+    lexicon TEXT NOT NULL,
+    record TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS seats (
+    game INTEGER NOT NULL REFERENCES games (id) ON DELETE CASCADE,
+    seat INTEGER NOT NULL,
+    player TEXT NOT NULL,
+    score INTEGER NOT NULL,
+    PRIMARY KEY (game, seat)
+);
+CREATE TABLE IF NOT EXISTS words (
+    game INTEGER NOT NULL REFERENCES games (id) ON DELETE CASCADE,
+    word TEXT NOT NULL,
+    PRIMARY KEY (word, game)
+);";
+
+// The columns of `Entry`, the players and scores joined in seat order.
+const ENTRY: &str = "SELECT id, played,
+    (SELECT group_concat(player, ', ' ORDER BY seat) FROM seats WHERE game = id),
+    (SELECT group_concat(score, ', ' ORDER BY seat) FROM seats WHERE game = id),
+    lexicon, record
+FROM games";
+
+/// The database at `path`, with the name of the lexicon the games are played in.
+#[derive(Clone)]
+pub struct Archive {
+    path: PathBuf,
+    lexicon: String,
+}
+
+/// A game as listed in the archive, without its record.
+pub struct Entry {
+    pub id: i64,
+    pub played: String,
+    pub players: String,
+    pub scores: String,
+    pub lexicon: String,
+}
+
+impl Archive {
+    pub fn new(path: PathBuf, lexicon: String) -> Self {
+        Self { path, lexicon }
+    }
+
+    // Connections aren't kept, as games can't hold on to one between turns.
+    fn open(&self) -> Result<Connection> {
+        let conn = Connection::open(&self.path)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(conn)
+    }
+
+    /// Keeps a finished game: the names and scores of its players, in seat order, and
+    /// its GCG record, along with the main words played in it.
+    pub fn save(&self, players: &[&str], scores: &[isize], record: &str) -> Result<()> {
+        let mut conn = self.open()?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO games (lexicon, record) VALUES (?1, ?2)",
+            params![self.lexicon, record],
+        )?;
+        let game = tx.last_insert_rowid();
+        for (seat, (player, score)) in players.iter().zip(scores).enumerate() {
+            tx.execute(
+                "INSERT INTO seats (game, seat, player, score) VALUES (?1, ?2, ?3, ?4)",
+                params![game, seat, player, *score as i64],
+            )?;
+        }
+        // Only main words count, as the record doesn't list the words formed across.
+        let words = gcg::parse(record)
+            .map(|record| {
+                gcg::words(&record.turns)
+                    .into_iter()
+                    .map(|(_, word)| word)
+                    .collect::<BTreeSet<_>>()
+            })
+            .unwrap_or_default();
+        for word in words {
+            tx.execute(
+                "INSERT INTO words (game, word) VALUES (?1, ?2)",
+                params![game, word],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Every game kept, the latest first.
+    pub fn games(&self) -> Result<Vec<Entry>> {
+        Ok(self
+            .records()?
+            .into_iter()
+            .map(|(entry, _)| entry)
+            .collect())
+    }
+
+    /// The games that match `query`, the latest first.
+    pub fn search(&self, query: &Query) -> Result<Vec<Entry>> {
+        let (filter, values) = query.filter();
+        Ok(self
+            .select(filter, values)?
+            .into_iter()
+            .map(|(entry, _)| entry)
+            .collect())
+    }
+
+    /// Every game kept with its GCG record, the latest first.
+    pub fn records(&self) -> Result<Vec<(Entry, String)>> {
+        self.select("", Vec::new())
+    }
+
+    // The games `filter` lets through with their records, its parameters bound to
+    // `values`.
+    fn select(&self, filter: &str, values: Vec<Value>) -> Result<Vec<(Entry, String)>> {
+        let conn = self.open()?;
+        let mut statement = conn.prepare(&format!("{ENTRY} {filter} ORDER BY id DESC"))?;
+        let games = statement
+            .query_map(params_from_iter(values), |row| {
+                let entry = Entry {
+                    id: row.get(0)?,
+                    played: row.get(1)?,
+                    players: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                    scores: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                    lexicon: row.get(4)?,
+                };
+                Ok((entry, row.get::<_, String>(5)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(games)
+    }
+
+    /// The GCG record of the game `id`.
+    pub fn record(&self, id: i64) -> Result<String> {
+        Ok(self
+            .open()?
+            .query_row("SELECT record FROM games WHERE id = ?1", [id], |row| {
+                row.get(0)
+            })?)
+    }
+}
+
+/// A search of the archive, typed as a word, "QUIXOTIC", for the games where someone
+/// played it, or as a score, "over 400" or "Jane over 400", for the games where anyone,
+/// or the player named, finished with more.
+pub enum Query {
+    Word(String),
+    Score { player: Option<String>, over: isize },
+}
+
+impl Query {
+    pub fn parse(text: &str) -> Option<Self> {
+        let words = text.split_whitespace().collect::<Vec<_>>();
+        match words[..] {
+            [ref player @ .., "over", score] => Some(Self::Score {
+                player: (!player.is_empty()).then(|| player.join(" ")),
+                over: score.parse().ok()?,
+            }),
+            [word] if word.chars().all(char::is_alphabetic) => {
+                Some(Self::Word(word.to_uppercase()))
+            }
+            _ => None,
+        }
+    }
+
+    // The WHERE clause picking the games that match, with the values of its parameters.
+    fn filter(&self) -> (&'static str, Vec<Value>) {
+        match self {
+            Self::Word(word) => (
+                "WHERE id IN (SELECT game FROM words WHERE word = ?1)",
+                vec![Value::Text(word.clone())],
+            ),
+            Self::Score { player, over } => (
+                "WHERE id IN (SELECT game FROM seats
+                    WHERE score > ?1 AND (?2 IS NULL OR player = ?2 COLLATE NOCASE))",
+                vec![
+                    Value::Integer(*over as i64),
+                    player.clone().map_or(Value::Null, Value::Text),
+                ],
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+    use crate::gcg::{Action, Turn};
+
+    // An archive in a file of its own, removed when it's dropped.
+    struct Scratch(Archive);
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let path = env::temp_dir().join(format!("scrabbler-{name}-{}.db", std::process::id()));
+            let _ = fs::remove_file(&path);
+            Self(Archive::new(path, "TWL".to_string()))
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0.path);
+        }
+    }
+
+    #[test]
+    fn games_are_listed_latest_first_with_their_seats() {
+        let archive = Scratch::new("listed");
+        archive
+            .0
+            .save(&["Ann", "Bo"], &[410, 385], "first")
+            .unwrap();
+        archive
+            .0
+            .save(&["Cy", "Di", "Ed"], &[-3, 120, 0], "second")
+            .unwrap();
+
+        let games = archive.0.games().unwrap();
+        let seats = games
+            .iter()
+            .map(|game| (game.players.as_str(), game.scores.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            seats,
+            [("Cy, Di, Ed", "-3, 120, 0"), ("Ann, Bo", "410, 385")]
+        );
+        assert_eq!(games[0].lexicon, "TWL");
+        assert_eq!(archive.0.record(games[1].id).unwrap(), "first");
+    }
+
+    // The GCG record of a game where `words` were played through the center in turn.
+    fn record(players: &[&str], words: &[&str]) -> String {
+        let turns = words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| Turn {
+                player: i % players.len(),
+                rack: word.chars().collect(),
+                action: Action::Play {
+                    coordinate: if i % 2 == 0 { "8D" } else { "H4" }.to_string(),
+                    word: word.to_string(),
+                },
+                score: 10,
+                total: 10,
+                notes: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        let mut record = Vec::new();
+        gcg::write(players, &turns, &mut record).unwrap();
+        String::from_utf8(record).unwrap()
+    }
+
+    fn search(archive: &Archive, query: &str) -> Vec<String> {
+        archive
+            .search(&Query::parse(query).unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|game| game.players)
+            .collect()
+    }
+
+    #[test]
+    fn search_by_word_or_score() {
+        let archive = Scratch::new("searched");
+        let players = ["Jane Doe", "Bo"];
+        let first = record(&players, &["QUIXOTIC", "ZAX"]);
+        archive.0.save(&players, &[420, 300], &first).unwrap();
+        let second = record(&["Cy", "Jane Doe"], &["JOUSTED"]);
+        archive
+            .0
+            .save(&["Cy", "Jane Doe"], &[380, 250], &second)
+            .unwrap();
+
+        assert_eq!(search(&archive.0, "quixotic"), ["Jane Doe, Bo"]);
+        assert_eq!(search(&archive.0, "JOUSTED"), ["Cy, Jane Doe"]);
+        assert!(search(&archive.0, "OXEN").is_empty());
+        assert_eq!(
+            search(&archive.0, "over 350"),
+            ["Cy, Jane Doe", "Jane Doe, Bo"]
+        );
+        assert_eq!(search(&archive.0, "jane doe over 350"), ["Jane Doe, Bo"]);
+        assert!(search(&archive.0, "Bo over 300").is_empty());
+    }
+
+    #[test]
+    fn queries_parse_as_words_or_scores() {
+        assert!(matches!(Query::parse("zax"), Some(Query::Word(word)) if word == "ZAX"));
+        assert!(matches!(
+            Query::parse("Jane Doe over 400"),
+            Some(Query::Score { player: Some(player), over: 400 }) if player == "Jane Doe"
+        ));
+        assert!(matches!(
+            Query::parse("over -5"),
+            Some(Query::Score {
+                player: None,
+                over: -5
+            })
+        ));
+        assert!(Query::parse("over lots").is_none());
+        assert!(Query::parse("two words").is_none());
+    }
+}
@@ -0,0 +1,270 @@
+//! Matches between two bots over many seeded games, for telling whether a change to how
+//! bots weigh their moves makes them stronger. Each seed is played twice with the bots
+//! swapping who goes first, so that neither gets the better tiles or the first move
+//! more often, and the result comes with a 95% confidence interval.
+//!
+//! `cargo run --release -- --match equity score 200` plays 200 games between the two
+//! styles named, see `Contender::parse`.
+
+use std::fmt;
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{
+    bag::Distribution,
+    board::Board,
+    gaddag::Gaddag,
+    game::{BOARD_SIZE, N_LETTERS},
+    leaves::Leaves,
+    solver::{Candidate, Solver},
+    strategy,
+    tile::Tile,
+};
+
+// Consecutive scoreless turns that end a game between two players.
+const SCORELESS_LIMIT: usize = 6;
+// Only the plays with the best equity are weighed for defense, as each costs a board
+// update.
+const DEFENDED: usize = 20;
+// Standard normal quantile for a 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// A way of picking moves: the best equity, with the leave counted if `leaves` has
+/// values, less what a play opens up for the opponent if `defense`.
+#[derive(Clone)]
+pub struct Contender {
+    pub name: String,
+    pub leaves: Leaves,
+    pub defense: bool,
+}
+
+/// The outcome of a match from the first contender's side.
+pub struct Report {
+    pub names: [String; 2],
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    // Final score of the first contender less the second's, for every game.
+    spreads: Vec<f64>,
+}
+
+impl Contender {
+    /// The contender for a style: "score" plays the highest score, "equity" weighs the
+    /// leave by `leaves` too, and "defense" and "equity-defense" also count what a play
+    /// opens up.
+    pub fn parse(name: &str, leaves: &Leaves) -> Option<Self> {
+        let (leaves, defense) = match name {
+            "score" => (Leaves::default(), false),
+            "equity" => (leaves.clone(), false),
+            "defense" => (Leaves::default(), true),
+            "equity-defense" => (leaves.clone(), true),
+            _ => return None,
+        };
+        Some(Self {
+            name: name.to_string(),
+            leaves,
+            defense,
+        })
+    }
+
+    fn choose(&self, dict: &Gaddag, board: &Board, rack: &[Tile], bag: usize) -> Candidate {
+        let mut candidates = Solver::new(dict, board).candidates(rack, &self.leaves, bag);
+        if self.defense {
+            let open = strategy::premiums_in_reach(board);
+            for evaluated in candidates.iter_mut().take(DEFENDED) {
+                if let Candidate::Play(play) = &evaluated.candidate {
+                    evaluated.equity -= strategy::openness(dict, board, &open, play);
+                }
+            }
+        }
+        candidates
+            .into_iter()
+            .max_by(|a, b| a.equity.total_cmp(&b.equity))
+            .map_or(Candidate::Pass, |best| best.candidate)
+    }
+}
+
+/// Plays `games` games between the contenders, seeded from `seed` onwards, half of
+/// them with each going first.
+pub fn run(dict: &Gaddag, contenders: [&Contender; 2], games: usize, seed: u64) -> Report {
+    let mut report = Report {
+        names: contenders.map(|contender| contender.name.clone()),
+        wins: 0,
+        draws: 0,
+        losses: 0,
+        spreads: Vec::new(),
+    };
+    for game in 0..games {
+        let seed = seed + (game / 2) as u64;
+        let [first, second] = contenders;
+        let spread = if game % 2 == 0 {
+            let [a, b] = play(dict, [first, second], seed);
+            a - b
+        } else {
+            let [b, a] = play(dict, [second, first], seed);
+            a - b
+        };
+        match spread {
+            s if s > 0 => report.wins += 1,
+            0 => report.draws += 1,
+            _ => report.losses += 1,
+        }
+        report.spreads.push(spread as f64);
+    }
+    report
+}
+
+// A game between `seats` in turn order, returning their final scores.
+fn play(dict: &Gaddag, seats: [&Contender; 2], seed: u64) -> [isize; 2] {
+    let mut bag = Distribution::default().tiles();
+    let mut rng = StdRng::seed_from_u64(seed);
+    bag.shuffle(&mut rng);
+    let mut board = Board::new(BOARD_SIZE);
+    let mut racks = [Vec::new(), Vec::new()];
+    let mut scores = [0isize; 2];
+    let mut scoreless = 0;
+    for rack in &mut racks {
+        rack.extend(bag.drain(..N_LETTERS.min(bag.len())));
+    }
+
+    for turn in 0.. {
+        let player = turn % 2;
+        let rack = &mut racks[player];
+        match seats[player].choose(dict, &board, rack, bag.len()) {
+            Candidate::Play(play) => {
+                for &(pos, tile) in &play.tiles {
+                    board.place_at(tile, &pos);
+                    let idx = rack.iter().position(|&t| t == tile.drawn()).unwrap();
+                    rack.swap_remove(idx);
+                }
+                board.commit_tentative(dict);
+                scores[player] += play.score as isize;
+                scoreless = 0;
+            }
+            Candidate::Exchange(tiles) => {
+                for tile in &tiles {
+                    let idx = rack.iter().position(|t| t == tile).unwrap();
+                    rack.swap_remove(idx);
+                }
+                rack.extend(bag.drain(..tiles.len()));
+                bag.extend(tiles);
+                bag.shuffle(&mut rng);
+                scoreless += 1;
+            }
+            Candidate::Pass => scoreless += 1,
+        }
+        let wanted = N_LETTERS - rack.len();
+        rack.extend(bag.drain(..wanted.min(bag.len())));
+        if rack.is_empty() || scoreless >= SCORELESS_LIMIT {
+            break;
+        }
+    }
+
+    // Letters left on a rack count against it, as in `Game`.
+    for (score, rack) in scores.iter_mut().zip(&racks) {
+        *score -= rack.iter().map(|tile| tile.value as isize).sum::<isize>();
+    }
+    scores
+}
+
+impl Report {
+    pub fn games(&self) -> usize {
+        self.spreads.len()
+    }
+
+    /// The share of the games won by the first contender, draws counting half, with
+    /// the half-width of its 95% confidence interval.
+    pub fn win_rate(&self) -> (f64, f64) {
+        let games = self.games() as f64;
+        let rate = (self.wins as f64 + self.draws as f64 / 2.0) / games;
+        (rate, Z_95 * (rate * (1.0 - rate) / games).sqrt())
+    }
+
+    /// The first contender's average lead at the end of a game, with the half-width of
+    /// its 95% confidence interval.
+    pub fn spread(&self) -> (f64, f64) {
+        let games = self.games() as f64;
+        let mean = self.spreads.iter().sum::<f64>() / games;
+        let variance = self
+            .spreads
+            .iter()
+            .map(|spread| (spread - mean).powi(2))
+            .sum::<f64>()
+            / (games - 1.0).max(1.0);
+        (mean, Z_95 * (variance / games).sqrt())
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [first, second] = &self.names;
+        if self.games() == 0 {
+            return write!(f, "{first} vs {second}: no games played");
+        }
+        let (rate, rate_margin) = self.win_rate();
+        let (spread, spread_margin) = self.spread();
+        writeln!(f, "{first} vs {second} over {} games", self.games())?;
+        writeln!(
+            f,
+            "{} wins, {} draws, {} losses",
+            self.wins, self.draws, self.losses
+        )?;
+        writeln!(
+            f,
+            "Win rate: {:.1}% ± {:.1}%",
+            rate * 100.0,
+            rate_margin * 100.0
+        )?;
+        write!(f, "Spread: {spread:+.1} ± {spread_margin:.1} points a game")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(spreads: &[f64]) -> Report {
+        Report {
+            names: ["a".to_string(), "b".to_string()],
+            wins: spreads.iter().filter(|&&s| s > 0.0).count(),
+            draws: spreads.iter().filter(|&&s| s == 0.0).count(),
+            losses: spreads.iter().filter(|&&s| s < 0.0).count(),
+            spreads: spreads.to_vec(),
+        }
+    }
+
+    #[test]
+    fn win_rate_counts_draws_half() {
+        let (rate, margin) = report(&[10.0, 0.0, -5.0, 20.0]).win_rate();
+        assert_eq!(rate, 0.625);
+        assert!((margin - 1.96 * (0.625f64 * 0.375 / 4.0).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn spread_is_the_mean_with_the_sample_variance() {
+        let (mean, margin) = report(&[10.0, -10.0, 30.0]).spread();
+        assert_eq!(mean, 10.0);
+        // Sample variance of 400, over 3 games.
+        assert!((margin - 1.96 * (400.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!(report(&[7.0]).spread(), (7.0, 0.0));
+    }
+
+    #[test]
+    fn no_games_played() {
+        assert_eq!(report(&[]).to_string(), "a vs b: no games played");
+    }
+
+    #[test]
+    fn every_game_is_reported() {
+        let words = [
+            "AT", "TA", "EAT", "TEA", "ATE", "RATE", "TEAR", "NEAT", "ANTE",
+        ];
+        let dict = Gaddag::from_words(words.map(String::from)).unwrap();
+        let score = Contender::parse("score", &Leaves::default()).unwrap();
+        let report = run(&dict, [&score, &score], 4, 3);
+        assert_eq!(report.games(), 4);
+        assert_eq!(report.wins + report.draws + report.losses, 4);
+        assert_eq!(report.names, ["score", "score"]);
+        assert!(Contender::parse("random", &Leaves::default()).is_none());
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use serde_derive::Deserialize;
+
+use crate::tile::Tile;
+
+/// The tiles a game's bag starts out with: a count per letter, a number of blanks and
+/// what each letter is worth. Defaults to the standard English distribution.
+#[derive(Deserialize, Clone)]
+pub struct Distribution {
+    pub letters: BTreeMap<char, usize>,
+    #[serde(default)]
+    pub blanks: usize,
+    /// Points per letter, the English ones for letters left out.
+    #[serde(default)]
+    pub values: BTreeMap<char, usize>,
+}
+
+impl Distribution {
+    /// Every tile in the distribution, in no particular order.
+    pub fn tiles(&self) -> Vec<Tile> {
+        self.letters
+            .iter()
+            .flat_map(|(&letter, &count)| vec![self.tile(letter); count])
+            .chain(vec![Tile::BLANK; self.blanks])
+            .collect()
+    }
+
+    /// The tile of `letter`, with what it's worth in this distribution.
+    pub fn tile(&self, letter: char) -> Tile {
+        let letter = letter.to_uppercase().next().unwrap_or(letter);
+        let value = self
+            .values
+            .get(&letter)
+            .copied()
+            .unwrap_or_else(|| english_value(letter));
+        Tile::new(letter, value)
+    }
+
+    /// The tile written `label`, see `Tile::label`.
+    pub fn written(&self, label: char) -> Tile {
+        match label {
+            '?' => Tile::BLANK,
+            label if label.is_lowercase() => {
+                Tile::BLANK.played_as(label.to_uppercase().next().unwrap_or(label))
+            }
+            label => self.tile(label),
+        }
+    }
+}
+
+/// What `letter` is worth in English, nothing for letters English doesn't have.
+pub fn english_value(letter: char) -> usize {
+    match letter {
+        'A' | 'E' | 'I' | 'L' | 'N' | 'O' | 'R' | 'S' | 'T' | 'U' => 1,
+        'D' | 'G' => 2,
+        'B' | 'C' | 'M' | 'P' => 3,
+        'F' | 'H' | 'V' | 'W' | 'Y' => 4,
+        'K' => 5,
+        'J' | 'X' => 8,
+        'Q' | 'Z' => 10,
+        _ => 0,
+    }
+}
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Self {
+            letters: BTreeMap::from([
+                ('A', 9),
+                ('B', 2),
+                ('C', 2),
+                ('D', 4),
+                ('E', 12),
+                ('F', 2),
+                ('G', 3),
+                ('H', 2),
+                ('I', 9),
+                ('J', 1),
+                ('K', 1),
+                ('L', 4),
+                ('M', 2),
+                ('N', 6),
+                ('O', 8),
+                ('P', 2),
+                ('Q', 1),
+                ('R', 6),
+                ('S', 4),
+                ('T', 6),
+                ('U', 4),
+                ('V', 2),
+                ('W', 2),
+                ('X', 1),
+                ('Y', 2),
+                ('Z', 1),
+            ]),
+            blanks: 2,
+            values: BTreeMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_the_english_bag() {
+        let tiles = Distribution::default().tiles();
+        assert_eq!(tiles.len(), 100);
+        assert_eq!(tiles.iter().filter(|tile| tile.is_blank).count(), 2);
+        assert_eq!(tiles.iter().map(|tile| tile.value).sum::<usize>(), 187);
+        assert_eq!(tiles.iter().filter(|tile| tile.letter == 'E').count(), 12);
+    }
+
+    #[test]
+    fn values_and_blanks_of_a_distribution_of_its_own() {
+        let distribution = Distribution {
+            letters: BTreeMap::from([('Å', 2), ('A', 3)]),
+            blanks: 2,
+            values: BTreeMap::from([('Å', 7)]),
+        };
+        let tiles = distribution.tiles();
+        assert_eq!(tiles.len(), 7);
+        assert_eq!(tiles.iter().filter(|tile| tile.is_blank).count(), 2);
+        assert_eq!(distribution.tile('å'), Tile::new('Å', 7));
+        assert_eq!(distribution.tile('a'), Tile::new('A', 1));
+        assert_eq!(distribution.tile('Ø').value, 0);
+    }
+
+    #[test]
+    fn written_reads_labels() {
+        let distribution = Distribution::default();
+        assert_eq!(distribution.written('Q'), Tile::new('Q', 10));
+        assert_eq!(distribution.written('?'), Tile::BLANK);
+        let played = distribution.written('q');
+        assert!(played.is_blank);
+        assert_eq!((played.letter, played.value), ('Q', 0));
+    }
+}
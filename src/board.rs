@@ -4,25 +4,57 @@ use std::{
 };
 
 use cursive::{
-    theme::{BaseColor::*, ColorStyle},
+    theme::{BaseColor::*, Color, ColorStyle, ColorType, Effect, Style},
     view::View,
     Printer, Vec2,
 };
 use itertools::Itertools;
+use serde_derive::Deserialize;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    dictionary::Dictionary, error::ScrabbleError, readout, rules::PlacementError, tile::Tile,
+    zobrist,
+};
+
+/// Squares a side of the largest board, a row of which fits a word of `Occupancy`.
+pub const MAX_SIZE: usize = 64;
+/// Squares a side of the smallest board a game is played on, which is enough for a
+/// full rack to be played across the center.
+pub const MIN_SIZE: usize = 7;
+
+// Columns between the brackets of a square, enough for double width glyphs.
+const GLYPH_WIDTH: usize = 2;
 
 #[derive(Clone)]
 pub struct Board {
+    anchors: HashSet<Vec2>,
+    // With the cross sum of each square, see `cross_sum`.
+    crosschecks: HashMap<(Vec2, Alignment), (CrossCheck, usize)>,
     focus: Vec2,
-    inserted: HashSet<Vec2>,
+    // A suggested play shown faintly on the board, see `show_ghost`.
+    ghost: Vec<(Vec2, Tile)>,
+    // The best score of a play putting a tile on each square, see `show_heat`.
+    heat: HashMap<Vec2, usize>,
+    // Squares holding a letter, tentative ones included.
+    occupied: Occupancy,
+    palette: Palette,
+    premiums: Premiums,
+    // Bumped whenever the committed letters change, see `revision`.
+    revision: u64,
+    // The Zobrist hash of the committed letters, see `hash`.
+    hash: u64,
     pub size: Vec2,
     tentative: HashSet<Vec2>,
     cells: Vec<Cell>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct Cell {
-    pub ch: Option<char>,
+    pub tile: Option<Tile>,
     pub mult: Option<Multiplier>,
+    /// Whether the premium is kept off screen until a tile is played on it.
+    pub hidden: bool,
 }
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq)]
@@ -33,14 +65,68 @@ pub enum Multiplier {
     Dl,
 }
 
-/// Represents the alignment that the placement of tiles on the board corresponds to.
+/// Set of letters that may be played on a vacant square without forming an invalid
+/// word perpendicular to the play, stored as a bitset over the alphabet.
 #[derive(PartialEq, Eq, Clone, Copy)]
+pub struct CrossCheck(u64);
+
+/// Represents the alignment that the placement of tiles on the board corresponds to.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Alignment {
     Horizontal,
     Vertical,
     Invalid,
 }
 
+/// How the board tells premiums, tentative letters and the focused square apart.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Palette {
+    /// Background colors for premiums and the theme's colors for everything else.
+    #[default]
+    Color,
+    /// Black and white only, with effects for premiums and the focus.
+    HighContrast,
+    /// No colors at all, only characters and effects.
+    Monochrome,
+}
+
+/// Labels and colors for each kind of premium square, in place of the palette's.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Premiums {
+    pub dl: PremiumLook,
+    pub tl: PremiumLook,
+    pub dw: PremiumLook,
+    pub tw: PremiumLook,
+}
+
+/// How an empty premium square is shown, e.g. "²" on a dark gray background. Colors
+/// are names, "red" or "light blue", or "#rrggbb".
+#[derive(Clone, Default, Deserialize)]
+#[serde(try_from = "RawLook")]
+pub struct PremiumLook {
+    label: Option<String>,
+    foreground: Option<Color>,
+    background: Option<Color>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct RawLook {
+    label: Option<String>,
+    foreground: Option<String>,
+    background: Option<String>,
+}
+
+/// The squares of a board as a bitset, a word per row with bit x standing for column
+/// x, so that neighborhoods are worked out a row at a time by shifting.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Occupancy {
+    rows: Vec<u64>,
+    width: usize,
+}
+
 pub enum Direction {
     Up,
     Right,
@@ -49,42 +135,186 @@ pub enum Direction {
 }
 
 impl Board {
+    /// A board of `size` squares a side.
     pub fn new(size: usize) -> Self {
-        let mut board = Self {
-            cells: vec![Cell::default(); size * size],
-            focus: Vec2::both_from((size - 1) / 2),
-            size: Vec2::both_from(size),
-            tentative: HashSet::new(),
-            inserted: HashSet::new(),
-        };
-        board.initialize_multipliers(size);
-        board
+        Self::rectangle(size, size)
     }
 
-    pub fn inserted(&self) -> &HashSet<Vec2> {
-        &self.inserted
+    /// A board `width` squares across and `height` down, with premiums laid out as on
+    /// the standard board, each quarter fitted to its own width and height.
+    pub fn rectangle(width: usize, height: usize) -> Self {
+        let mut board = Self::blank(Vec2::new(width, height));
+        board.initialize_multipliers();
+        board
     }
 
-    // BFS through the board to make sure it's all connected
-    pub fn is_connected(&self) -> bool {
-        let Some(&inserted) = self.inserted.iter().next() else {
-            return false;
-        };
-
-        let mut queue = Vec::new();
-        let mut visited = HashSet::new();
-        let is_occupied = |p: &&Vec2| self.letter_at(p).is_some();
-        queue.push(inserted);
-        while let Some(pos) = queue.pop() {
-            visited.insert(pos);
-            for neighbor in self.neighbors_satisfying_predicate(&pos, is_occupied) {
-                if !visited.contains(&neighbor) {
-                    queue.push(neighbor);
+    /// A board with the premiums of `layout`, a line per row of a character per square:
+    /// '.' for none, 'd' and 't' for double and triple letter, and 'D' and 'T' for double
+    /// and triple word. Boards can be any size up to `MAX_SIZE` either way, and needn't
+    /// be symmetric.
+    pub fn from_layout(layout: &str) -> Result<Self, ScrabbleError> {
+        let rows = layout
+            .lines()
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .collect::<Vec<_>>();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        if width == 0 || width > MAX_SIZE || rows.len() > MAX_SIZE {
+            return Err(ScrabbleError::Layout(format!(
+                "boards are from 1 to {MAX_SIZE} squares either way"
+            )));
+        }
+        let mut board = Self::blank(Vec2::new(width, rows.len()));
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(ScrabbleError::Layout(format!(
+                    "row {} isn't {width} squares wide like the first",
+                    y + 1
+                )));
+            }
+            for (x, square) in row.chars().enumerate() {
+                let mult = match square {
+                    '.' => None,
+                    'd' => Some(Multiplier::Dl),
+                    't' => Some(Multiplier::Tl),
+                    'D' => Some(Multiplier::Dw),
+                    'T' => Some(Multiplier::Tw),
+                    _ => {
+                        return Err(ScrabbleError::Layout(format!(
+                            "'{square}' on row {}",
+                            y + 1
+                        )))
+                    }
+                };
+                if let Some(cell) = board.cell_at_coords_mut(x, y) {
+                    cell.mult = mult;
                 }
             }
         }
+        Ok(board)
+    }
+
+    fn blank(size: Vec2) -> Self {
+        Self {
+            anchors: HashSet::new(),
+            crosschecks: HashMap::new(),
+            cells: vec![Cell::default(); size.x * size.y],
+            focus: size.map(|v| (v - 1) / 2),
+            ghost: Vec::new(),
+            heat: HashMap::new(),
+            palette: Palette::default(),
+            premiums: Premiums::default(),
+            revision: 0,
+            hash: 0,
+            size,
+            tentative: HashSet::new(),
+            occupied: Occupancy::new(size.x, size.y),
+        }
+    }
+
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
 
-        visited.len() == self.inserted.len()
+    /// Shows the premium squares with the labels and colors of `premiums` where set.
+    pub fn with_premiums(mut self, premiums: Premiums) -> Self {
+        self.premiums = premiums;
+        self
+    }
+
+    /// Hides every premium square until a tile is committed on it.
+    pub fn with_hidden_premiums(mut self) -> Self {
+        for cell in &mut self.cells {
+            cell.hidden = cell.mult.is_some();
+        }
+        self
+    }
+
+    /// Shows the tiles of a play faintly on their squares, without placing them, until
+    /// `clear_ghost`.
+    pub fn show_ghost(&mut self, tiles: Vec<(Vec2, Tile)>) {
+        self.ghost = tiles;
+    }
+
+    pub fn clear_ghost(&mut self) {
+        self.ghost.clear();
+    }
+
+    /// Shades the empty squares by the best score of a play putting a tile on them, as
+    /// `heat` has it, until `clear_heat`. The hottest third of the scores is shaded
+    /// darkest.
+    pub fn show_heat(&mut self, heat: HashMap<Vec2, usize>) {
+        self.heat = heat;
+    }
+
+    pub fn clear_heat(&mut self) {
+        self.heat.clear();
+    }
+
+    pub fn has_heat(&self) -> bool {
+        !self.heat.is_empty()
+    }
+
+    // How hot the square at `pos` is, from 0 to 2, if it's empty and any play reaches it.
+    fn heat_level(&self, pos: &Vec2) -> Option<usize> {
+        if self.letter_at(pos).is_some() {
+            return None;
+        }
+        let score = *self.heat.get(pos)?;
+        let hottest = self.heat.values().copied().max().unwrap_or(0).max(1);
+        Some((3 * score / hottest).min(2))
+    }
+
+    pub fn occupancy(&self) -> &Occupancy {
+        &self.occupied
+    }
+
+    /// Vacant squares adjacent to committed tiles, i.e. the squares every legal
+    /// placement after the first one has to cover.
+    pub fn anchors(&self) -> &HashSet<Vec2> {
+        &self.anchors
+    }
+
+    /// Letters allowed on the vacant square `pos` for a play along `alignment`.
+    pub fn crosscheck(&self, pos: &Vec2, alignment: Alignment) -> CrossCheck {
+        self.crosschecks
+            .get(&(*pos, alignment))
+            .map_or(CrossCheck::ANY, |&(check, _)| check)
+    }
+
+    /// Sum of the letter scores of the word a tile on the vacant square `pos` would
+    /// join, across a play along `alignment`, or None if it would join none.
+    pub fn cross_sum(&self, pos: &Vec2, alignment: Alignment) -> Option<usize> {
+        self.crosschecks
+            .get(&(*pos, alignment))
+            .map(|&(_, sum)| sum)
+    }
+
+    /// Whether any tiles have been committed to the board, tentative ones excluded.
+    /// Tells apart the states of the committed letters of this board, for what's worked
+    /// out from them to be kept until they change. Tentative letters don't count.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// The Zobrist hash of the committed letters, kept up to date as they're committed
+    /// and retracted, and the same for the same letters on the same squares however
+    /// they got there.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    pub fn has_committed(&self) -> bool {
+        self.occupied.len() > self.tentative.len()
+    }
+
+    // Flood fill through the board to make sure it's all connected
+    pub fn is_connected(&self) -> bool {
+        let Some(start) = self.occupied.iter().next() else {
+            return false;
+        };
+        self.occupied.connected(&start).len() == self.occupied.len()
     }
 
     pub fn move_focus(&mut self, dir: &Direction) {
@@ -97,52 +327,89 @@ impl Board {
             Direction::Left => self
                 .focus
                 .map_x(|x| if x > 0 { x } else { self.size.x } - 1),
-        }
-        .map(|v| v % self.size.x);
+        };
+        self.focus = Vec2::new(self.focus.x % self.size.x, self.focus.y % self.size.y);
     }
 
-    pub fn place_focused(&mut self, letter: char) -> Option<char> {
-        self.place_at(letter, &self.focus().clone())
+    pub fn place_focused(&mut self, tile: Tile) -> Option<Tile> {
+        self.place_at(tile, &self.focus().clone())
     }
 
-    pub fn place_at(&mut self, letter: char, pos: &Vec2) -> Option<char> {
-        let Some(cell) = self.cell_at_mut(pos) else {
-            return None;
-        };
-        let previous = cell.ch;
-        cell.ch = Some(letter);
-        self.inserted.insert(self.focus.clone());
-        self.tentative.insert(self.focus.clone());
-        previous
+    /// Places `tile` at `pos`, returning the tile it replaces as drawn, a blank going
+    /// back on the rack as a blank.
+    pub fn place_at(&mut self, tile: Tile, pos: &Vec2) -> Option<Tile> {
+        let cell = self.cell_at_mut(pos)?;
+        let previous = cell.tile.replace(tile);
+        self.occupied.insert(pos);
+        self.tentative.insert(*pos);
+        previous.map(Tile::drawn)
     }
 
-    pub fn place_focused_tentative(&mut self, letter: char) -> Result<Option<char>, &str> {
+    pub fn place_focused_tentative(&mut self, tile: Tile) -> Result<Option<Tile>, PlacementError> {
         if self.letter_at(self.focus()).is_some() && !self.tentative.contains(self.focus()) {
-            return Err("Cell occupied");
+            return Err(PlacementError::SquareOccupied);
         }
-        Ok(self.place_focused(letter))
+        Ok(self.place_focused(tile))
     }
 
     pub fn tentative(&self) -> &HashSet<Vec2> {
         &self.tentative
     }
 
+    /// Moves the focus to the next anchor in reading order, or the previous one if not
+    /// `forward`, wrapping around the board. Before the first placement the center is
+    /// the only anchor.
+    pub fn focus_anchor(&mut self, forward: bool) {
+        let mut anchors = if self.has_committed() {
+            self.anchors.iter().copied().collect::<Vec<_>>()
+        } else {
+            vec![self.center_pos()]
+        };
+        anchors.sort_by_key(|pos| (pos.y, pos.x));
+        let focus = (self.focus.y, self.focus.x);
+        let next = if forward {
+            anchors
+                .iter()
+                .find(|pos| (pos.y, pos.x) > focus)
+                .or(anchors.first())
+        } else {
+            anchors
+                .iter()
+                .rev()
+                .find(|pos| (pos.y, pos.x) < focus)
+                .or(anchors.last())
+        };
+        if let Some(&pos) = next {
+            self.focus = pos;
+        }
+    }
+
+    /// Moves the focus to `pos`, unless it's off the board.
+    pub fn focus_on(&mut self, pos: Vec2) -> bool {
+        let on_board = pos.x < self.size.x && pos.y < self.size.y;
+        if on_board {
+            self.focus = pos;
+        }
+        on_board
+    }
+
     pub fn focus(&self) -> &Vec2 {
         &self.focus
     }
 
-    pub fn clear_focused(&mut self) -> Option<char> {
-        self.clear_cell(&self.focus().clone())
+    /// Takes the tile off the focused square, as drawn.
+    pub fn clear_focused(&mut self) -> Option<Tile> {
+        self.clear_cell(&self.focus().clone()).map(Tile::drawn)
     }
 
-    fn clear_cell(&mut self, pos: &Vec2) -> Option<char> {
-        self.inserted.remove(pos);
+    fn clear_cell(&mut self, pos: &Vec2) -> Option<Tile> {
+        self.occupied.remove(pos);
         self.tentative.remove(pos);
         self.cell_at_mut(pos).and_then(|cell| cell.clear_letter())
     }
 
     pub fn focused_letter(&self) -> Option<char> {
-        self.focused_cell().ch
+        self.focused_cell().letter()
     }
 
     fn focused_cell(&self) -> &Cell {
@@ -150,25 +417,56 @@ impl Board {
     }
 
     pub fn letter_at(&self, pos: &Vec2) -> Option<char> {
-        self.cell_at(pos).and_then(|cell| cell.ch)
+        self.tile_at(pos).map(|tile| tile.letter)
+    }
+
+    pub fn tile_at(&self, pos: &Vec2) -> Option<Tile> {
+        self.cell_at(pos).and_then(|cell| cell.tile)
+    }
+
+    /// Like `letter_at`, but treats tentatively placed letters as vacant.
+    pub fn committed_letter_at(&self, pos: &Vec2) -> Option<char> {
+        self.committed_tile_at(pos).map(|tile| tile.letter)
+    }
+
+    /// Like `tile_at`, but treats tentatively placed tiles as vacant.
+    pub fn committed_tile_at(&self, pos: &Vec2) -> Option<Tile> {
+        if self.tentative.contains(pos) {
+            None
+        } else {
+            self.tile_at(pos)
+        }
+    }
+
+    /// Steps `delta` squares from `pos` along the alignment, returning None if that
+    /// leaves the board.
+    pub fn offset(&self, pos: &Vec2, alignment: Alignment, delta: isize) -> Option<Vec2> {
+        let (x, y) = match alignment {
+            Alignment::Horizontal => (pos.x as isize + delta, pos.y as isize),
+            Alignment::Vertical => (pos.x as isize, pos.y as isize + delta),
+            Alignment::Invalid => return None,
+        };
+        if x < 0 || y < 0 || x as usize >= self.size.x || y as usize >= self.size.y {
+            return None;
+        }
+        Some(Vec2::new(x as usize, y as usize))
     }
 
     fn cell_at(&self, pos: &Vec2) -> Option<&Cell> {
-        self.cells
-            .get(Self::coords_to_index(pos.x, pos.y, self.size.y))
+        self.cell_at_coords(pos.x, pos.y)
     }
 
     fn cell_at_mut(&mut self, pos: &Vec2) -> Option<&mut Cell> {
-        self.cells
-            .get_mut(Self::coords_to_index(pos.x, pos.y, self.size.y))
+        self.cell_at_coords_mut(pos.x, pos.y)
     }
 
     fn cell_at_coords(&self, x: usize, y: usize) -> Option<&Cell> {
-        self.cells.get(Self::coords_to_index(x, y, self.size.y))
+        self.cells.get(self.coords_to_index(x, y)?)
     }
 
     fn cell_at_coords_mut(&mut self, x: usize, y: usize) -> Option<&mut Cell> {
-        self.cells.get_mut(Self::coords_to_index(x, y, self.size.y))
+        let index = self.coords_to_index(x, y)?;
+        self.cells.get_mut(index)
     }
 
     pub fn center_pos(&self) -> Vec2 {
@@ -180,62 +478,186 @@ impl Board {
         self.neighbors_satisfying_predicate(pos, is_vacant)
     }
 
+    /// Returns the committed letters running contiguously before and after `pos`
+    /// along the alignment, in reading order.
+    pub fn adjacent_letters(&self, pos: &Vec2, alignment: Alignment) -> (String, String) {
+        let (before, after) = self.adjacent_tiles(pos, alignment);
+        let letters = |tiles: Vec<Tile>| tiles.iter().map(|tile| tile.letter).collect();
+        (letters(before), letters(after))
+    }
+
+    /// Like `adjacent_letters`, but the tiles themselves.
+    pub fn adjacent_tiles(&self, pos: &Vec2, alignment: Alignment) -> (Vec<Tile>, Vec<Tile>) {
+        let collect = |step: isize| {
+            let mut tiles = Vec::new();
+            let mut curr = self.offset(pos, alignment, step);
+            while let Some(tile) = curr.and_then(|p| self.committed_tile_at(&p)) {
+                tiles.push(tile);
+                curr = curr.and_then(|p| self.offset(&p, alignment, step));
+            }
+            tiles
+        };
+        (collect(-1).into_iter().rev().collect(), collect(1))
+    }
+
     fn neighbors_satisfying_predicate(
         &self,
         pos: &Vec2,
         predicate: impl FnMut(&&Vec2) -> bool,
     ) -> Vec<Vec2> {
-        let neighbors = vec![
-            pos.map_x(|x| x - 1),
-            pos.map_x(|x| x + 1),
-            pos.map_y(|y| y + 1),
-            pos.map_y(|y| y - 1),
+        let neighbors = [
+            self.offset(pos, Alignment::Horizontal, -1),
+            self.offset(pos, Alignment::Horizontal, 1),
+            self.offset(pos, Alignment::Vertical, 1),
+            self.offset(pos, Alignment::Vertical, -1),
         ];
 
-        neighbors.iter().filter(predicate).cloned().collect()
+        neighbors
+            .iter()
+            .flatten()
+            .filter(predicate)
+            .cloned()
+            .collect()
     }
 
     pub fn mult_at(&self, x: usize, y: usize) -> Option<Multiplier> {
         self.cell_at_coords(x, y).and_then(|cell| cell.mult)
     }
 
-    //
-    pub fn clear_tentative_from_board(&mut self) -> Vec<char> {
-        let mut cleared = Vec::new();
-        for pos in self.tentative.clone() {
-            cleared.push(self.clear_cell(&pos).unwrap());
+    /// The premium at `pos` as far as the players can see it.
+    pub fn visible_mult_at(&self, pos: &Vec2) -> Option<Multiplier> {
+        self.cell_at(pos).and_then(Cell::visible_mult)
+    }
+
+    /// Takes the tentative tiles off the board, as drawn.
+    pub fn clear_tentative_from_board(&mut self) -> Vec<Tile> {
+        mem::take(&mut self.tentative)
+            .iter()
+            .filter_map(|pos| self.clear_cell(pos))
+            .map(Tile::drawn)
+            .collect()
+    }
+
+    /// Commits the tentative letters to the board, updating the anchors and the
+    /// crosschecks of the rows and columns the letters were placed in.
+    pub fn commit_tentative(&mut self, dict: &impl Dictionary) {
+        let committed = mem::take(&mut self.tentative);
+        self.revision += 1;
+        for pos in &committed {
+            if let Some(cell) = self.cell_at_mut(pos) {
+                cell.hidden = false;
+            }
+            if let Some(tile) = self.tile_at(pos) {
+                self.hash ^= zobrist::square_key(pos, tile);
+            }
+        }
+        self.anchors = self.occupied.frontier().iter().collect();
+
+        let mut stale = HashSet::new();
+        for pos in &committed {
+            self.crosschecks.remove(&(*pos, Alignment::Horizontal));
+            self.crosschecks.remove(&(*pos, Alignment::Vertical));
+            for alignment in [Alignment::Horizontal, Alignment::Vertical] {
+                for step in [-1, 1] {
+                    let mut curr = *pos;
+                    while let Some(next) = self.offset(&curr, alignment, step) {
+                        if self.letter_at(&next).is_none() {
+                            // The word along `alignment` now ends next to this square,
+                            // which constrains plays across it.
+                            stale.insert((next, alignment.perpendicular()));
+                            break;
+                        }
+                        curr = next;
+                    }
+                }
+            }
+        }
+
+        for (pos, alignment) in stale {
+            self.update_crosscheck(dict, pos, alignment);
         }
-        self.tentative.clear();
-        cleared
     }
 
-    pub fn clear_tentative(&mut self) {
-        self.tentative.clear();
+    /// Takes committed letters back off the board and recomputes the anchors and
+    /// crosschecks. Tentative letters must have been cleared first.
+    pub fn retract(&mut self, positions: &[Vec2], dict: &impl Dictionary) {
+        for pos in positions {
+            if let Some(tile) = self.clear_cell(pos) {
+                self.hash ^= zobrist::square_key(pos, tile);
+            }
+        }
+        self.revision += 1;
+
+        self.anchors = self.occupied.frontier().iter().collect();
+        self.crosschecks.clear();
+        for pos in self.anchors.clone() {
+            self.update_crosscheck(dict, pos, Alignment::Horizontal);
+            self.update_crosscheck(dict, pos, Alignment::Vertical);
+        }
     }
 
-    fn cell_mut_at_coords(&mut self, x: usize, y: usize) -> Option<&mut Cell> {
-        self.cells.get_mut(Self::coords_to_index(x, y, self.size.y))
+    fn update_crosscheck(&mut self, dict: &impl Dictionary, pos: Vec2, alignment: Alignment) {
+        let (before, after) = self.adjacent_tiles(&pos, alignment.perpendicular());
+        if before.is_empty() && after.is_empty() {
+            self.crosschecks.remove(&(pos, alignment));
+            return;
+        }
+
+        let sum = before.iter().chain(&after).map(|tile| tile.value).sum();
+        let letters = |tiles: Vec<Tile>| tiles.iter().map(|tile| tile.letter).collect::<String>();
+        let (before, after) = (letters(before), letters(after));
+
+        // The fully reversed GADDAG entry of a word is its reversed suffix, the letter
+        // on this square, and then its reversed prefix.
+        let mut check = CrossCheck::NONE;
+        let Some(suffix_node) = after
+            .chars()
+            .rev()
+            .try_fold(dict.root(), |node, ch| dict.next_node(&node, ch))
+        else {
+            self.crosschecks.insert((pos, alignment), (check, sum));
+            return;
+        };
+        for (letter, node) in dict.transitions(&suffix_node) {
+            if before
+                .chars()
+                .rev()
+                .try_fold(node, |node, ch| dict.next_node(&node, ch))
+                .is_some_and(|node| dict.is_final(&node))
+            {
+                check.insert(letter);
+            }
+        }
+        self.crosschecks.insert((pos, alignment), (check, sum));
     }
 
-    fn initialize_multipliers(&mut self, size: usize) {
-        let half_way = (size - 1) / 2;
-        let init_mult = HashMap::from([
+    // Lays out the top left quarter, up to the middle row and column, as on the standard
+    // board and mirrors it into the others.
+    fn initialize_multipliers(&mut self) {
+        let Vec2 {
+            x: width,
+            y: height,
+        } = self.size;
+        let (half_x, half_y) = ((width - 1) / 2, (height - 1) / 2);
+        let (near_x, near_y) = (half_x.saturating_sub(1), half_y.saturating_sub(1));
+        let (nearer_x, nearer_y) = (half_x.saturating_sub(2), half_y.saturating_sub(2));
+        let init_mult = [
             (
                 Multiplier::Tw,
-                vec![Vec2::zero(), Vec2::new(0, half_way), Vec2::new(half_way, 0)],
+                vec![Vec2::zero(), Vec2::new(0, half_y), Vec2::new(half_x, 0)],
             ),
             (
                 Multiplier::Tl,
                 vec![
-                    Vec2::new(1, half_way - 2),
-                    Vec2::new(half_way - 2, 1),
-                    Vec2::new(half_way - 2, half_way - 2),
+                    Vec2::new(1, nearer_y),
+                    Vec2::new(nearer_x, 1),
+                    Vec2::new(nearer_x, nearer_y),
                 ],
             ),
             (
                 Multiplier::Dw,
-                (1..5)
-                    .into_iter()
+                // Up to the triple letters on the diagonal, four squares on a 15x15 board.
+                (1..nearer_x.min(nearer_y))
                     .map(|n| Vec2::new(n, n))
                     .collect::<Vec<Vec2>>(),
             ),
@@ -243,33 +665,38 @@ impl Board {
                 Multiplier::Dl,
                 vec![
                     Vec2::new(0, 3),
-                    Vec2::new(half_way, 3),
+                    Vec2::new(half_x, 3),
                     Vec2::new(3, 0),
-                    Vec2::new(3, half_way),
-                    Vec2::new(2, half_way - 1),
-                    Vec2::new(half_way - 1, 2),
-                    Vec2::new(half_way - 1, half_way - 1),
+                    Vec2::new(3, half_y),
+                    Vec2::new(2, near_y),
+                    Vec2::new(near_x, 2),
+                    Vec2::new(near_x, near_y),
                 ],
             ),
-        ]);
+        ];
 
         for (mult, positions) in &init_mult {
-            for pos in positions {
-                self.cell_at_mut(&pos).unwrap().mult = Some(mult.clone());
+            for pos in positions
+                .iter()
+                .filter(|pos| pos.x <= half_x && pos.y <= half_y)
+            {
+                if let Some(cell) = self.cell_at_mut(pos) {
+                    cell.mult = Some(*mult);
+                }
             }
         }
 
-        for y in 0..(half_way + 1) {
-            for x in 0..(half_way + 1) {
-                self.cell_at_coords_mut(size - x - 1, y).unwrap().mult =
+        for y in 0..=half_y {
+            for x in 0..=half_x {
+                self.cell_at_coords_mut(width - x - 1, y).unwrap().mult =
                     self.cell_at_coords(x, y).unwrap().mult;
             }
         }
 
-        for y in 0..(half_way + 1) {
-            for x in 0..(size) {
-                self.cell_at_coords_mut(x, size - y - 1).unwrap().mult =
-                    self.cell_at_coords_mut(x, y).unwrap().mult;
+        for y in 0..=half_y {
+            for x in 0..width {
+                self.cell_at_coords_mut(x, height - y - 1).unwrap().mult =
+                    self.cell_at_coords(x, y).unwrap().mult;
             }
         }
     }
@@ -277,8 +704,8 @@ impl Board {
     pub fn tentative_alignment(&self) -> Option<Alignment> {
         let mut tent = self.tentative.iter();
         match self.tentative.len() {
-            0 => return Some(Alignment::Invalid),
-            1 => return None,
+            0 => Some(Alignment::Invalid),
+            1 => None,
             2 => Some(Alignment::new(tent.next().unwrap(), tent.next().unwrap())),
             _ => {
                 let mut a = None;
@@ -294,215 +721,449 @@ impl Board {
         }
     }
 
-    pub fn collect_tentative(&mut self) -> Result<Vec<Vec<Cell>>, String> {
-        let horizontal_pred = |pos: &Vec2| pos.map_x(|x| x - 1);
-        let horizontal_succ = |pos: &Vec2| pos.map_x(|x| x + 1);
-        let vertical_pred = |pos: &Vec2| pos.map_y(|y| y - 1);
-        let vertical_succ = |pos: &Vec2| pos.map_y(|y| y + 1);
-
-        let mut mults_to_clear: Vec<Vec2> = Vec::new();
-        let res = match self.tentative_alignment() {
-            Some(Alignment::Horizontal) => Ok(self.collecter_aux(
-                &mut mults_to_clear,
-                horizontal_pred,
-                horizontal_succ,
-                vertical_pred,
-                vertical_succ,
-            )),
-            Some(Alignment::Vertical) => Ok(self.collecter_aux(
-                &mut mults_to_clear,
-                vertical_pred,
-                vertical_succ,
-                horizontal_pred,
-                horizontal_succ,
-            )),
-            None => {
-                let mut curr = *self.tentative.iter().next().unwrap();
-                let mut mults_to_clear_hori = Vec::new();
-                while let Some(_) = self.letter_at(&horizontal_pred(&curr)) {
-                    curr = horizontal_pred(&curr);
-                }
-                let mut hori = Vec::new();
-                while let Some(cell) = self.cell_at(&curr) {
-                    if cell.ch.is_none() {
-                        break;
-                    }
-                    hori.push(cell.clone());
-                    mults_to_clear_hori.push(curr.clone());
-                    curr = horizontal_succ(&curr);
-                }
+    /// Positions of the contiguous letters running through `pos` along the alignment.
+    pub fn run_through(&self, pos: &Vec2, alignment: Alignment) -> Vec<Vec2> {
+        let occupied = |p: &Vec2| self.letter_at(p).is_some();
+        let mut start = *pos;
+        while let Some(prev) = self.offset(&start, alignment, -1).filter(occupied) {
+            start = prev;
+        }
+        let mut run = vec![start];
+        while let Some(next) = self
+            .offset(&run[run.len() - 1], alignment, 1)
+            .filter(occupied)
+        {
+            run.push(next);
+        }
+        run
+    }
 
-                let mut curr = *self.tentative.iter().next().unwrap();
-                while let Some(_) = self.letter_at(&vertical_pred(&curr)) {
-                    curr = vertical_pred(&curr);
-                }
+    /// The cell at `pos` as it scores this turn: premiums only count under
+    /// tentative letters.
+    pub fn scoring_cell(&self, pos: &Vec2) -> Cell {
+        let cell = self.cell_at(pos).cloned().unwrap_or_default();
+        Cell {
+            mult: cell.mult.filter(|_| self.tentative.contains(pos)),
+            ..cell
+        }
+    }
 
-                let mut vert = Vec::new();
-                while let Some(cell) = self.cell_at(&curr) {
-                    if cell.ch.is_none() {
-                        break;
-                    }
-                    vert.push(cell.clone());
-                    mults_to_clear.push(curr.clone());
-                    curr = vertical_succ(&curr);
-                }
-                match (hori.len(), vert.len()) {
-                    (_, 1) => {
-                        mults_to_clear = mults_to_clear_hori;
-                        Ok(vec![hori])
-                    }
-                    (1, _) => Ok(vec![vert]),
-                    (_, _) => {
-                        mults_to_clear.append(&mut mults_to_clear_hori);
-                        Ok(vec![hori, vert])
-                    }
-                }
-            }
-            Some(Alignment::Invalid) => return Err("Letters not aligned".to_string()),
-        };
+    pub fn index_to_coords(&self, idx: usize) -> (usize, usize) {
+        (idx % self.size.x, idx / self.size.x)
+    }
 
-        if res.is_ok() {
-            for pos in mults_to_clear {
-                self.cell_mut_at_coords(pos.x, pos.y).unwrap().mult = None;
-            }
-        }
+    /// Where the square at `x` and `y` is in the cells, a row after the other, unless
+    /// it's off the board.
+    pub fn coords_to_index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.size.x && y < self.size.y).then_some(y * self.size.x + x)
+    }
+}
 
-        res
+impl Board {
+    fn draw_square(&self, printer: &Printer, at: (usize, usize), cell: &Cell) {
+        draw_square(printer, self.palette, &self.premiums, at, cell);
     }
 
-    fn collecter_aux(
-        &self,
-        mults_to_clear: &mut Vec<Vec2>,
-        outer_pred: impl Fn(&Vec2) -> Vec2,
-        outer_succ: impl Fn(&Vec2) -> Vec2,
-        inner_pred: impl Fn(&Vec2) -> Vec2,
-        inner_succ: impl Fn(&Vec2) -> Vec2,
-    ) -> Vec<Vec<Cell>> {
-        let mut word_cells: Vec<Vec<Cell>> = Vec::new();
-
-        let mut curr_main = *self.tentative.iter().next().unwrap();
-        while let Some(_) = self.letter_at(&outer_pred(&curr_main)) {
-            curr_main = outer_pred(&curr_main);
-        }
-
-        let mut main_cells: Vec<Cell> = Vec::new();
-        while let Some(cell) = self.cell_at(&curr_main) {
-            let mut inner_cells: Vec<Cell> = Vec::new();
-            if cell.ch.is_none() {
-                break;
-            }
-            main_cells.push(cell.clone());
-            mults_to_clear.push(curr_main.clone());
-            if self.tentative().contains(&curr_main) {
-                let mut curr = curr_main.clone();
-                match (
-                    self.letter_at(&inner_pred(&curr_main)),
-                    self.letter_at(&inner_succ(&curr_main)),
-                ) {
-                    (None, None) | (Some(_), Some(_)) => (),
-                    (Some(_), None) => {
-                        while let Some(cell) = self.cell_at(&curr) {
-                            if cell.ch.is_none() {
-                                break;
-                            }
-                            inner_cells.insert(0, cell.clone());
-                            mults_to_clear.insert(0, curr.clone());
-                            curr = inner_pred(&curr);
-                        }
-                        word_cells.push(inner_cells);
-                    }
-                    (None, Some(_)) => {
-                        while let Some(cell) = self.cell_at(&curr) {
-                            if cell.ch.is_none() {
-                                break;
-                            }
-                            inner_cells.push(cell.clone());
-                            mults_to_clear.push(curr.clone());
-                            curr = inner_succ(&curr);
-                        }
-                        word_cells.push(inner_cells);
-                    }
-                }
-            }
-            curr_main = outer_succ(&curr_main);
+    /// The key to the premium squares of this board, to be drawn beside it.
+    pub fn legend(&self) -> Legend {
+        Legend {
+            palette: self.palette,
+            premiums: self.premiums.clone(),
         }
-        word_cells.push(main_cells);
-
-        word_cells
     }
+}
 
-    pub fn index_to_coords(&self, idx: usize) -> (usize, usize) {
-        (idx % self.size.x, idx / self.size.y)
+// A square in the style of the palette, unless it's an empty premium square with a look
+// of its own.
+fn draw_square(
+    printer: &Printer,
+    palette: Palette,
+    premiums: &Premiums,
+    at: (usize, usize),
+    cell: &Cell,
+) {
+    let look = match (cell.tile, cell.visible_mult()) {
+        (None, Some(mult)) => Some((mult, premiums.look(mult))),
+        _ => None,
+    };
+    printer.with_style(palette.square_style(cell), |printer| match look {
+        Some((mult, look)) => printer.with_style(look.style(), |printer| {
+            printer.print(at, &format!("[{}]", look.label(mult)))
+        }),
+        None => printer.print(at, &cell.to_string()),
+    });
+}
+
+/// Each kind of premium square as the board shows it, with its name.
+pub struct Legend {
+    palette: Palette,
+    premiums: Premiums,
+}
+
+impl Legend {
+    const KINDS: [Multiplier; 4] = [
+        Multiplier::Tw,
+        Multiplier::Dw,
+        Multiplier::Tl,
+        Multiplier::Dl,
+    ];
+}
+
+impl View for Legend {
+    fn draw(&self, printer: &Printer) {
+        for (y, mult) in Self::KINDS.into_iter().enumerate() {
+            let cell = Cell {
+                mult: Some(mult),
+                ..Cell::default()
+            };
+            draw_square(printer, self.palette, &self.premiums, (0, y), &cell);
+            printer.print((Cell::size() + 1, y), readout::premium_name(mult));
+        }
     }
 
-    pub fn coords_to_index(x: usize, y: usize, col_len: usize) -> usize {
-        y * col_len + x
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        let name = Self::KINDS
+            .iter()
+            .map(|&mult| readout::premium_name(mult).width())
+            .max()
+            .unwrap_or(0);
+        Vec2::new(Cell::size() + 1 + name, Self::KINDS.len())
     }
 }
 
 impl View for Board {
     fn draw(&self, printer: &Printer) {
-        for (y, row) in self.cells.chunks(self.size.y).enumerate() {
+        for (y, row) in self.cells.chunks(self.size.x).enumerate() {
             for (x, cell) in row.iter().enumerate() {
-                printer.with_color(
-                    match cell.mult {
-                        _ if cell.ch.is_some() => ColorStyle::primary(),
-                        Some(Multiplier::Dl) => ColorStyle::new(Black, Blue),
-                        Some(Multiplier::Tl) => ColorStyle::new(Black, Blue.light()),
-                        Some(Multiplier::Dw) => ColorStyle::new(Black, Red),
-                        Some(Multiplier::Tw) => ColorStyle::new(Black, Red.light()),
-                        None => ColorStyle::primary(),
-                    },
-                    |printer| {
-                        printer.print((x * Cell::size(), y), &format!("{}", cell));
-                    },
-                );
+                self.draw_square(printer, (x * Cell::size(), y), cell);
+            }
+        }
+
+        for pos in self.heat.keys() {
+            if let Some(level) = self.heat_level(pos) {
+                printer.with_style(self.palette.heat_style(level), |printer| {
+                    printer.print((pos.x * Cell::size(), pos.y), &Palette::heat(level))
+                });
+            }
+        }
+
+        // Ghost letters give way to the letters placed since.
+        for (pos, tile) in &self.ghost {
+            if self.letter_at(pos).is_none() {
+                printer.with_style(self.palette.ghost_style(), |printer| {
+                    printer.print((pos.x * Cell::size(), pos.y), &self.palette.ghost(*tile))
+                });
             }
         }
 
         for pos in &self.tentative {
-            printer.with_color(ColorStyle::secondary(), |printer| {
-                printer.print(
-                    (4 * pos.x, pos.y),
-                    &format!("[{} ]", self.letter_at(pos).unwrap()),
-                )
-            });
+            if let Some(tile) = self.tile_at(pos) {
+                printer.with_style(self.palette.tentative_style(), |printer| {
+                    printer.print((pos.x * Cell::size(), pos.y), &self.palette.tentative(tile))
+                });
+            }
         }
 
         // Print the focused cell
         let Vec2 { x, y } = *self.focus();
-        printer.with_color(ColorStyle::highlight(), |printer| {
-            if let Some(ch) = self.focused_letter() {
-                printer.print((4 * x, y), &format!("[{} ]", ch));
-            } else {
-                printer.print((x * Cell::size(), y), &format!("{}", self.focused_cell()));
+        printer.with_style(self.palette.focus_style(), |printer| {
+            match self.focused_cell().tile {
+                Some(tile) if self.tentative.contains(self.focus()) => {
+                    printer.print((x * Cell::size(), y), &self.palette.tentative(tile))
+                }
+                Some(tile) => {
+                    printer.print((x * Cell::size(), y), &format!("[{}]", glyph(tile.label())))
+                }
+                None => printer.print((x * Cell::size(), y), &format!("{}", self.focused_cell())),
             }
         })
     }
 
     fn required_size(&mut self, _: Vec2) -> Vec2 {
-        self.size.map_x(|x| x * 4)
+        self.size.map_x(|x| x * Cell::size())
+    }
+}
+
+impl Palette {
+    fn square_style(self, cell: &Cell) -> Style {
+        match self {
+            Self::Color => match cell.visible_mult() {
+                _ if cell.tile.is_some() => ColorStyle::primary(),
+                Some(Multiplier::Dl) => ColorStyle::new(Black, Blue),
+                Some(Multiplier::Tl) => ColorStyle::new(Black, Blue.light()),
+                Some(Multiplier::Dw) => ColorStyle::new(Black, Red),
+                Some(Multiplier::Tw) => ColorStyle::new(Black, Red.light()),
+                None => ColorStyle::primary(),
+            }
+            .into(),
+            Self::HighContrast => Style::from(match (cell.tile, cell.visible_mult()) {
+                (None, Some(_)) => ColorStyle::new(Black, White.light()),
+                _ => ColorStyle::new(White.light(), Black),
+            })
+            .combine(Self::premium_effect(cell)),
+            Self::Monochrome => Self::premium_effect(cell),
+        }
+    }
+
+    // The premium labels already tell the squares apart, the effects make word
+    // premiums stand out from letter premiums at a glance.
+    fn premium_effect(cell: &Cell) -> Style {
+        match (cell.tile, cell.visible_mult()) {
+            (None, Some(Multiplier::Tw | Multiplier::Dw)) => Effect::Bold.into(),
+            (None, Some(Multiplier::Tl | Multiplier::Dl)) => Effect::Underline.into(),
+            _ => Style::none(),
+        }
+    }
+
+    fn tentative_style(self) -> Style {
+        match self {
+            Self::Color => ColorStyle::secondary().into(),
+            Self::HighContrast => {
+                Style::from(ColorStyle::new(White.light(), Black)).combine(Effect::Bold)
+            }
+            Self::Monochrome => Effect::Bold.into(),
+        }
+    }
+
+    fn ghost_style(self) -> Style {
+        match self {
+            Self::Color => Style::from(ColorStyle::secondary()).combine(Effect::Dim),
+            Self::HighContrast | Self::Monochrome => Effect::Dim.into(),
+        }
+    }
+
+    fn focus_style(self) -> Style {
+        match self {
+            Self::Color => ColorStyle::highlight().into(),
+            Self::HighContrast | Self::Monochrome => Effect::Reverse.into(),
+        }
+    }
+
+    // Without colors tentative letters are told apart from committed ones by their
+    // brackets.
+    fn tentative(self, tile: Tile) -> String {
+        match self {
+            Self::Color => format!("[{}]", glyph(tile.label())),
+            Self::HighContrast | Self::Monochrome => format!("({})", glyph(tile.label())),
+        }
+    }
+
+    // Heat is told by its shade in every palette, and by its color as well in color.
+    fn heat_style(self, level: usize) -> Style {
+        match (self, level) {
+            (Self::Color, 0) => ColorStyle::front(Green).into(),
+            (Self::Color, 1) => ColorStyle::front(Yellow).into(),
+            (Self::Color, _) => Style::from(ColorStyle::front(Red.light())).combine(Effect::Bold),
+            (Self::HighContrast, _) => ColorStyle::new(White.light(), Black).into(),
+            (Self::Monochrome, _) => Style::none(),
+        }
+    }
+
+    fn heat(level: usize) -> String {
+        let shade = ['░', '▒', '▓'][level.min(2)];
+        format!("[{shade}{shade}]")
+    }
+
+    // Ghost letters keep their brackets apart from tentative letters in every palette.
+    fn ghost(self, tile: Tile) -> String {
+        format!("{{{}}}", glyph(tile.label()))
+    }
+}
+
+impl Occupancy {
+    /// An empty set for a board `width` squares across, at most `MAX_SIZE`, and `height`
+    /// down.
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(
+            width <= MAX_SIZE,
+            "boards are at most {MAX_SIZE} squares wide"
+        );
+        Self {
+            rows: vec![0; height],
+            width,
+        }
+    }
+
+    pub fn contains(&self, pos: &Vec2) -> bool {
+        self.rows
+            .get(pos.y)
+            .is_some_and(|row| row >> pos.x & 1 == 1)
+    }
+
+    fn insert(&mut self, pos: &Vec2) {
+        if let Some(row) = self.rows.get_mut(pos.y).filter(|_| pos.x < self.width) {
+            *row |= 1 << pos.x;
+        }
+    }
+
+    fn remove(&mut self, pos: &Vec2) {
+        if let Some(row) = self.rows.get_mut(pos.y) {
+            *row &= !(1 << pos.x);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.iter().map(|row| row.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(|&row| row == 0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.rows.iter().enumerate().flat_map(|(y, &row)| {
+            (0..self.width)
+                .filter(move |x| row >> x & 1 == 1)
+                .map(move |x| Vec2::new(x, y))
+        })
+    }
+
+    // Columns of a row that are on the board.
+    fn mask(&self) -> u64 {
+        u64::MAX >> (MAX_SIZE - self.width)
+    }
+
+    // The squares of the set with the squares next to them along rows and columns.
+    fn grown(&self) -> Self {
+        let rows = (0..self.rows.len())
+            .map(|y| {
+                let row = self.rows[y];
+                let above = y.checked_sub(1).map_or(0, |y| self.rows[y]);
+                let below = self.rows.get(y + 1).copied().unwrap_or(0);
+                (row | row << 1 | row >> 1 | above | below) & self.mask()
+            })
+            .collect();
+        Self {
+            rows,
+            width: self.width,
+        }
+    }
+
+    /// The squares next to the set along rows and columns that are not in it, which
+    /// once the set is the committed tiles are the anchors.
+    pub fn frontier(&self) -> Self {
+        let mut frontier = self.grown();
+        for (row, &occupied) in frontier.rows.iter_mut().zip(&self.rows) {
+            *row &= !occupied;
+        }
+        frontier
+    }
+
+    /// The squares of the set reached from `pos` through the set.
+    pub fn connected(&self, pos: &Vec2) -> Self {
+        let mut reached = Self::new(self.width, self.rows.len());
+        if !self.contains(pos) {
+            return reached;
+        }
+        reached.insert(pos);
+        loop {
+            let mut next = reached.grown();
+            for (row, &occupied) in next.rows.iter_mut().zip(&self.rows) {
+                *row &= occupied;
+            }
+            if next == reached {
+                return reached;
+            }
+            reached = next;
+        }
+    }
+}
+
+impl Premiums {
+    fn look(&self, mult: Multiplier) -> &PremiumLook {
+        match mult {
+            Multiplier::Dl => &self.dl,
+            Multiplier::Tl => &self.tl,
+            Multiplier::Dw => &self.dw,
+            Multiplier::Tw => &self.tw,
+        }
+    }
+}
+
+impl PremiumLook {
+    fn label(&self, mult: Multiplier) -> String {
+        match &self.label {
+            Some(label) => pad(label),
+            None => mult.to_string(),
+        }
+    }
+
+    // Colors left unset keep the palette's.
+    fn style(&self) -> ColorStyle {
+        let color = |color: Option<Color>| color.map_or(ColorType::InheritParent, ColorType::from);
+        ColorStyle::new(color(self.foreground), color(self.background))
+    }
+}
+
+impl TryFrom<RawLook> for PremiumLook {
+    type Error = String;
+
+    fn try_from(raw: RawLook) -> Result<Self, Self::Error> {
+        let color = |name: Option<String>| match name {
+            Some(name) => Color::parse(&name)
+                .map(Some)
+                .ok_or(format!("unknown color \"{name}\"")),
+            None => Ok(None),
+        };
+        if let Some(label) = raw
+            .label
+            .as_ref()
+            .filter(|label| label.width() > GLYPH_WIDTH)
+        {
+            return Err(format!(
+                "premium label \"{label}\" is wider than {GLYPH_WIDTH} columns"
+            ));
+        }
+        Ok(Self {
+            label: raw.label,
+            foreground: color(raw.foreground)?,
+            background: color(raw.background)?,
+        })
     }
 }
 
 impl Cell {
-    pub fn clear_letter(&mut self) -> Option<char> {
-        mem::take(&mut self.ch)
+    pub fn visible_mult(&self) -> Option<Multiplier> {
+        self.mult.filter(|_| !self.hidden)
+    }
+
+    pub fn letter(&self) -> Option<char> {
+        self.tile.map(|tile| tile.letter)
+    }
+
+    pub fn clear_letter(&mut self) -> Option<Tile> {
+        mem::take(&mut self.tile)
     }
 
+    /// Columns a square takes up: a glyph between brackets.
     pub fn size() -> usize {
-        4
+        GLYPH_WIDTH + 2
     }
 }
 
+/// `letter` padded to the columns of a square's content, whatever the columns its glyph
+/// takes up. Letters wider than that are shown as they are.
+pub fn glyph(letter: char) -> String {
+    let width = letter.width().unwrap_or(1);
+    format!("{letter}{}", " ".repeat(GLYPH_WIDTH.saturating_sub(width)))
+}
+
+// `text` padded to the columns of a square's content.
+fn pad(text: &str) -> String {
+    format!(
+        "{text}{}",
+        " ".repeat(GLYPH_WIDTH.saturating_sub(text.width()))
+    )
+}
+
 impl fmt::Display for Cell {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
             "[{}]",
-            if let Some(ch) = self.ch {
-                String::from(ch) + " "
-            } else if let Some(mult) = self.mult {
+            if let Some(tile) = self.tile {
+                glyph(tile.label())
+            } else if let Some(mult) = self.visible_mult() {
                 mult.to_string()
             } else {
                 String::from("  ")
@@ -511,11 +1172,34 @@ impl fmt::Display for Cell {
     }
 }
 
-impl Default for Cell {
-    fn default() -> Self {
-        Self {
-            ch: None,
-            mult: None,
+impl CrossCheck {
+    pub const ANY: Self = Self(u64::MAX);
+    pub const NONE: Self = Self(0);
+
+    pub fn allows(&self, letter: char) -> bool {
+        *self == Self::ANY || Self::bit(letter).is_some_and(|bit| self.0 & bit != 0)
+    }
+
+    /// The letters allowed, in alphabetical order.
+    pub fn letters(&self) -> Vec<char> {
+        ('A'..='Z')
+            .chain('À'..='Þ')
+            .filter(|&letter| self.allows(letter))
+            .collect()
+    }
+
+    fn insert(&mut self, letter: char) {
+        if let Some(bit) = Self::bit(letter) {
+            self.0 |= bit;
+        }
+    }
+
+    // A-Z followed by the accented Latin-1 capitals, which covers the Swedish letters.
+    fn bit(letter: char) -> Option<u64> {
+        match letter {
+            'A'..='Z' => Some(1 << (letter as u32 - 'A' as u32)),
+            'À'..='Þ' => Some(1 << (26 + letter as u32 - 'À' as u32)),
+            _ => None,
         }
     }
 }
@@ -545,6 +1229,14 @@ impl fmt::Display for Multiplier {
 }
 
 impl Alignment {
+    pub fn perpendicular(self) -> Self {
+        match self {
+            Self::Horizontal => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+            Self::Invalid => Self::Invalid,
+        }
+    }
+
     fn new(a: &Vec2, b: &Vec2) -> Self {
         if a.x != b.x && a.y != b.y {
             Self::Invalid
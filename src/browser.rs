@@ -0,0 +1,150 @@
+//! The dictionary browser: the words of the lexicon beginning with what's typed, or with
+//! it anywhere in them, how many there are, and the definition of the one picked, for
+//! settling a dispute over a word without leaving the game.
+
+use std::{collections::HashMap, sync::Arc};
+
+use cursive::{
+    view::{Nameable, Resizable, Scrollable},
+    views::{Checkbox, Dialog, DummyView, EditView, LinearLayout, SelectView, TextView},
+    Cursive,
+};
+
+use crate::dictionary::Dictionary;
+
+// Words listed at most. How many match is told either way.
+const SHOWN: usize = 500;
+
+pub fn show(s: &mut Cursive, dict: impl Dictionary, definitions: Arc<HashMap<String, String>>) {
+    let (edited, checked) = (dict.clone(), dict);
+    let words = SelectView::<String>::new().on_select(move |s, word| {
+        let definition = match definitions.get(word) {
+            Some(definition) => format!("{word}: {definition}"),
+            None => format!("{word} has no definition in this lexicon."),
+        };
+        s.call_on_name("browser-definition", |view: &mut TextView| {
+            view.set_content(definition)
+        });
+    });
+    s.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(
+                    EditView::new()
+                        .on_edit(move |s, _, _| search(s, &edited))
+                        .with_name("browser-query"),
+                )
+                .child(
+                    LinearLayout::horizontal()
+                        .child(
+                            Checkbox::new()
+                                .on_change(move |s, _| search(s, &checked))
+                                .with_name("browser-anywhere"),
+                        )
+                        .child(TextView::new(" Anywhere in the word")),
+                )
+                .child(TextView::new("Type the start of a word.").with_name("browser-count"))
+                .child(DummyView)
+                .child(
+                    words
+                        .with_name("browser-words")
+                        .scrollable()
+                        .fixed_height(12),
+                )
+                .child(DummyView)
+                .child(TextView::new("").with_name("browser-definition")),
+        )
+        .title("Dictionary")
+        .dismiss_button("Close")
+        .fixed_width(50),
+    );
+}
+
+// Lists the words matching the query as the browser has it.
+fn search(s: &mut Cursive, dict: &impl Dictionary) {
+    let query = s
+        .find_name::<EditView>("browser-query")
+        .map(|view| view.get_content().trim().to_uppercase())
+        .unwrap_or_default();
+    let anywhere = s
+        .find_name::<Checkbox>("browser-anywhere")
+        .is_some_and(|view| view.is_checked());
+    let found = if query.is_empty() {
+        Vec::new()
+    } else if anywhere {
+        dict.words_containing(&query)
+    } else {
+        dict.words_with_prefix(&query)
+    };
+    let count = match found.len() {
+        _ if query.is_empty() => "Type the start of a word.".to_string(),
+        1 => "1 word".to_string(),
+        n if n > SHOWN => format!("{n} words, the first {SHOWN} listed"),
+        n => format!("{n} words"),
+    };
+    s.call_on_name("browser-count", |view: &mut TextView| {
+        view.set_content(count)
+    });
+    s.call_on_name("browser-definition", |view: &mut TextView| {
+        view.set_content("")
+    });
+    s.call_on_name("browser-words", |view: &mut SelectView<String>| {
+        view.clear();
+        view.add_all_str(found.into_iter().take(SHOWN));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gaddag::Gaddag;
+
+    fn dict() -> Gaddag {
+        Gaddag::from_words(["CARE", "CARES", "SCARE", "RACE"].map(String::from)).unwrap()
+    }
+
+    fn browse(query: &str, anywhere: bool) -> (String, Vec<String>) {
+        let mut s = Cursive::new();
+        let definitions = HashMap::from([("CARE".to_string(), "concern".to_string())]);
+        show(&mut s, dict(), Arc::new(definitions));
+        s.call_on_name("browser-query", |view: &mut EditView| {
+            view.set_content(query)
+        });
+        s.call_on_name("browser-anywhere", |view: &mut Checkbox| {
+            view.set_checked(anywhere)
+        });
+        search(&mut s, &dict());
+        let count = s
+            .call_on_name("browser-count", |view: &mut TextView| {
+                view.get_content().source().to_string()
+            })
+            .unwrap();
+        let words = s
+            .call_on_name("browser-words", |view: &mut SelectView<String>| {
+                view.iter().map(|(word, _)| word.to_string()).collect()
+            })
+            .unwrap();
+        (count, words)
+    }
+
+    #[test]
+    fn words_are_found_by_their_start_or_anywhere() {
+        assert_eq!(
+            browse("car", false),
+            (
+                "2 words".to_string(),
+                vec!["CARE".to_string(), "CARES".to_string()]
+            )
+        );
+        assert_eq!(
+            browse("SCA", false),
+            ("1 word".to_string(), vec!["SCARE".to_string()])
+        );
+        assert_eq!(browse("ARE", true).1, ["CARE", "CARES", "SCARE"]);
+        assert_eq!(
+            browse(" ", true),
+            ("Type the start of a word.".to_string(), vec![])
+        );
+        assert_eq!(browse("Q", false).0, "0 words");
+    }
+}
@@ -0,0 +1,108 @@
+//! Plain text line charts of how a game went, drawn from its record.
+
+use crate::gcg::Turn;
+
+const MARKS: [char; 4] = ['*', 'o', '+', 'x'];
+
+/// Chart of every player's score after each turn, `height` lines tall, with a legend
+/// naming the mark of each player.
+pub fn scores(names: &[&str], turns: &[Turn], height: usize) -> String {
+    let mut totals = vec![0; names.len()];
+    let series = turns
+        .iter()
+        .map(|turn| {
+            totals[turn.player] = turn.total;
+            totals.clone()
+        })
+        .collect::<Vec<_>>();
+    let legend = names
+        .iter()
+        .zip(MARKS.iter().cycle())
+        .map(|(name, mark)| format!("{mark} {name}"))
+        .collect::<Vec<_>>()
+        .join("  ");
+    format!("{}\n{legend}", plot(&series, height))
+}
+
+/// Chart of the lead of the first player over the second after each turn.
+pub fn spread(turns: &[Turn], height: usize) -> String {
+    let mut totals = [0, 0];
+    let series = turns
+        .iter()
+        .filter(|turn| turn.player < 2)
+        .map(|turn| {
+            totals[turn.player] = turn.total;
+            vec![totals[0] - totals[1]]
+        })
+        .collect::<Vec<_>>();
+    plot(&series, height)
+}
+
+// Plots a column per entry of `series`, each holding a value per line, with the scale
+// on the left. Where lines meet the mark of the earlier one wins.
+fn plot(series: &[Vec<isize>], height: usize) -> String {
+    let height = height.max(2);
+    let values = series.iter().flatten();
+    let (Some(&max), Some(&min)) = (values.clone().max(), values.min()) else {
+        return "Nothing played yet.".to_string();
+    };
+    let range = (max - min).max(1);
+    let row_of = |value: isize| ((max - value) * (height as isize - 1) / range) as usize;
+
+    let mut grid = vec![vec![' '; series.len()]; height];
+    for (x, column) in series.iter().enumerate() {
+        for (line, &value) in column.iter().enumerate().rev() {
+            grid[row_of(value)][x] = MARKS[line % MARKS.len()];
+        }
+    }
+
+    let label_width = max.to_string().len().max(min.to_string().len());
+    grid.iter()
+        .enumerate()
+        .map(|(y, row)| {
+            let label = match y {
+                0 => max.to_string(),
+                y if y == height - 1 => min.to_string(),
+                _ => String::new(),
+            };
+            format!("{label:>label_width$} |{}", row.iter().collect::<String>())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gcg::Action;
+
+    fn turns() -> Vec<Turn> {
+        [(0, 10), (1, 20)]
+            .into_iter()
+            .map(|(player, total)| Turn {
+                player,
+                rack: Vec::new(),
+                action: Action::Pass,
+                score: 0,
+                total,
+                notes: Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn scores_are_plotted_per_player() {
+        let chart = scores(&["Ann", "Bo"], &turns(), 3);
+        assert_eq!(chart, "20 | o\n   |**\n 0 |o \n* Ann  o Bo");
+    }
+
+    #[test]
+    fn spread_is_the_lead_of_the_first_player() {
+        assert_eq!(spread(&turns(), 3), " 10 |* \n    |  \n-10 | *");
+    }
+
+    #[test]
+    fn empty_record_has_nothing_to_plot() {
+        assert_eq!(spread(&[], 10), "Nothing played yet.");
+    }
+}
@@ -0,0 +1,198 @@
+//! The game clock. Every player has a time budget for the whole game, which runs down
+//! on their turns, and loses points for going over it. The budget can grow by an
+//! increment after every turn, and a delay at the start of every turn can go by before
+//! it starts running down.
+
+use std::time::{Duration, Instant};
+
+use serde_derive::Deserialize;
+
+/// Points lost for every minute, or part of a minute, a player goes over their budget.
+pub const OVERTIME_PENALTY: usize = 10;
+
+/// The clock of a game, under `[rules.clock]`: a preset budget, and the increment and
+/// delay in seconds.
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+pub struct ClockOptions {
+    pub preset: Preset,
+    /// Seconds added to a player's budget after each of their turns (Fischer).
+    pub increment: u64,
+    /// Seconds of each turn that go by before the budget starts running down.
+    pub delay: u64,
+}
+
+impl ClockOptions {
+    pub fn control(self) -> TimeControl {
+        TimeControl {
+            increment: Duration::from_secs(self.increment),
+            delay: Duration::from_secs(self.delay),
+            ..self.preset.control()
+        }
+    }
+}
+
+/// The time budgets that can be picked on the setup screen.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    #[default]
+    Untimed,
+    /// 25 minutes per player, as in most tournaments.
+    Tournament,
+    /// 3 minutes per player.
+    Blitz,
+}
+
+impl Preset {
+    pub const ALL: [Self; 3] = [Self::Untimed, Self::Tournament, Self::Blitz];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Untimed => "Untimed",
+            Self::Tournament => "Tournament, 25 min",
+            Self::Blitz => "Blitz, 3 min",
+        }
+    }
+
+    pub fn control(self) -> TimeControl {
+        let minutes = |minutes: u64| Some(Duration::from_secs(minutes * 60));
+        TimeControl {
+            budget: match self {
+                Self::Untimed => None,
+                Self::Tournament => minutes(25),
+                Self::Blitz => minutes(3),
+            },
+            increment: Duration::ZERO,
+            delay: Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct TimeControl {
+    /// Time each player has for the game, or none for untimed games.
+    pub budget: Option<Duration>,
+    pub increment: Duration,
+    pub delay: Duration,
+}
+
+pub struct Clock {
+    control: TimeControl,
+    used: Vec<Duration>,
+    // The increments earned, on top of the budget.
+    earned: Vec<Duration>,
+    turn_started: Instant,
+}
+
+impl Clock {
+    pub fn new(control: TimeControl, players: usize) -> Self {
+        Self {
+            control,
+            used: vec![Duration::ZERO; players],
+            earned: vec![Duration::ZERO; players],
+            turn_started: Instant::now(),
+        }
+    }
+
+    /// Ends the turn of `player`, charging it the time since the last turn ended, less
+    /// the delay, and crediting it the increment.
+    pub fn end_turn(&mut self, player: usize) {
+        let now = Instant::now();
+        let charged = self.charged(now - self.turn_started);
+        self.used[player] += charged;
+        self.earned[player] += self.control.increment;
+        self.turn_started = now;
+    }
+
+    fn charged(&self, turn: Duration) -> Duration {
+        turn.saturating_sub(self.control.delay)
+    }
+
+    /// What's left of the budget of `player`, negative once it's used up, counting the
+    /// turn under way if it's theirs.
+    pub fn left(&self, player: usize, on_turn: bool) -> Option<(Duration, bool)> {
+        let budget = self.control.budget? + self.earned[player];
+        let mut used = self.used[player];
+        if on_turn {
+            used += self.charged(self.turn_started.elapsed());
+        }
+        Some(match budget.checked_sub(used) {
+            Some(left) => (left, false),
+            None => (used - budget, true),
+        })
+    }
+
+    /// Points `player` loses for the time they went over their budget.
+    pub fn penalty(&self, player: usize) -> usize {
+        match self.left(player, false) {
+            Some((over, true)) => OVERTIME_PENALTY * over.as_secs().div_ceil(60) as usize,
+            _ => 0,
+        }
+    }
+}
+
+/// Time left as "m:ss", with a minus sign once it's overtime.
+pub fn format(left: Duration, overtime: bool) -> String {
+    let secs = left.as_secs();
+    format!(
+        "{}{}:{:02}",
+        if overtime { "-" } else { "" },
+        secs / 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blitz() -> Clock {
+        let options: ClockOptions =
+            toml::from_str("preset = \"blitz\"\nincrement = 5\ndelay = 2").unwrap();
+        Clock::new(options.control(), 2)
+    }
+
+    #[test]
+    fn untimed_games_have_no_budget() {
+        let clock = Clock::new(Preset::Untimed.control(), 2);
+        assert!(clock.left(0, true).is_none());
+        assert_eq!(clock.penalty(0), 0);
+    }
+
+    #[test]
+    fn increments_are_earned_and_delays_are_free() {
+        let mut clock = blitz();
+        assert_eq!(clock.charged(Duration::from_secs(1)), Duration::ZERO);
+        assert_eq!(
+            clock.charged(Duration::from_secs(12)),
+            Duration::from_secs(10)
+        );
+        clock.end_turn(0);
+        assert_eq!(
+            clock.left(0, false),
+            Some((Duration::from_secs(185), false))
+        );
+        assert_eq!(
+            clock.left(1, false),
+            Some((Duration::from_secs(180), false))
+        );
+    }
+
+    #[test]
+    fn overtime_costs_points_per_minute_begun() {
+        let mut clock = blitz();
+        clock.used[1] = Duration::from_secs(181);
+        assert_eq!(clock.left(1, false), Some((Duration::from_secs(1), true)));
+        assert_eq!(clock.penalty(1), OVERTIME_PENALTY);
+        clock.used[1] = Duration::from_secs(300);
+        assert_eq!(clock.penalty(1), 2 * OVERTIME_PENALTY);
+        assert_eq!(clock.penalty(0), 0);
+    }
+
+    #[test]
+    fn time_is_shown_in_minutes_and_seconds() {
+        assert_eq!(format(Duration::from_secs(185), false), "3:05");
+        assert_eq!(format(Duration::from_secs(59), true), "-0:59");
+    }
+}
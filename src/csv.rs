@@ -0,0 +1,150 @@
+//! Scores and moves written as CSV for spreadsheets, a row per turn:
+//!
+//! ```text
+//! turn,player,move,word,score,cumulative
+//! 1,John,8D NASTIER,NASTIER,64,64
+//! ```
+//!
+//! with the word left empty for turns that play none. Games from the history are
+//! written together, each row led by the game's number, date and lexicon.
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use crate::{
+    archive::Entry,
+    gcg::{self, Record, Turn},
+};
+
+const TURN_COLUMNS: &str = "turn,player,move,word,score,cumulative";
+
+/// Writes the turns of a game between `players`, in seat order.
+pub fn write(players: &[&str], turns: &[Turn], mut out: impl Write) -> io::Result<()> {
+    writeln!(out, "{TURN_COLUMNS}")?;
+    for row in rows(players, turns) {
+        writeln!(out, "{row}")?;
+    }
+    Ok(())
+}
+
+/// Writes the turns of every game in `games`, with the entry each was kept under.
+pub fn write_games(games: &[(Entry, Record)], mut out: impl Write) -> io::Result<()> {
+    writeln!(out, "game,date,lexicon,{TURN_COLUMNS}")?;
+    for (entry, record) in games {
+        let players = record
+            .players
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        for row in rows(&players, &record.turns) {
+            writeln!(
+                out,
+                "{},{},{},{row}",
+                entry.id,
+                field(&entry.played),
+                field(&entry.lexicon)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// The turn columns of every turn, joined.
+fn rows(players: &[&str], turns: &[Turn]) -> Vec<String> {
+    let words = gcg::words(turns).into_iter().collect::<HashMap<_, _>>();
+    turns
+        .iter()
+        .enumerate()
+        .map(|(i, turn)| {
+            [
+                (i + 1).to_string(),
+                field(players.get(turn.player).copied().unwrap_or_default()),
+                field(&turn.action.to_string()),
+                words.get(&i).cloned().unwrap_or_default(),
+                turn.score.to_string(),
+                turn.total.to_string(),
+            ]
+            .join(",")
+        })
+        .collect()
+}
+
+// Quotes a field holding a separator, a quote or a line break.
+fn field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gcg::Action;
+
+    fn turns() -> Vec<Turn> {
+        let turn = |player, action, score, total| Turn {
+            player,
+            rack: Vec::new(),
+            action,
+            score,
+            total,
+            notes: Vec::new(),
+        };
+        vec![
+            turn(
+                0,
+                Action::Play {
+                    coordinate: "8D".to_string(),
+                    word: "NASTIER".to_string(),
+                },
+                64,
+                64,
+            ),
+            turn(1, Action::Exchange(vec!['Q', 'U']), 0, 0),
+        ]
+    }
+
+    #[test]
+    fn rows_are_written_per_turn_with_names_quoted() {
+        let mut out = Vec::new();
+        write(&["John", "Smith, \"Jo\""], &turns(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "turn,player,move,word,score,cumulative\n\
+             1,John,8D NASTIER,NASTIER,64,64\n\
+             2,\"Smith, \"\"Jo\"\"\",-QU,,0,0\n"
+        );
+    }
+
+    #[test]
+    fn games_are_led_by_their_entry() {
+        let entry = Entry {
+            id: 3,
+            played: "2024-01-02 10:00".to_string(),
+            players: "John,Jo".to_string(),
+            scores: "64,0".to_string(),
+            lexicon: "CSW21".to_string(),
+        };
+        let record = Record {
+            players: vec!["John".to_string(), "Jo".to_string()],
+            turns: turns(),
+        };
+        let mut out = Vec::new();
+        write_games(&[(entry, record)], &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "game,date,lexicon,turn,player,move,word,score,cumulative"
+        );
+        assert_eq!(
+            lines[1],
+            "3,2024-01-02 10:00,CSW21,1,John,8D NASTIER,NASTIER,64,64"
+        );
+        assert_eq!(lines.len(), 3);
+    }
+}
@@ -0,0 +1,232 @@
+//! What the move generator and the game need of a dictionary, so other backends than
+//! the FST of `Gaddag` can be swapped in.
+//!
+//! A dictionary is a graph of GADDAG paths: each word is spelled by a path for every
+//! place it can be split, the letters before the split reversed, then the separator,
+//! then the letters after it, and by one more of the whole word reversed. CARES is
+//! ERAC+S, RAC+ES, AC+RES, C+ARES and SERAC. A path spells an entry if it ends at a
+//! final node. Implementations provide the traversal and get the rest from it.
+
+use std::collections::BTreeSet;
+
+/// Splits the letters played before the anchor from those played after it.
+pub const SEP: char = '+';
+
+pub trait Dictionary: Clone + Send + Sync + 'static {
+    /// A place in the graph, reached by following letters from `root`.
+    type Node: Copy;
+
+    fn root(&self) -> Self::Node;
+
+    /// Follows `next`, a letter or the separator, out of `node`.
+    fn next_node(&self, node: &Self::Node, next: char) -> Option<Self::Node>;
+
+    fn is_final(&self, node: &Self::Node) -> bool;
+
+    /// Every letter leading out of the node together with the node it leads to, the
+    /// separator included, in alphabetical order.
+    fn edges(&self, node: &Self::Node) -> Vec<(char, Self::Node)>;
+
+    /// The node `path` leads to from the root, if it's a prefix of some entry.
+    fn node_for_prefix(&self, path: &str) -> Option<Self::Node> {
+        path.chars()
+            .try_fold(self.root(), |node, next| self.next_node(&node, next))
+    }
+
+    fn accepts(&self, word: &str) -> bool {
+        let reversed = word.chars().rev().collect::<String>();
+        self.node_for_prefix(&reversed)
+            .is_some_and(|node| self.is_final(&node))
+    }
+
+    /// Whether some word in the dictionary begins with `prefix`, the word itself
+    /// included.
+    fn starts_word(&self, prefix: &str) -> bool {
+        let reversed = prefix.chars().rev().collect::<String>();
+        self.accepts(prefix) || self.node_for_prefix(&format!("{reversed}{SEP}")).is_some()
+    }
+
+    /// Returns every letter leading out of the node together with the node it leads to,
+    /// leaving out the separator.
+    fn transitions(&self, node: &Self::Node) -> Vec<(char, Self::Node)> {
+        self.edges(node)
+            .into_iter()
+            .filter(|&(letter, _)| letter != SEP)
+            .collect()
+    }
+
+    /// Iterates over the original word list. Each word is stored reversed once without
+    /// a separator among the paths through it, so only the paths that never cross one
+    /// are followed. Words come sorted by their reversed spelling.
+    fn iter_words(&self) -> Words<'_, Self> {
+        Words {
+            dict: self,
+            stack: vec![(self.root(), String::new())],
+        }
+    }
+
+    /// Every word of length `len` in the dictionary, in the order of `iter_words`.
+    fn words(&self, len: usize) -> Vec<String> {
+        self.iter_words()
+            .filter(|word| word.chars().count() == len)
+            .collect()
+    }
+
+    /// Every word in the dictionary, in the order of `iter_words`.
+    fn all_words(&self) -> Vec<String> {
+        self.iter_words().collect()
+    }
+
+    /// Every word beginning with `prefix`, the prefix itself included, sorted. The rest
+    /// of each follows the path of the prefix reversed and the separator.
+    fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let reversed = prefix.chars().rev().collect::<String>();
+        let mut words = BTreeSet::new();
+        if self.accepts(prefix) {
+            words.insert(prefix.to_string());
+        }
+        if let Some(node) = self.node_for_prefix(&format!("{reversed}{SEP}")) {
+            paths(self, node, |path| {
+                words.insert(format!("{prefix}{path}"));
+            });
+        }
+        words.into_iter().collect()
+    }
+
+    /// Every word with `infix` anywhere in it, sorted. A word has a path starting with
+    /// the infix reversed for every place it's in: its beginning up to the end of the
+    /// infix reversed, then the separator and the rest if there is any.
+    fn words_containing(&self, infix: &str) -> Vec<String> {
+        if infix.is_empty() {
+            return Vec::new();
+        }
+        let reversed = infix.chars().rev().collect::<String>();
+        let mut words = BTreeSet::new();
+        if let Some(node) = self.node_for_prefix(&reversed) {
+            paths(self, node, |path| {
+                let (before, after) = path.split_once(SEP).unwrap_or((path, ""));
+                let beginning = reversed.chars().chain(before.chars()).rev();
+                words.insert(beginning.chain(after.chars()).collect());
+            });
+        }
+        words.into_iter().collect()
+    }
+}
+
+/// Iterator over the words of a dictionary, see `Dictionary::iter_words`.
+pub struct Words<'a, D: Dictionary> {
+    dict: &'a D,
+    // Nodes left to visit with the reversed letters leading to them.
+    stack: Vec<(D::Node, String)>,
+}
+
+impl<D: Dictionary> Iterator for Words<'_, D> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        while let Some((node, path)) = self.stack.pop() {
+            // Pushed last to first, to be visited in order.
+            for (letter, next) in self.dict.transitions(&node).into_iter().rev() {
+                self.stack.push((next, format!("{path}{letter}")));
+            }
+            if self.dict.is_final(&node) && !path.is_empty() {
+                return Some(path.chars().rev().collect());
+            }
+        }
+        None
+    }
+}
+
+// Calls `found` with every path from `node` to a final node, `node` itself included.
+fn paths<D: Dictionary>(dict: &D, node: D::Node, mut found: impl FnMut(&str)) {
+    let mut stack = vec![(node, String::new())];
+    while let Some((node, path)) = stack.pop() {
+        for (letter, next) in dict.edges(&node) {
+            stack.push((next, format!("{path}{letter}")));
+        }
+        if dict.is_final(&node) {
+            found(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gaddag::{Gaddag, Node};
+
+    const WORDS: [&str; 6] = ["CARE", "CARES", "SCARE", "RACE", "ACE", "AA"];
+
+    // A Gaddag without its own lookups, walking the graph as any backend would.
+    #[derive(Clone)]
+    struct Walked(Gaddag);
+
+    impl Dictionary for Walked {
+        type Node = Node;
+
+        fn root(&self) -> Node {
+            self.0.root()
+        }
+
+        fn next_node(&self, node: &Node, next: char) -> Option<Node> {
+            self.0.next_node(node, next)
+        }
+
+        fn is_final(&self, node: &Node) -> bool {
+            self.0.is_final(node)
+        }
+
+        fn edges(&self, node: &Node) -> Vec<(char, Node)> {
+            self.0.edges(node)
+        }
+    }
+
+    fn walked() -> Walked {
+        Walked(Gaddag::from_words(WORDS.map(String::from)).unwrap())
+    }
+
+    #[test]
+    fn accepts_by_walking_the_reversed_word() {
+        let dict = walked();
+        for word in WORDS {
+            assert!(dict.accepts(word), "{word}");
+        }
+        for word in ["CAR", "ARES", "CARESS", "A", ""] {
+            assert!(!dict.accepts(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn iter_words_gives_back_the_word_list() {
+        let mut words = walked().all_words();
+        words.sort();
+        let mut expected = WORDS.map(String::from).to_vec();
+        expected.sort();
+        assert_eq!(words, expected);
+        assert_eq!(walked().words(4), ["RACE", "CARE"]);
+    }
+
+    #[test]
+    fn transitions_leave_out_the_separator() {
+        let dict = walked();
+        let node = dict.node_for_prefix("AC").unwrap();
+        assert!(dict.edges(&node).iter().any(|&(letter, _)| letter == SEP));
+        assert!(dict
+            .transitions(&node)
+            .iter()
+            .all(|&(letter, _)| letter != SEP));
+    }
+
+    #[test]
+    fn prefixes_and_infixes() {
+        let dict = walked();
+        assert!(dict.starts_word("CAR"));
+        assert!(dict.starts_word("CARE"));
+        assert!(!dict.starts_word("ARC"));
+        assert_eq!(dict.words_with_prefix("CAR"), ["CARE", "CARES"]);
+        assert!(dict.words_with_prefix("X").is_empty());
+        assert_eq!(dict.words_containing("CAR"), ["CARE", "CARES", "SCARE"]);
+        assert_eq!(dict.words_containing("ACE"), ["ACE", "RACE"]);
+        assert!(dict.words_containing("").is_empty());
+    }
+}
@@ -0,0 +1,210 @@
+//! Where the moves of each seat come from. On every tick of a turn the game asks the
+//! seat's `MoveProvider` for its move, whoever or whatever is behind it, so people at
+//! the screen, bots and players elsewhere all take their turns through the same loop.
+
+use std::{fs, path::PathBuf};
+
+use cursive::Vec2;
+
+use crate::{
+    board::Board,
+    dictionary::Dictionary,
+    endgame,
+    gaddag::Gaddag,
+    mail::MoveFile,
+    solver::Openings,
+    strategy::{self, Effort},
+    tile::Tile,
+};
+
+/// A move as a seat hands it over. Tiles are the ones on the player's rack, blanks as
+/// played (see `Tile::played_as`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Decision {
+    Play(Vec<(Vec2, Tile)>),
+    Exchange(Vec<Tile>),
+    Pass,
+    /// Challenges the last play, after which the seat is asked for its move again.
+    Challenge,
+}
+
+/// What the player to move knows of the game.
+pub struct Position<'a, D: Dictionary = Gaddag> {
+    /// The player's own lexicon if they have one, otherwise the game's dictionary.
+    pub dict: &'a D,
+    /// Openings cached for `dict`, when it's the game's dictionary.
+    pub openings: Option<&'a Openings>,
+    pub board: &'a Board,
+    pub rack: &'a [Tile],
+    pub turn: usize,
+    /// The only opponent's rack and how far the player leads them, once the bag is
+    /// empty and the rack can be told from the tiles unseen.
+    pub opponent: Option<(Vec<Tile>, isize)>,
+    /// Words of the last play that aren't in the dictionary, which can be challenged.
+    pub phonies: &'a [String],
+}
+
+/// Makes the moves of a seat.
+pub trait MoveProvider<D: Dictionary = Gaddag>: Send + Sync {
+    /// The move of the player to move, or `None` while it's still to come, in which case
+    /// the game asks again on the next tick.
+    fn request(&mut self, position: &Position<D>) -> Option<Decision>;
+
+    /// Whether the player makes their moves at the screen, with the keys. Only they can
+    /// place tiles and play with the keys on their turn.
+    fn at_screen(&self) -> bool {
+        false
+    }
+}
+
+/// A person at the screen, who plays with the keys rather than handing moves over.
+pub struct Screen;
+
+impl<D: Dictionary> MoveProvider<D> for Screen {
+    fn request(&mut self, _: &Position<D>) -> Option<Decision> {
+        None
+    }
+
+    fn at_screen(&self) -> bool {
+        true
+    }
+}
+
+/// A bot, playing the move `strategy` picks, or looking into the endgame once the
+/// opponent's rack is known. It challenges every phony it's shown.
+pub struct Bot {
+    effort: Effort,
+}
+
+impl Bot {
+    pub fn new(effort: Effort) -> Self {
+        Self { effort }
+    }
+}
+
+impl<D: Dictionary> MoveProvider<D> for Bot {
+    fn request(&mut self, position: &Position<D>) -> Option<Decision> {
+        if !position.phonies.is_empty() {
+            return Some(Decision::Challenge);
+        }
+        let (dict, board, rack) = (position.dict, position.board, position.rack);
+        let play = match &position.opponent {
+            Some((opponent, lead)) => {
+                endgame::outlook(dict, board, rack, opponent, *lead, self.effort).play
+            }
+            None => strategy::choose(dict, board, rack, position.openings, self.effort),
+        };
+        Some(play.map_or(Decision::Pass, |play| Decision::Play(play.tiles)))
+    }
+}
+
+/// A player elsewhere, whose moves arrive as move files at `path` (see `mail`). Files
+/// written for another position are left alone until one for this one turns up, and
+/// each is only handed over once.
+pub struct Mailbox {
+    path: PathBuf,
+    opened: Option<(usize, u64)>,
+}
+
+impl Mailbox {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, opened: None }
+    }
+}
+
+impl<D: Dictionary> MoveProvider<D> for Mailbox {
+    fn request(&mut self, position: &Position<D>) -> Option<Decision> {
+        let key = (position.turn, position.board.hash());
+        if self.opened == Some(key) {
+            return None;
+        }
+        let file = MoveFile::parse(&fs::read_to_string(&self.path).ok()?).ok()?;
+        if (file.turn, file.board) != key {
+            return None;
+        }
+        self.opened = Some(key);
+        file.play.decision(position.rack).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::bag::Distribution;
+
+    fn position<'a>(dict: &'a Gaddag, board: &'a Board, rack: &'a [Tile]) -> Position<'a> {
+        Position {
+            dict,
+            openings: None,
+            board,
+            rack,
+            turn: 3,
+            opponent: None,
+            phonies: &[],
+        }
+    }
+
+    fn setup() -> (Gaddag, Board, Vec<Tile>) {
+        let dict = Gaddag::from_words(["AT", "CAT", "ACT"].map(String::from)).unwrap();
+        let rack = "CAT"
+            .chars()
+            .map(|ch| Distribution::default().tile(ch))
+            .collect();
+        (dict, Board::new(15), rack)
+    }
+
+    #[test]
+    fn screen_players_use_the_keys() {
+        let (dict, board, rack) = setup();
+        let mut screen = Screen;
+        assert!(MoveProvider::<Gaddag>::at_screen(&screen));
+        assert_eq!(screen.request(&position(&dict, &board, &rack)), None);
+        assert!(!MoveProvider::<Gaddag>::at_screen(&Bot::new(
+            Effort::default()
+        )));
+    }
+
+    #[test]
+    fn bots_play_and_challenge_phonies() {
+        let (dict, board, rack) = setup();
+        let mut bot = Bot::new(Effort::default());
+        let Some(Decision::Play(tiles)) = bot.request(&position(&dict, &board, &rack)) else {
+            panic!("the bot should have played");
+        };
+        assert_eq!(tiles.len(), 3);
+        let phonies = ["TCA".to_string()];
+        let challenged = Position {
+            phonies: &phonies,
+            ..position(&dict, &board, &rack)
+        };
+        assert_eq!(bot.request(&challenged), Some(Decision::Challenge));
+    }
+
+    #[test]
+    fn mailboxes_hand_each_move_for_the_position_over_once() {
+        let (dict, board, rack) = setup();
+        let path = env::temp_dir().join(format!("scrabbler-driver-{}.move", std::process::id()));
+        let mut mailbox = Mailbox::new(path.clone());
+        assert_eq!(mailbox.request(&position(&dict, &board, &rack)), None);
+
+        let file = |turn| {
+            let content = format!(
+                "scrabbler move\nturn {turn}\nboard {:016x}\nexchange CT\n",
+                board.hash()
+            );
+            fs::write(&path, content).unwrap();
+        };
+        file(2);
+        assert_eq!(mailbox.request(&position(&dict, &board, &rack)), None);
+        file(3);
+        let traded = vec![rack[0], rack[2]];
+        assert_eq!(
+            mailbox.request(&position(&dict, &board, &rack)),
+            Some(Decision::Exchange(traded))
+        );
+        assert_eq!(mailbox.request(&position(&dict, &board, &rack)), None);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,186 @@
+//! A look at the endgame. Once the bag is empty the tiles not on the board or on your
+//! rack are on your opponent's, so every play can be weighed against the best reply to
+//! it, and the best reply to that as deep as `Effort::endgame_depth` goes, counting the
+//! letters both players are left holding against them. Plays in a different order can
+//! reach the same position, which is then only looked into once (see `Table`).
+
+use std::collections::HashMap;
+
+use crate::{
+    board::Board,
+    dictionary::Dictionary,
+    solver::{Move, Solver},
+    strategy::Effort,
+    tile::Tile,
+    zobrist,
+};
+
+// What positions net the player to move, by their Zobrist hash and the plies looked
+// ahead from them.
+type Table = HashMap<(u64, usize), isize>;
+
+/// The play with the best margin after the opponent's best reply, and that margin.
+/// A positive margin means the play wins whatever the reply, no play means passing.
+pub struct Outlook {
+    pub play: Option<Move>,
+    pub margin: isize,
+}
+
+/// Looks for the play that leaves the player with `rack` furthest ahead of the
+/// opponent holding `opponent`, given the player's current `lead`.
+pub fn outlook(
+    dict: &impl Dictionary,
+    board: &Board,
+    rack: &[Tile],
+    opponent: &[Tile],
+    lead: isize,
+    effort: Effort,
+) -> Outlook {
+    let (play, net) = best(
+        dict,
+        board,
+        rack,
+        opponent,
+        effort.endgame_depth.max(1),
+        effort,
+        &mut Table::new(),
+    );
+    Outlook {
+        play,
+        margin: lead + net,
+    }
+}
+
+// The best of the plays of the player with `rack` to move, passing included, and what
+// it nets them over the opponent holding `opponent` in the `depth` plays from here:
+// scores, less the letters each is left holding. Only the highest scoring plays are
+// looked into below the last ply, as every one of them costs a full move generation.
+fn best(
+    dict: &impl Dictionary,
+    board: &Board,
+    rack: &[Tile],
+    opponent: &[Tile],
+    depth: usize,
+    effort: Effort,
+    table: &mut Table,
+) -> (Option<Move>, isize) {
+    let mut best = (
+        None,
+        -net(dict, board, opponent, rack, depth - 1, effort, table),
+    );
+    let plays = Solver::new(dict, board).moves(rack);
+    let width = if depth > 1 {
+        effort.endgame_candidates.max(1)
+    } else {
+        plays.len()
+    };
+    for play in plays.into_iter().take(width) {
+        let leave = play.leave(rack);
+        // Going out ends the game, and the opponent is left holding their letters.
+        let reply = if depth > 1 && !leave.is_empty() {
+            let mut after = board.clone();
+            for (pos, tile) in &play.tiles {
+                after.place_at(*tile, pos);
+            }
+            after.commit_tentative(dict);
+            net(dict, &after, opponent, &leave, depth - 1, effort, table)
+        } else {
+            rack_value(&leave) - rack_value(opponent)
+        };
+        let margin = play.score as isize - reply;
+        if margin > best.1 {
+            best = (Some(play), margin);
+        }
+    }
+    best
+}
+
+// What the player with `rack` to move nets over the opponent holding `opponent` in the
+// `depth` plays from here, which with none left is the letters they're left holding.
+fn net(
+    dict: &impl Dictionary,
+    board: &Board,
+    rack: &[Tile],
+    opponent: &[Tile],
+    depth: usize,
+    effort: Effort,
+    table: &mut Table,
+) -> isize {
+    if depth == 0 {
+        return rack_value(opponent) - rack_value(rack);
+    }
+    let position = board.hash() ^ zobrist::rack_hash(rack, 0) ^ zobrist::rack_hash(opponent, 1);
+    if let Some(&net) = table.get(&(position, depth)) {
+        return net;
+    }
+    let net = best(dict, board, rack, opponent, depth, effort, table).1;
+    table.insert((position, depth), net);
+    net
+}
+
+fn rack_value(rack: &[Tile]) -> isize {
+    rack.iter().map(|tile| tile.value as isize).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bag::Distribution, gaddag::Gaddag};
+
+    fn tiles(letters: &str) -> Vec<Tile> {
+        let distribution = Distribution::default();
+        letters
+            .chars()
+            .map(|letter| distribution.tile(letter))
+            .collect()
+    }
+
+    fn dict() -> Gaddag {
+        Gaddag::from_words(["QI", "QIS", "ZA"].map(String::from)).unwrap()
+    }
+
+    #[test]
+    fn going_out_wins_the_opponents_letters() {
+        let (dict, board) = (dict(), Board::new(15));
+        let outlook = outlook(
+            &dict,
+            &board,
+            &tiles("IQ"),
+            &tiles("Z"),
+            5,
+            Effort::default(),
+        );
+        let play = outlook.play.expect("QI goes out");
+        assert_eq!(play.tiles.len(), 2);
+        assert_eq!(outlook.margin, 5 + play.score as isize + 10);
+    }
+
+    #[test]
+    fn stuck_players_count_the_letters_they_hold() {
+        let (dict, board) = (dict(), Board::new(15));
+        for depth in 1..=3 {
+            let effort = Effort {
+                endgame_depth: depth,
+                ..Effort::default()
+            };
+            let outlook = outlook(&dict, &board, &tiles("XV"), &tiles("K"), -3, effort);
+            assert!(outlook.play.is_none());
+            assert_eq!(outlook.margin, -3 + 5 - 12, "depth {depth}");
+        }
+    }
+
+    #[test]
+    fn going_out_is_found_at_any_depth() {
+        let (dict, board) = (dict(), Board::new(15));
+        let rack = tiles("QIS");
+        let shallow = Effort {
+            endgame_depth: 1,
+            ..Effort::default()
+        };
+        // Going out with QIS leaves nothing to reply to.
+        let deep = outlook(&dict, &board, &rack, &tiles("ZA"), 0, Effort::default());
+        let flat = outlook(&dict, &board, &rack, &tiles("ZA"), 0, shallow);
+        assert_eq!(deep.play.map(|play| play.tiles.len()), Some(3));
+        assert_eq!(deep.margin, flat.margin);
+    }
+}
@@ -0,0 +1,240 @@
+//! Line based protocol for driving the solver from other programs, in the spirit of
+//! chess UCI. Every command is a single line on stdin, answered on stdout:
+//!
+//! ```text
+//! scrabbler                 -> id name scrabbler <version>, then scrabblerok
+//! isready                   -> readyok
+//! newgame                   clears the board and the rack
+//! position <row>/<row>/...  sets the board, one row per field, '.' for vacant squares
+//!                           and lower case letters for blanks
+//! rack <letters>            sets the letters to move with, '?' for a blank
+//! bag <count>               sets the number of letters left in the bag, 100 by default
+//! leaves <path>             loads a leave table, see `Leaves`
+//! go [movetime <ms>]        -> info equity <value>, then bestmove <coordinate> <word> <score>,
+//!                              bestmove exchange <letters>, or bestmove pass
+//! quit                      exits engine mode
+//! ```
+//!
+//! Moves are written the way GCG files and Quackle write them: "8H" for a play across
+//! starting on row 8, column H, "H8" for a play down from the same square, and '.' in
+//! the word for letters that were already on the board.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    time::{Duration, Instant},
+};
+
+use cursive::Vec2;
+
+use crate::{
+    bag::Distribution,
+    board::{self, Board},
+    error::{Result, ScrabbleError},
+    gaddag::Gaddag,
+    leaves::Leaves,
+    solver::{Candidate, Solver},
+};
+
+const DEFAULT_SIZE: usize = 15;
+const DEFAULT_BAG: usize = 100;
+
+pub fn run(dict: &Gaddag, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut board = Board::new(DEFAULT_SIZE);
+    let mut rack = Vec::new();
+    let mut leaves = Leaves::default();
+    let mut bag = DEFAULT_BAG;
+    for line in input.lines() {
+        let line = line?;
+        let mut args = line.split_whitespace();
+        let Some(command) = args.next() else {
+            continue;
+        };
+        match command {
+            "scrabbler" => {
+                writeln!(output, "id name scrabbler {}", env!("CARGO_PKG_VERSION"))?;
+                writeln!(output, "scrabblerok")?;
+            }
+            "isready" => writeln!(output, "readyok")?,
+            "newgame" => {
+                board = Board::new(DEFAULT_SIZE);
+                rack.clear();
+                bag = DEFAULT_BAG;
+            }
+            "position" => match parse_position(dict, args.next().unwrap_or_default()) {
+                Ok(position) => board = position,
+                Err(e) => writeln!(output, "error {e}")?,
+            },
+            "rack" => {
+                let distribution = Distribution::default();
+                rack = args
+                    .next()
+                    .unwrap_or_default()
+                    .chars()
+                    .map(|ch| distribution.written(ch.to_ascii_uppercase()))
+                    .collect()
+            }
+            "bag" => match args.next().unwrap_or_default().parse() {
+                Ok(count) => bag = count,
+                Err(_) => writeln!(output, "error invalid bag count")?,
+            },
+            "leaves" => match load_leaves(args.next().unwrap_or_default()) {
+                Ok(loaded) => leaves = loaded,
+                Err(e) => writeln!(output, "error {e}")?,
+            },
+            "go" => {
+                let mut solver = Solver::new(dict, &board);
+                if let (Some("movetime"), Some(ms)) = (args.next(), args.next()) {
+                    match ms.parse() {
+                        Ok(ms) => {
+                            solver =
+                                solver.with_deadline(Instant::now() + Duration::from_millis(ms))
+                        }
+                        Err(_) => {
+                            writeln!(output, "error invalid movetime {ms}")?;
+                            continue;
+                        }
+                    }
+                }
+                let best = solver.candidates(&rack, &leaves, bag).into_iter().next();
+                if let Some(best) = &best {
+                    writeln!(output, "info equity {:.1}", best.equity)?;
+                }
+                match best.map(|best| best.candidate) {
+                    Some(Candidate::Play(play)) => {
+                        writeln!(output, "bestmove {} {}", play.notation(), play.score)?
+                    }
+                    Some(Candidate::Exchange(tiles)) => writeln!(
+                        output,
+                        "bestmove exchange {}",
+                        tiles.iter().map(|tile| tile.label()).collect::<String>()
+                    )?,
+                    Some(Candidate::Pass) | None => writeln!(output, "bestmove pass")?,
+                }
+            }
+            "quit" => break,
+            _ => writeln!(output, "error unknown command {command}")?,
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
+
+fn load_leaves(path: &str) -> Result<Leaves> {
+    Ok(Leaves::from_reader(BufReader::new(File::open(path)?))?)
+}
+
+fn parse_position(dict: &Gaddag, position: &str) -> Result<Board> {
+    let rows = position.split('/').collect::<Vec<&str>>();
+    let size = rows.len();
+    if size < board::MIN_SIZE || size % 2 == 0 || size > board::MAX_SIZE {
+        return Err(ScrabbleError::Position(format!(
+            "board must have an odd number of rows from {} to {}, got {size}",
+            board::MIN_SIZE,
+            board::MAX_SIZE
+        )));
+    }
+
+    let distribution = Distribution::default();
+    let mut board = Board::new(size);
+    for (y, row) in rows.iter().enumerate() {
+        if row.chars().count() != size {
+            return Err(ScrabbleError::Position(format!(
+                "row {} does not have {size} squares",
+                y + 1
+            )));
+        }
+        for (x, ch) in row.chars().enumerate() {
+            match ch {
+                '.' => (),
+                ch if ch.is_alphabetic() => {
+                    board.place_at(distribution.written(ch), &Vec2::new(x, y));
+                }
+                _ => {
+                    return Err(ScrabbleError::Position(format!(
+                        "invalid square '{ch}' on row {}",
+                        y + 1
+                    )))
+                }
+            }
+        }
+    }
+    board.commit_tentative(dict);
+    Ok(board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dict() -> Gaddag {
+        Gaddag::from_words(["AT", "CAT", "ACT"].map(String::from)).unwrap()
+    }
+
+    fn answers(commands: &str) -> Vec<String> {
+        let mut output = Vec::new();
+        run(&dict(), commands.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn commands_are_answered_line_by_line() {
+        let answers = answers("scrabbler\n\nisready\nbag lots\nshuffle\nquit\nisready\n");
+        assert_eq!(
+            answers,
+            [
+                format!("id name scrabbler {}", env!("CARGO_PKG_VERSION")),
+                "scrabblerok".to_string(),
+                "readyok".to_string(),
+                "error invalid bag count".to_string(),
+                "error unknown command shuffle".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn go_answers_the_best_move() {
+        let answers = answers("rack CAT\ngo movetime 1000\nnewgame\ngo\n");
+        assert!(answers[0].starts_with("info equity "));
+        let best = answers[1].split_whitespace().collect::<Vec<_>>();
+        assert!(
+            matches!(best[..], ["bestmove", _, "CAT" | "ACT", _]),
+            "{best:?}"
+        );
+        assert_eq!(answers[2..], ["info equity 0.0", "bestmove pass"]);
+    }
+
+    #[test]
+    fn positions_are_read_row_by_row() {
+        let board = parse_position(
+            &dict(),
+            ".......\n.......\n.......\n..cAT..\n.......\n.......\n......."
+                .replace('\n', "/")
+                .as_str(),
+        )
+        .unwrap();
+        assert_eq!(board.committed_letter_at(&Vec2::new(2, 3)), Some('C'));
+        assert_eq!(
+            board
+                .committed_tile_at(&Vec2::new(2, 3))
+                .map(|tile| tile.is_blank),
+            Some(true)
+        );
+        assert_eq!(board.committed_letter_at(&Vec2::new(4, 3)), Some('T'));
+
+        for position in [
+            "...../...../...../...../.....",
+            "......./......./......",
+            "......./......./......./...1.../......./......./.......",
+        ] {
+            assert!(matches!(
+                parse_position(&dict(), position),
+                Err(ScrabbleError::Position(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::io;
+
+use thiserror::Error;
+
+use crate::{
+    board::{MAX_SIZE, MIN_SIZE},
+    gaddag::BuildError,
+    leaves::LeavesError,
+    rules::PlacementError,
+};
+
+/// Everything that can go wrong in scrabbler, for the public APIs to return.
+#[derive(Debug, Error)]
+pub enum ScrabbleError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Failed to build the dictionary: {0}")]
+    Dictionary(#[from] BuildError),
+    #[error("Failed to load the processed dictionary: {0}")]
+    Fst(#[from] fst::Error),
+    #[error(transparent)]
+    Placement(#[from] PlacementError),
+    #[error("Can't exchange {wanted} letters with {left} left in the bag.")]
+    Exchange { wanted: usize, left: usize },
+    #[error("Your rack doesn't hold {0}.")]
+    NotOnRack(String),
+    #[error("Invalid board layout: {0}")]
+    Layout(String),
+    #[error("Boards are an odd number of squares from {MIN_SIZE} to {MAX_SIZE} across, not {0}.")]
+    BoardSize(usize),
+    #[error("Invalid position: {0}")]
+    Position(String),
+    #[error("The play isn't legal here: {0}")]
+    IllegalMove(String),
+    #[error("Invalid move file: {0}")]
+    MoveFile(String),
+    #[error("Invalid GCG file, line {line}: {reason}")]
+    Gcg { line: usize, reason: String },
+    #[error("The game has {record} players, but {seats} seats are set up.")]
+    Seats { record: usize, seats: usize },
+    #[error("A game needs at least one seat.")]
+    NoSeats,
+    #[error("Invalid scenario \"{name}\": {reason}")]
+    Scenario { name: String, reason: String },
+    #[error("Failed to load leaves: {0}")]
+    Leaves(#[from] LeavesError),
+    #[error("Failed to access the game history: {0}")]
+    Archive(#[from] rusqlite::Error),
+    #[error("Invalid configuration: {0}")]
+    Config(#[from] toml::de::Error),
+    #[error("House rules failed: {0}")]
+    Script(String),
+    #[error("No lexicons are set up in scrabble_config.toml.")]
+    NoLexicon,
+    #[error("Failed to import the word list: {0}")]
+    Import(String),
+    #[error("Invalid arguments: {0}")]
+    Arguments(String),
+    #[error("Failed to serialize: {0}")]
+    Serialize(#[from] toml::ser::Error),
+}
+
+pub type Result<T> = std::result::Result<T, ScrabbleError>;
@@ -3,31 +3,132 @@ use crate::board::Direction;
 use cursive::event::{Event, Key};
 
 pub enum SEvent {
-    Move(Direction),
+    /// A move of the focus, repeated as often as the count typed before it.
+    Move(Direction, usize),
+    /// A digit of a count, or the `?` before the letter of a blank, waiting for the rest.
+    Count,
+    Command,
+    SearchLog,
+    WordLists,
+    Anchor {
+        forward: bool,
+    },
     Letter(char),
+    /// A blank played as the letter, typed as `?` and the letter.
+    Blank(char),
     Pass,
+    Resign,
     Confirm,
+    Challenge,
     Shuffle,
+    Sort,
+    Suggest,
+    Heatmap,
+    ExportMove,
+    ExportGcg,
+    Chart,
+    DrawChance,
+    Annotate,
+    ImportMove,
+    Endgame,
     Exchange,
     Delete,
     DeleteAll,
+    ReadRack,
+    ReadLastPlay,
+    ReadBoard,
+    CoachView,
+    TrackingSheet,
+    Dictionary,
+    TakeBack,
+    Tick,
     Ignored,
 }
 
+// Counts beyond the size of any board only go round it again.
+const MAX_COUNT: usize = 999;
+
+/// Maps key presses to events, gathering the digits typed before a move into a count
+/// the way vim does: `5l` moves five squares right.
+#[derive(Default)]
+pub struct Input {
+    count: Option<usize>,
+    // `?` was typed, so the next letter is what a blank is played as.
+    blank: bool,
+}
+
+impl Input {
+    pub fn map(&mut self, event: Event) -> SEvent {
+        match event {
+            // Refreshes come in between key presses and don't end a count.
+            Event::Refresh => SEvent::Tick,
+            // A count can't start with 0.
+            Event::Char(digit @ '0'..='9') if digit != '0' || self.count.is_some() => {
+                let digit = digit as usize - '0' as usize;
+                self.count = Some((self.count.unwrap_or(0) * 10 + digit).min(MAX_COUNT));
+                SEvent::Count
+            }
+            Event::Char('?') => {
+                self.count = None;
+                self.blank = true;
+                SEvent::Count
+            }
+            event => match (SEvent::from(event), self.count.take()) {
+                (SEvent::Letter(letter), _) if std::mem::take(&mut self.blank) => {
+                    SEvent::Blank(letter)
+                }
+                (SEvent::Move(direction, _), Some(count)) => {
+                    self.blank = false;
+                    SEvent::Move(direction, count)
+                }
+                (event, _) => {
+                    self.blank = false;
+                    event
+                }
+            },
+        }
+    }
+}
+
 impl From<Event> for SEvent {
     fn from(event: Event) -> Self {
         match event {
-            Event::Key(Key::Up) | Event::Char('K') => Self::Move(Direction::Up),
-            Event::Key(Key::Down) | Event::Char('J') => Self::Move(Direction::Down),
-            Event::Key(Key::Left) | Event::Char('H') => Self::Move(Direction::Left),
-            Event::Key(Key::Right) | Event::Char('L') => Self::Move(Direction::Right),
+            Event::Key(Key::Up) | Event::Char('K') => Self::Move(Direction::Up, 1),
+            Event::Key(Key::Down) | Event::Char('J') => Self::Move(Direction::Down, 1),
+            Event::Key(Key::Left) | Event::Char('H') => Self::Move(Direction::Left, 1),
+            Event::Key(Key::Right) | Event::Char('L') => Self::Move(Direction::Right, 1),
             Event::Key(Key::Del | Key::Backspace) => Self::Delete,
+            Event::Char(':') => Self::Command,
+            Event::Char('/') => Self::SearchLog,
+            Event::Key(Key::Tab) => Self::Anchor { forward: true },
+            Event::Shift(Key::Tab) => Self::Anchor { forward: false },
             Event::Char(ch @ ('a'..='z' | 'å'..='ö')) => Self::Letter(ch),
             Event::CtrlChar('p') => Self::Pass,
+            Event::CtrlChar('q') => Self::Resign,
             Event::CtrlChar('e') => Self::Exchange,
             Event::CtrlChar('d') => Self::DeleteAll,
             Event::CtrlChar('r') => Self::Shuffle,
+            Event::CtrlChar('y') => Self::Sort,
+            Event::CtrlChar('s') => Self::Suggest,
+            Event::CtrlChar('v') => Self::Heatmap,
+            Event::CtrlChar('g') => Self::Endgame,
+            Event::CtrlChar('o') => Self::ExportMove,
+            Event::CtrlChar('n') => Self::ImportMove,
+            Event::CtrlChar('w') => Self::ExportGcg,
+            Event::CtrlChar('a') => Self::Annotate,
+            Event::CtrlChar('f') => Self::Chart,
+            Event::CtrlChar('k') => Self::DrawChance,
+            Event::CtrlChar('u') => Self::WordLists,
+            Event::CtrlChar('x') => Self::Challenge,
+            Event::CtrlChar('t') => Self::ReadRack,
+            Event::CtrlChar('l') => Self::ReadLastPlay,
+            Event::CtrlChar('b') => Self::ReadBoard,
+            Event::Key(Key::F2) => Self::CoachView,
+            Event::Key(Key::F3) => Self::TrackingSheet,
+            Event::Key(Key::F4) => Self::Dictionary,
+            Event::Key(Key::F5) => Self::TakeBack,
             Event::Key(Key::Enter) => Self::Confirm,
+            Event::Refresh => Self::Tick,
             _ => Self::Ignored,
         }
     }
@@ -1,6 +1,15 @@
-use std::{collections::BTreeSet, iter};
+use std::{
+    collections::{BTreeSet, HashSet},
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+    iter,
+    ops::ControlFlow,
+};
 
-use fst::{raw::CompiledAddr, Result};
+use fst::raw::CompiledAddr;
+
+use crate::dictionary::Dictionary;
 
 static SEP: u8 = b'+';
 
@@ -17,6 +26,34 @@ impl Node {
     }
 }
 
+/// Reasons a word list can fail to be built into a Gaddag.
+#[derive(Debug)]
+pub enum BuildError {
+    Io {
+        line: usize,
+        source: io::Error,
+    },
+    InvalidWord {
+        line: usize,
+        word: String,
+        reason: &'static str,
+    },
+    Fst(fst::Error),
+    /// The build was called off through its progress callback.
+    Cancelled,
+}
+
+/// Size of a `Gaddag`, see `Gaddag::stats`.
+pub struct Stats {
+    pub words: usize,
+    /// Paths through the words, one per letter of each word.
+    pub entries: usize,
+    pub nodes: usize,
+    pub edges: usize,
+    /// Size of the serialized FST, which is also what it takes up in memory.
+    pub bytes: usize,
+}
+
 /// https://en.wikipedia.org/wiki/GADDAG
 #[derive(Clone)]
 pub struct Gaddag {
@@ -24,64 +61,105 @@ pub struct Gaddag {
 }
 
 impl Gaddag {
-    pub fn accepts(&self, input: &str) -> bool {
-        self.set
-            .contains(input.as_bytes().iter().rev().cloned().collect::<Vec<u8>>())
-    }
-
-    pub fn root(&self) -> Node {
-        Node::new(self.set.as_fst().root().addr())
-    }
-
     pub fn from_fst(set: fst::Set<Vec<u8>>) -> Self {
         Self { set }
     }
 
     ///Builds a Gaddag from its byte representation.
-    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+    pub fn from_bytes(bytes: Vec<u8>) -> fst::Result<Self> {
         Ok(Self::from_fst(fst::Set::new(bytes)?))
     }
 
     ///Builds a Gaddag from an input list of words.
-    pub fn from_words(input: impl IntoIterator<Item = String>) -> Self {
-        Self::from_fst(fst::Set::from_iter(Gaddag::build_entries(input)).unwrap())
+    pub fn from_words(input: impl IntoIterator<Item = String>) -> Result<Self, BuildError> {
+        Self::build(input.into_iter().map(Ok), |_| ControlFlow::Continue(()))
     }
 
-    ///Returns the byte representation of the Gaddag.
-    pub fn as_bytes(&self) -> &[u8] {
-        self.set.as_fst().as_bytes()
+    ///Builds a Gaddag from a word list with one word per line.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, BuildError> {
+        Self::from_reader_with_progress(reader, |_| ControlFlow::Continue(()))
     }
 
-    /// Returns the node address for a prefix in the dictionary.
-    /// This means the input doesn't have to be a full word, but has to be a prefix
-    /// of a word in the dictionary. Will return None if the word doesn't exist in the
-    /// dictionary.
-    pub fn node_for_prefix(&self, prefix: &str) -> Option<Node> {
-        let mut current_node = self.set.as_fst().root();
-        for ch in prefix.chars() {
-            if let Some(transition_idx) = current_node.find_input(ch as u8) {
-                let next_node = self
-                    .set
-                    .as_fst()
-                    .node(current_node.transition_addr(transition_idx));
-                current_node = next_node;
-            } else {
-                return None;
+    /// Builds a Gaddag like `from_reader`, telling `progress` how many words are done
+    /// every thousand words. The build stops with `BuildError::Cancelled` once
+    /// `progress` breaks.
+    pub fn from_reader_with_progress(
+        reader: impl BufRead,
+        progress: impl FnMut(usize) -> ControlFlow<()>,
+    ) -> Result<Self, BuildError> {
+        let lines = reader.lines().enumerate().map(|(i, line)| {
+            line.map_err(|source| BuildError::Io {
+                line: i + 1,
+                source,
+            })
+        });
+        Self::build(lines, progress)
+    }
+
+    // Validates every word before building, so a bad line is reported with its
+    // position instead of silently corrupting or aborting the build.
+    fn build(
+        lines: impl Iterator<Item = Result<String, BuildError>>,
+        progress: impl FnMut(usize) -> ControlFlow<()>,
+    ) -> Result<Self, BuildError> {
+        let mut words = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let word = line?.trim().to_string();
+            if word.is_empty() {
+                continue;
+            }
+            if let Some(reason) = Self::invalid_reason(&word) {
+                return Err(BuildError::InvalidWord {
+                    line: i + 1,
+                    word,
+                    reason,
+                });
             }
+            words.push(word);
         }
-        Some(Node::new(current_node.addr()))
+        let entries = Self::build_entries(words, progress).ok_or(BuildError::Cancelled)?;
+        Ok(Self::from_fst(fst::Set::from_iter(entries)?))
     }
 
-    /// Attempts to follow the node in the GADDAG, and returns the next node.
-    pub fn next_node(&self, node: &Node, next: char) -> Option<Node> {
-        let current_node = self.set.as_fst().node(node.addr);
-        current_node
-            .find_input(next as u8)
-            .map(|i| Node::new(current_node.transition_addr(i)))
+    fn invalid_reason(word: &str) -> Option<&'static str> {
+        if word.bytes().any(|b| b == SEP) {
+            Some("contains the reserved separator '+'")
+        } else if word.chars().any(char::is_whitespace) {
+            Some("contains whitespace")
+        } else if word.chars().any(|ch| ch as u32 > u8::MAX as u32) {
+            Some("contains a letter outside of Latin-1")
+        } else {
+            None
+        }
     }
 
-    pub fn is_final(&self, node: &Node) -> bool {
-        self.set.as_fst().node(node.addr).is_final()
+    /// Counts the words, entries, nodes and edges of the dictionary, visiting every
+    /// node once.
+    pub fn stats(&self) -> Stats {
+        let fst = self.set.as_fst();
+        let mut visited = HashSet::new();
+        let mut stack = vec![fst.root().addr()];
+        let mut edges = 0;
+        while let Some(addr) = stack.pop() {
+            if !visited.insert(addr) {
+                continue;
+            }
+            let node = fst.node(addr);
+            edges += node.len();
+            stack.extend(node.transitions().map(|t| t.addr));
+        }
+        Stats {
+            words: self.iter_words().count(),
+            entries: self.set.len(),
+            nodes: visited.len(),
+            edges,
+            bytes: self.as_bytes().len(),
+        }
+    }
+
+    ///Returns the byte representation of the Gaddag.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.set.as_fst().as_bytes()
     }
 
     /*
@@ -92,23 +170,155 @@ impl Gaddag {
      * C+ARES
      * ECARES
      */
-    fn build_entries(input: impl IntoIterator<Item = String>) -> BTreeSet<Vec<u8>> {
+    fn build_entries(
+        input: impl IntoIterator<Item = String>,
+        mut progress: impl FnMut(usize) -> ControlFlow<()>,
+    ) -> Option<BTreeSet<Vec<u8>>> {
+        const EVERY: usize = 1000;
         let mut entries = BTreeSet::new();
-        for word in input {
-            for n in 1..word.len() {
+        for (done, word) in input.into_iter().enumerate() {
+            if done % EVERY == 0 && progress(done).is_break() {
+                return None;
+            }
+            let Some(letters) = latin1(&word) else {
+                continue;
+            };
+            for n in 1..letters.len() {
                 entries.insert(
-                    word.as_bytes()
+                    letters
                         .iter()
                         .take(n)
                         .rev()
                         .chain(iter::once(&SEP))
-                        .chain(word.as_bytes().iter().skip(n))
+                        .chain(letters.iter().skip(n))
                         .cloned()
                         .collect(),
                 );
-                entries.insert(word.as_bytes().iter().rev().cloned().collect());
+                entries.insert(letters.iter().rev().cloned().collect());
             }
         }
-        entries
+        Some(entries)
+    }
+}
+
+impl Dictionary for Gaddag {
+    type Node = Node;
+
+    fn root(&self) -> Node {
+        Node::new(self.set.as_fst().root().addr())
+    }
+
+    fn next_node(&self, node: &Node, next: char) -> Option<Node> {
+        let current_node = self.set.as_fst().node(node.addr);
+        current_node
+            .find_input(u8::try_from(next).ok()?)
+            .map(|i| Node::new(current_node.transition_addr(i)))
+    }
+
+    fn is_final(&self, node: &Node) -> bool {
+        self.set.as_fst().node(node.addr).is_final()
+    }
+
+    fn edges(&self, node: &Node) -> Vec<(char, Node)> {
+        self.set
+            .as_fst()
+            .node(node.addr)
+            .transitions()
+            .map(|t| (t.inp as char, Node::new(t.addr)))
+            .collect()
+    }
+
+    // A single lookup in the FST instead of a walk from the root.
+    fn accepts(&self, word: &str) -> bool {
+        latin1(word).is_some_and(|mut letters| {
+            letters.reverse();
+            self.set.contains(letters)
+        })
+    }
+}
+
+// The letters of `word` a byte each, as they're stored: words built into a Gaddag are
+// all Latin-1, see `Gaddag::invalid_reason`, so each letter is its own code point.
+fn latin1(word: &str) -> Option<Vec<u8>> {
+    word.chars().map(|ch| u8::try_from(ch).ok()).collect()
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Words: {}", self.words)?;
+        writeln!(f, "Entries: {}", self.entries)?;
+        writeln!(f, "Nodes: {}", self.nodes)?;
+        writeln!(f, "Edges: {}", self.edges)?;
+        write!(f, "Size: {:.1} MiB", self.bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io { line, source } => write!(f, "Failed to read line {line}: {source}"),
+            Self::InvalidWord { line, word, reason } => {
+                write!(f, "Invalid word \"{word}\" on line {line}: {reason}")
+            }
+            Self::Fst(e) => write!(f, "Failed to build dictionary: {e}"),
+            Self::Cancelled => write!(f, "The build was cancelled"),
+        }
+    }
+}
+
+impl Error for BuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::InvalidWord { .. } | Self::Cancelled => None,
+            Self::Fst(e) => Some(e),
+        }
+    }
+}
+
+impl From<fst::Error> for BuildError {
+    fn from(e: fst::Error) -> Self {
+        Self::Fst(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sorted as strings are.
+    const WORDS: [&str; 5] = ["CARES", "SÆD", "TA", "ÅT", "ØL"];
+
+    fn gaddag() -> Gaddag {
+        Gaddag::from_words(WORDS.map(String::from)).unwrap()
+    }
+
+    #[test]
+    fn iter_words_round_trips_latin1() {
+        let mut words = gaddag().iter_words().collect::<Vec<_>>();
+        words.sort();
+        assert_eq!(words, WORDS);
+    }
+
+    #[test]
+    fn accepts_latin1_words_only() {
+        let dict = gaddag();
+        for word in WORDS {
+            assert!(dict.accepts(word), "{word}");
+        }
+        assert!(!dict.accepts("AT"));
+        assert!(!dict.accepts("ĀT"));
+        assert!(dict.starts_word("SÆ"));
+        assert_eq!(dict.words_with_prefix("CA"), ["CARES"]);
+    }
+
+    #[test]
+    fn rejects_invalid_words() {
+        for word in ["A+B", "A B", "ĀT"] {
+            assert!(matches!(
+                Gaddag::from_words([word.to_string()]),
+                Err(BuildError::InvalidWord { .. })
+            ));
+        }
     }
 }
@@ -1,458 +1,3018 @@
 use crate::{
-    board::{Board, Cell, Direction, Multiplier},
-    event::SEvent,
+    archive::Archive,
+    bag::Distribution,
+    board::{
+        Alignment, Board, Cell, CrossCheck, Direction, Legend, Multiplier, Palette, Premiums,
+        MAX_SIZE, MIN_SIZE,
+    },
+    browser, chart,
+    clock::{self, Clock, ClockOptions},
+    csv,
+    dictionary::Dictionary,
+    driver::{Bot, Decision, Mailbox, MoveProvider, Position, Screen},
+    endgame,
+    error::ScrabbleError,
+    event::{Input, SEvent},
     gaddag::Gaddag,
+    gcg::{self, Action, Turn},
+    journal::{Entry, Journal, State, Withdrawal},
+    leaves::Leaves,
+    mail::{self, MailMove, MoveFile},
+    observer::{GameEvent, Subscriber},
+    panels::{LogView, RackView, ScorePanel, Standing},
+    practice::{self, PracticeRacks},
+    printout, readout,
+    rules::{self, PlacementRules},
+    scenario::Scenario,
+    script::HouseRules,
+    solver::{Candidate, Evaluated, Move, Openings, Solver},
+    stats::{self, Odds},
+    strategy::{self, Effort, Volatility},
+    tile::Tile,
+    tracking::TrackingSheet,
+    wordlists::{List, WordLists},
 };
 
 use cursive::{
     event::{Callback, Event, EventResult},
-    view::CannotFocus,
-    views::Dialog,
-    Vec2,
+    theme::Effect,
+    utils::markup::StyledString,
+    view::{CannotFocus, Finder, Nameable, Resizable, Scrollable},
+    views::{Dialog, DummyView, EditView, LinearLayout, SelectView, TextView},
+    Cursive, Vec2,
 };
 
 use itertools::Itertools;
-use rand::prelude::SliceRandom;
+use rand::{prelude::SliceRandom, rngs::StdRng, Rng, SeedableRng};
+use regex::RegexBuilder;
+use serde_derive::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io, mem,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-const N_LETTERS: usize = 7;
+pub const N_LETTERS: usize = 7;
+// Placements listed with a suggestion.
+const SUGGESTIONS: usize = 5;
+pub const BOARD_SIZE: usize = 15;
+/// The sizes a board can be picked in on the setup screen.
+pub const BOARD_SIZES: [usize; 4] = [11, 15, 17, 21];
 
 type PlayerIndex = usize;
 
-pub struct Game {
+/// Whether `wanted` tiles can be exchanged with `left` in the bag, which takes a full
+/// rack's worth.
+pub fn can_exchange(wanted: usize, left: usize) -> bool {
+    wanted > 0 && wanted <= left && left >= N_LETTERS
+}
+
+// The board revision, sorted rack and bag size a list of hints was worked out for.
+type HintKey = (u64, Vec<Tile>, usize);
+
+pub struct Game<D: Dictionary = Gaddag> {
+    // Every tile of the game, for tracking the ones not yet seen.
+    all_tiles: Vec<Tile>,
+    // Where the game is kept once it's over.
+    archive: Option<Archive>,
     board: Board,
+    // Whether the rack of `player` on `turn` can play a bingo, for the indicator.
+    bingo: Option<(usize, PlayerIndex, bool)>,
+    bot_turn: Option<BotTurn>,
+    clock: Clock,
     current_player: PlayerIndex,
-    dict: Gaddag,
-    letters_bag: Vec<char>,
+    // The definitions of the lexicon's words, shared by the games played with it.
+    definitions: Arc<HashMap<String, String>>,
+    dict: D,
+    // What the tiles are worth, for the ones read from records.
+    distribution: Distribution,
+    display: DisplayOptions,
+    // Who went first, for rematches to go round the table.
+    first: PlayerIndex,
+    // The ranked candidates of the last hint, by the board revision, rack and bag
+    // size they were worked out for, as asking again without a change finds the same.
+    hints: Option<(HintKey, Vec<Evaluated>)>,
+    house_rules: Option<Arc<HouseRules>>,
+    // Words of the house words file, marked in the log when played.
+    house_words: HashSet<String>,
+    input: Input,
+    last_play: Option<LastPlay>,
+    // The empty board of a layout of its own, see `with_layout`, for rematches.
+    layout: Option<Board>,
+    // Values of the letters a play keeps, which suggestions are ranked by.
+    leaves: Leaves,
+    // Every turn since the racks were dealt, for taking turns back.
+    journal: Journal,
+    letters_bag: Vec<Tile>,
     log: Vec<String>,
-    passes: usize,
-    players: Vec<Player>,
+    // Opening plays of the racks looked at so far, see `Openings`.
+    openings: Openings,
+    options: Options,
+    // The latest move made in this copy of the game, for playing by mail.
+    outbox: Option<MoveFile>,
+    players: Vec<Player<D>>,
+    // Who the players were set up as, for rematches.
+    profiles: Vec<PlayerProfile>,
+    // Where the moves of each seat come from, see `driver`.
+    providers: Vec<Box<dyn MoveProvider<D>>>,
+    readout: String,
+    rng: StdRng,
+    // Consecutive turns without a score, which end the game at `scoreless_limit`.
+    scoreless: usize,
+    // The views the game is shown in, laid out once by `layout_screen`.
+    screen: LinearLayout,
+    // Whether the game has changed since the screen was last brought up to date.
+    stale: bool,
+    // Told about every turn and the end of the game, see `subscribe`.
+    subscribers: Vec<Box<dyn Subscriber>>,
     turn: usize,
+    // Worked out from `dict` the first time they're looked at.
+    word_lists: Option<WordLists>,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default, Deserialize)]
 pub struct Options {
-    pub n_players: usize,
+    #[serde(default)]
+    pub phonies: PhonyRule,
+    /// Suggestions each player may ask for in a game, unlimited if not set.
+    #[serde(default)]
+    pub hints: Option<usize>,
+    /// Premium squares stay hidden until a tile is played on them.
+    #[serde(default)]
+    pub blind_premiums: bool,
+    /// Seeds the bag, so that copies of a game played by mail draw the same tiles.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub placement: PlacementRules,
+    /// Consecutive scoreless turns, passes and exchanges, that end the game, one per
+    /// player if not set.
+    #[serde(default)]
+    pub scoreless_turns: Option<usize>,
+    #[serde(default)]
+    pub clock: ClockOptions,
+    #[serde(default)]
+    pub first_player: FirstPlayer,
+    #[serde(default)]
+    pub practice: PracticeRacks,
+    /// Lights up when the rack of a player who isn't a bot can play a bingo, without
+    /// telling where.
+    #[serde(default)]
+    pub bingo_indicator: bool,
+    /// Squares a side of the board, an odd number, `BOARD_SIZE` if not set. The premium
+    /// squares are laid out as on the standard board, scaled to fit.
+    #[serde(default)]
+    pub board_size: Option<usize>,
+    /// Lets a teacher look at the bag and every rack during a lesson, with <F2>. Each
+    /// look is noted in the log, so the students know.
+    #[serde(default)]
+    pub coach_mode: bool,
+    /// Lets turns be taken back with <F5>, see `journal`.
+    #[serde(default)]
+    pub takebacks: bool,
+}
+
+impl Options {
+    /// Squares a side of the board, `BOARD_SIZE` if not set. Fails on a size no board
+    /// can be, an even one or one outside of `MIN_SIZE..=MAX_SIZE`.
+    pub fn checked_board_size(&self) -> Result<usize, ScrabbleError> {
+        match self.board_size.unwrap_or(BOARD_SIZE) {
+            size if (MIN_SIZE..=MAX_SIZE).contains(&size) && size % 2 == 1 => Ok(size),
+            size => Err(ScrabbleError::BoardSize(size)),
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize)]
+pub struct DisplayOptions {
+    /// Announce the focused square on a line of its own after every move, and the
+    /// rack, last placement and board there on request, for screen readers.
+    #[serde(default)]
+    pub readout: bool,
+    #[serde(default)]
+    pub palette: Palette,
+    #[serde(default)]
+    pub pacing: Pacing,
+    /// Labels and colors of the premium squares, overriding the palette's.
+    #[serde(default)]
+    pub premiums: Premiums,
+}
+
+/// How long bots take over their moves, so they can be followed on the board.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct Pacing {
+    /// Milliseconds a bot "thinks" before its first tile appears.
+    pub think_ms: u64,
+    /// Milliseconds between the tiles of a bot's move appearing.
+    pub tile_ms: u64,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct PlayerProfile {
+    pub name: String,
+    /// Played by the solver instead of a person.
+    #[serde(default)]
+    pub bot: bool,
+    #[serde(default)]
+    pub handicap: Handicap,
+    /// Word list a bot finds its moves in, instead of the game's dictionary. Words
+    /// are still judged by the game's dictionary.
+    #[serde(default)]
+    pub lexicon: Option<PathBuf>,
+    /// How the letters on the rack are arranged until the player picks otherwise.
+    #[serde(default)]
+    pub rack_order: RackOrder,
+    /// How hard a bot looks for its moves.
+    #[serde(default)]
+    pub effort: Effort,
+    /// Played elsewhere, the moves arriving as move files at this path, see `mail`.
+    #[serde(default)]
+    pub mailbox: Option<PathBuf>,
+}
+
+impl PlayerProfile {
+    /// A person playing as `name`, with no head start.
+    pub fn human(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            bot: false,
+            handicap: Handicap::default(),
+            lexicon: None,
+            rack_order: RackOrder::default(),
+            effort: Effort::default(),
+            mailbox: None,
+        }
+    }
+
+    /// A bot playing as `name`, looking as hard for its moves as `effort` says.
+    pub fn bot(name: &str, effort: Effort) -> Self {
+        Self {
+            bot: true,
+            effort,
+            ..Self::human(name)
+        }
+    }
+}
+
+/// How a player likes their rack arranged.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RackOrder {
+    /// As drawn, or as last shuffled.
+    #[default]
+    Drawn,
+    /// In alphabetical order, blanks last, as most players anagram.
+    Alphabetical,
+}
+
+/// Head starts for the weaker player of a mismatched game.
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+pub struct Handicap {
+    /// Points to start the game with.
+    pub bonus: usize,
+    /// Letters on the rack beyond the usual seven.
+    pub extra_tiles: usize,
+    /// Hints on top of the game's budget.
+    pub extra_hints: usize,
+}
+
+impl Pacing {
+    // Number of tiles of a move that should be on the board `elapsed` into the turn.
+    fn tiles_due(&self, elapsed: Duration) -> usize {
+        let thinking = Duration::from_millis(self.think_ms);
+        if elapsed < thinking {
+            0
+        } else if self.tile_ms == 0 {
+            usize::MAX
+        } else {
+            1 + ((elapsed - thinking).as_millis() / self.tile_ms as u128) as usize
+        }
+    }
+}
+
+impl Default for Pacing {
+    fn default() -> Self {
+        Self {
+            think_ms: 800,
+            tile_ms: 300,
+        }
+    }
+}
+
+/// What happens to placements forming words that aren't in the dictionary.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PhonyRule {
+    /// The placement is rejected outright.
+    #[default]
+    Void,
+    /// The placement stands unless the next player challenges it.
+    Challenge,
+}
+
+/// How the player who goes first is picked.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FirstPlayer {
+    /// Everyone draws a tile, and whoever draws closest to A goes first, a blank being
+    /// closer still. Players who tie draw again.
+    #[default]
+    Draw,
+    /// A player picked at random.
+    Random,
+    /// The first player set up.
+    Listed,
+}
+
+/// A legal play for the player to move, see `Game::legal_moves`.
+#[derive(Clone)]
+pub struct ScoredMove {
+    pub play: Move,
+    /// The points the play scores plus the worth of the letters it keeps.
+    pub equity: f64,
+}
+
+// A move handed over by a seat that isn't at the screen, a bot's or a player's
+// elsewhere, revealed a tile at a time as the turn goes on. Other moves are made once
+// the bot is done thinking.
+struct BotTurn {
+    started: Instant,
+    decision: Decision,
+    shown: usize,
+    // What the play scores, counted up as its tiles appear.
+    score: usize,
+}
+
+// The most recent placement, kept until the next player acts so it can be challenged.
+struct LastPlay {
+    player: PlayerIndex,
+    tiles: Vec<(Vec2, Tile)>,
+    drawn: Vec<Tile>,
+    score: usize,
+    phonies: Vec<String>,
+}
+
+/// Everything a game is set up with before the first tiles are drawn, see `Game::builder`.
+/// Whatever isn't set is as in a default configuration: the English tiles on the
+/// standard board, by the default rules and without a clock.
+pub struct GameBuilder<D: Dictionary = Gaddag> {
+    dict: D,
+    seats: Vec<PlayerProfile>,
+    distribution: Distribution,
+    layout: Option<String>,
+    options: Options,
+    display: DisplayOptions,
+}
+
+impl<D: Dictionary> GameBuilder<D> {
+    /// Adds a player, who sits after the ones added before.
+    pub fn with_seat(mut self, profile: PlayerProfile) -> Self {
+        self.seats.push(profile);
+        self
+    }
+
+    pub fn with_seats(mut self, profiles: impl IntoIterator<Item = PlayerProfile>) -> Self {
+        self.seats.extend(profiles);
+        self
+    }
+
+    /// Plays with the tiles of `distribution`.
+    pub fn with_distribution(mut self, distribution: Distribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+
+    /// Plays on a board with the premiums of `layout`, see `Game::with_layout`.
+    pub fn with_layout(mut self, layout: &str) -> Self {
+        self.layout = Some(layout.to_string());
+        self
+    }
+
+    /// Plays by `options`, replacing the clock, seed and rules set before.
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_placement(mut self, placement: PlacementRules) -> Self {
+        self.options.placement = placement;
+        self
+    }
+
+    pub fn with_phonies(mut self, phonies: PhonyRule) -> Self {
+        self.options.phonies = phonies;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: ClockOptions) -> Self {
+        self.options.clock = clock;
+        self
+    }
+
+    /// Shuffles the bag by `seed`, so games built with the same one draw the same tiles.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.options.seed = Some(seed);
+        self
+    }
+
+    pub fn with_display(mut self, display: DisplayOptions) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// The game, with the racks dealt. Fails without a seat, on a board size out of
+    /// range or on a layout that can't be read.
+    pub fn build(self) -> Result<Game<D>, ScrabbleError> {
+        if self.seats.is_empty() {
+            return Err(ScrabbleError::NoSeats);
+        }
+        self.options.checked_board_size()?;
+        let rng = match self.options.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let game = Game::deal(
+            self.dict,
+            &self.seats,
+            self.distribution,
+            rng,
+            self.options,
+            self.display,
+            None,
+        );
+        match &self.layout {
+            Some(layout) => game.with_layout(layout),
+            None => Ok(game),
+        }
+    }
 }
 
-impl Game {
-    pub fn new(dict: Gaddag, player_names: &[String]) -> Self {
-        const BOARD_SIZE: usize = 15;
-        let mut letters = vec![
-            vec!['A'; 9],
-            vec!['B'; 2],
-            vec!['C'; 2],
-            vec!['D'; 4],
-            vec!['E'; 12],
-            vec!['F'; 2],
-            vec!['G'; 3],
-            vec!['H'; 2],
-            vec!['I'; 9],
-            vec!['J'; 1],
-            vec!['K'; 1],
-            vec!['L'; 4],
-            vec!['M'; 2],
-            vec!['N'; 6],
-            vec!['O'; 8],
-            vec!['P'; 2],
-            vec!['Q'; 1],
-            vec!['R'; 6],
-            vec!['S'; 4],
-            vec!['T'; 6],
-            vec!['U'; 4],
-            vec!['V'; 2],
-            vec!['W'; 2],
-            vec!['X'; 1],
-            vec!['Y'; 2],
-            vec!['Z'; 1],
-            // vec![' '; 2], TODO
-        ]
-        .into_iter()
-        .flatten()
-        .collect::<Vec<char>>();
-        letters.shuffle(&mut rand::thread_rng());
+impl<D: Dictionary> Game<D> {
+    /// Sets up a game judged by `dict`, see `GameBuilder`.
+    pub fn builder(dict: D) -> GameBuilder<D> {
+        GameBuilder {
+            dict,
+            seats: Vec::new(),
+            distribution: Distribution::default(),
+            layout: None,
+            options: Options::default(),
+            display: DisplayOptions::default(),
+        }
+    }
+
+    // A game about to start, with racks dealt from the tiles of `distribution` shuffled
+    // by `rng`, and `first` going first if given, or whoever `options.first_player` picks.
+    fn deal(
+        dict: D,
+        profiles: &[PlayerProfile],
+        distribution: Distribution,
+        mut rng: StdRng,
+        options: Options,
+        display: DisplayOptions,
+        first: Option<PlayerIndex>,
+    ) -> Self {
+        let all_tiles = distribution.tiles();
+        let mut log = vec!["Game started! Good luck :)".to_string()];
+        let first = match first {
+            Some(first) => first,
+            None => {
+                let (first, draws) =
+                    Self::pick_first(profiles, &all_tiles, &mut rng, options.first_player);
+                log.extend(draws);
+                first
+            }
+        };
+        if let Some(profile) = profiles.get(first) {
+            log.push(format!("{} goes first.", profile.name));
+        }
+
+        let mut letters = all_tiles.clone();
+        letters.shuffle(&mut rng);
 
         let mut players = Vec::new();
-        for name in player_names {
-            let player_letters = letters.drain(0..N_LETTERS).collect();
-            players.push(Player::new(player_letters, name.clone()));
+        for profile in profiles {
+            let rack_size = N_LETTERS + profile.handicap.extra_tiles;
+            let player_letters = letters.drain(0..rack_size.min(letters.len())).collect();
+            players.push(Player::new(player_letters, profile, options.hints));
+        }
+
+        let board = Self::dress(
+            Board::new(options.board_size.unwrap_or(BOARD_SIZE)),
+            &options,
+            &display,
+        );
+
+        let start = State::new(
+            board.clone(),
+            players
+                .iter()
+                .map(|player| player.letters.clone())
+                .collect(),
+            letters.clone(),
+            players.iter().map(|player| player.score as isize).collect(),
+        );
+        let mut game = Self {
+            all_tiles,
+            archive: None,
+            board,
+            bingo: None,
+            bot_turn: None,
+            clock: Clock::new(options.clock.control(), profiles.len()),
+            current_player: first,
+            definitions: Arc::default(),
+            dict,
+            display,
+            distribution,
+            first,
+            hints: None,
+            house_rules: None,
+            house_words: HashSet::new(),
+            input: Input::default(),
+            last_play: None,
+            layout: None,
+            leaves: Leaves::default(),
+            journal: Journal::new(start),
+            letters_bag: letters,
+            log,
+            openings: Openings::default(),
+            options,
+            outbox: None,
+            players,
+            profiles: profiles.to_vec(),
+            providers: profiles.iter().map(Self::provider).collect(),
+            readout: String::new(),
+            rng,
+            scoreless: 0,
+            screen: Self::layout_screen(options.coach_mode),
+            stale: true,
+            subscribers: Vec::new(),
+            turn: 0,
+            word_lists: None,
+        };
+        if options.practice.is_set() {
+            for i in 0..game.players.len() {
+                if !game.at_screen(i) {
+                    continue;
+                }
+                let mut rack = mem::take(&mut game.players[i].letters);
+                game.letters_bag.append(&mut rack);
+                game.letters_bag.shuffle(&mut game.rng);
+                game.refill(i);
+            }
+            game.journal = Journal::new(game.snapshot());
+        }
+        game
+    }
+
+    // Takes back the last turn, and the turns before it until it's the turn of a player
+    // at the screen again, so a bot's reply goes with the move it answered.
+    fn take_back(&mut self) {
+        if !self.options.takebacks {
+            self.log
+                .push("Turns can't be taken back in this game.".to_string());
+            return;
+        }
+        let mut turns = 0;
+        while let Some(entry) = self.journal.undo(&self.dict) {
+            // A successful challenge keeps the challenger's turn.
+            let next_turn = !matches!(
+                entry,
+                Entry::Challenged {
+                    withdrawn: Some(_),
+                    ..
+                }
+            );
+            if next_turn {
+                self.turn = self.turn.saturating_sub(1);
+            }
+            let player = entry.player();
+            self.current_player = player;
+            turns += 1;
+            if self.at_screen(player) {
+                break;
+            }
+        }
+        if turns == 0 {
+            self.log.push("There is no turn to take back.".to_string());
+            return;
+        }
+
+        let state = self.journal.state().clone();
+        self.board = state.board;
+        for ((player, rack), score) in self.players.iter_mut().zip(state.racks).zip(state.scores) {
+            player.letters = rack;
+            player.score = score.max(0) as usize;
+            player.arrange();
+        }
+        self.letters_bag = state.bag;
+        self.letters_bag.shuffle(&mut self.rng);
+        // Passes and exchanges since the last placement, challenges lost included.
+        self.scoreless = self
+            .journal
+            .turns()
+            .iter()
+            .rev()
+            .filter(|turn| !matches!(turn.action, Action::Withdrawn))
+            .take_while(|turn| !matches!(turn.action, Action::Play { .. }))
+            .count();
+        self.last_play = None;
+        self.bot_turn = None;
+        self.hints = None;
+        self.bingo = None;
+        let name = self.current_player().name.clone();
+        self.log
+            .push(format!("{turns} turn(s) taken back, {name} to move."));
+        self.emit(GameEvent::TurnsTakenBack {
+            player: name,
+            turns,
+        });
+    }
+
+    // The tiles and scores as they stand, taken as the start of the journal.
+    fn snapshot(&self) -> State {
+        let mut board = self.board.clone();
+        board.clear_tentative_from_board();
+        State::new(
+            board,
+            self.players
+                .iter()
+                .map(|player| player.letters.clone())
+                .collect(),
+            self.letters_bag.clone(),
+            self.players
+                .iter()
+                .map(|player| player.score as isize)
+                .collect(),
+        )
+    }
+
+    // Returns the total score and the words not in the dictionary if the placement
+    // stands, which with challenges allowed it does even with such words. Otherwise
+    // returns all the words that are not in the dictionary.
+    fn try_score(
+        &mut self,
+        word_squares: &Vec<Vec<Cell>>,
+    ) -> Result<(usize, Vec<String>), Vec<String>> {
+        let mut words_and_scores = Vec::new();
+        let mut not_accepted = Vec::new();
+        for squares in word_squares {
+            let word = squares.iter().filter_map(Cell::letter).collect::<String>();
+            if !self.dict.accepts(&word) {
+                not_accepted.push(word.clone());
+                if self.options.phonies == PhonyRule::Void {
+                    continue;
+                }
+            }
+            let word_score = self.house_score(&word, Self::word_score(squares));
+            words_and_scores.push((word, word_score));
+        }
+
+        if not_accepted.is_empty() || self.options.phonies == PhonyRule::Challenge {
+            let score_tot = words_and_scores.iter().map(|(_, score)| score).sum();
+            self.current_player_mut().add_score(score_tot);
+            for (word, _) in &mut words_and_scores {
+                if self.house_words.contains(word.as_str()) {
+                    word.push_str(" (house word)");
+                }
+            }
+            self.log
+                .push(if let [(word, _)] = words_and_scores.as_slice() {
+                    format!(
+                        "{} played {} for {} points.",
+                        self.current_player().name,
+                        word,
+                        score_tot
+                    )
+                } else {
+                    format!(
+                        "{} played {:?}, {} points total.",
+                        self.current_player().name,
+                        words_and_scores,
+                        score_tot,
+                    )
+                });
+            Ok((score_tot, not_accepted))
+        } else {
+            Err(not_accepted)
+        }
+    }
+
+    // What the word on `squares` scores with its premiums.
+    fn word_score(squares: &[Cell]) -> usize {
+        let mut letters_score = 0;
+        let mut word_mults = Vec::new();
+        for square in squares {
+            let letter_score = square.tile.map_or(0, |tile| tile.value);
+            letters_score += match square.mult {
+                None => letter_score,
+                Some(word_mult @ (Multiplier::Dw | Multiplier::Tw)) => {
+                    word_mults.push(word_mult);
+                    letter_score
+                }
+                Some(letter_mult @ (Multiplier::Dl | Multiplier::Tl)) => {
+                    letter_score * letter_mult.as_factor()
+                }
+            };
+        }
+        word_mults
+            .iter()
+            .fold(letters_score, |acc, mult| acc * mult.as_factor())
+    }
+
+    // The words the tentative placement forms, the main word first, with what each would
+    // score, for a look before it's confirmed. Nothing is scored or logged, and placements
+    // that wouldn't stand are left for `confirm` to turn down.
+    fn words_summary(&self) -> Option<String> {
+        let tiles = self.board.tentative().len();
+        let word_squares = rules::validate(&self.board, &self.options.placement).ok()?;
+        let mut lines = Vec::new();
+        let mut total = 0;
+        for squares in &word_squares {
+            let word = squares.iter().filter_map(Cell::letter).collect::<String>();
+            let accepted = self.dict.accepts(&word);
+            if !accepted && self.options.phonies == PhonyRule::Void {
+                return None;
+            }
+            let score = Self::word_score(squares);
+            let score = match &self.house_rules {
+                Some(rules) => rules.score_word(&word, score).unwrap_or(score),
+                None => score,
+            };
+            total += score;
+            let note = if !accepted {
+                " (not in dictionary)"
+            } else if self.house_words.contains(&word) {
+                " (house word)"
+            } else {
+                ""
+            };
+            lines.push(format!("{word}{note}: {score}"));
+        }
+        if tiles >= N_LETTERS {
+            total += 50;
+            lines.push("Bingo: 50".to_string());
+        }
+        lines.push(format!("Total: {total}"));
+        Some(lines.join("\n"))
+    }
+
+    // Returns the letters drawn by the player whose turn ended.
+    fn next_turn(&mut self) -> Vec<Tile> {
+        self.last_play = None;
+        self.board.clear_ghost();
+        self.board.clear_heat();
+        self.house_turn_end();
+        let drawn = self.refill(self.current_player);
+
+        self.clock.end_turn(self.current_player);
+        self.current_player = self.next_active();
+        self.turn += 1;
+        self.turn_started();
+        drawn
+    }
+
+    // Fills the rack of `player` from the bag, redrawn for practice unless they're a
+    // bot, see `PracticeRacks`. Returns the letters drawn.
+    fn refill(&mut self, player: PlayerIndex) -> Vec<Tile> {
+        let practice = self.options.practice;
+        let redraws = if practice.is_set() && self.at_screen(player) {
+            practice::REDRAWS
+        } else {
+            0
+        };
+        let blanks_left = self.letters_bag.iter().any(|tile| tile.is_blank);
+        let mut drawn = Vec::new();
+        for redraw in 0..=redraws {
+            let rack = &mut self.players[player];
+            for _ in rack.letters.len()..rack.rack_size {
+                if let Some(letter) = self.letters_bag.pop() {
+                    rack.letters.push(letter);
+                    drawn.push(letter);
+                }
+            }
+            if redraw == redraws
+                || drawn.is_empty()
+                || practice.accepts(
+                    &rack.letters,
+                    blanks_left,
+                    &self.board,
+                    &self.dict,
+                    &self.openings,
+                )
+            {
+                break;
+            }
+            // The letters drawn go back, to be drawn anew.
+            rack.letters.truncate(rack.letters.len() - drawn.len());
+            self.letters_bag.append(&mut drawn);
+            self.letters_bag.shuffle(&mut self.rng);
+        }
+        self.players[player].arrange();
+        if !drawn.is_empty() {
+            let entry = Entry::Drew {
+                player,
+                tiles: drawn.clone(),
+            };
+            self.journal.push(entry, &self.dict);
+        }
+        drawn
+    }
+
+    fn confirm(&mut self) {
+        let tiles = self
+            .board
+            .tentative()
+            .iter()
+            .filter_map(|pos| Some((*pos, self.board.tile_at(pos)?)))
+            .collect::<Vec<_>>();
+        let word_squares = match rules::validate(&self.board, &self.options.placement) {
+            Ok(word_squares) => word_squares,
+            Err(e) => {
+                self.log.push(e.to_string());
+                return;
+            }
+        };
+        if let Some(reason) = self.house_veto(&word_squares, tiles.len()) {
+            self.log.push(reason);
+            return;
+        }
+        match self.try_score(&word_squares) {
+            Ok((score, phonies)) => {
+                self.outbox = Some(
+                    self.move_file(MailMove::Play {
+                        tiles: tiles
+                            .iter()
+                            .map(|&(pos, tile)| (pos, tile.label()))
+                            .collect(),
+                    }),
+                );
+                let action = gcg::tentative_play(&self.board);
+                let mut rack = self.current_player().letters.clone();
+                rack.extend(tiles.iter().map(|&(_, tile)| tile.drawn()));
+                self.reveal_premiums(&tiles);
+                self.board.commit_tentative(&self.dict);
+                self.scoreless = 0;
+                let player = self.current_player;
+                let bingo = if tiles.len() >= N_LETTERS { 50 } else { 0 };
+                self.current_player_mut().add_score(bingo);
+                let entry = Entry::TilesPlaced {
+                    player,
+                    tiles: tiles.clone(),
+                    score: (score + bingo) as isize,
+                };
+                self.record(entry, player, rack, action, (score + bingo) as isize);
+                let drawn = self.next_turn();
+                self.last_play = Some(LastPlay {
+                    player,
+                    tiles,
+                    drawn,
+                    score: score + bingo,
+                    phonies,
+                });
+            }
+            Err(e) => self
+                .log
+                .push(format!("Word(s) not in dictionary: {:?}.", e)),
+        }
+    }
+
+    // Why the house rules turn down a placement forming the words of `word_squares`, if
+    // they do. Rules that fail are logged and don't stand in the way.
+    fn house_veto(&mut self, word_squares: &[Vec<Cell>], tiles: usize) -> Option<String> {
+        let rules = self.house_rules.clone()?;
+        let words = word_squares
+            .iter()
+            .map(|squares| squares.iter().filter_map(Cell::letter).collect::<String>())
+            .collect::<Vec<_>>();
+        match rules.validate_placement(&words, tiles) {
+            Ok(veto) => veto,
+            Err(e) => {
+                self.log.push(e.to_string());
+                None
+            }
+        }
+    }
+
+    // What `word` scores by the house rules, `score` being what it scores by the usual ones.
+    fn house_score(&mut self, word: &str, score: usize) -> usize {
+        let Some(rules) = self.house_rules.clone() else {
+            return score;
+        };
+        rules.score_word(word, score).unwrap_or_else(|e| {
+            self.log.push(e.to_string());
+            score
+        })
+    }
+
+    // Adds the points the house rules give at the end of a turn to the turn just recorded.
+    fn house_turn_end(&mut self) {
+        let Some(rules) = self.house_rules.clone() else {
+            return;
+        };
+        let Some(&Turn { player, score, .. }) = self.journal.turns().last() else {
+            return;
+        };
+        let bonus = match rules.turn_end(&self.players[player].name, score, self.turn) {
+            Ok(bonus) => bonus,
+            Err(e) => return self.log.push(e.to_string()),
+        };
+        if bonus == 0 {
+            return;
+        }
+        let p = &mut self.players[player];
+        let before = p.score as isize;
+        p.score = (before + bonus).max(0) as usize;
+        let points = p.score as isize - before;
+        self.log
+            .push(format!("House rules: {} gets {bonus} points.", p.name));
+        if let Some(turn) = self.journal.last_turn_mut() {
+            turn.score += bonus;
+            turn.total = p.score as isize;
+        }
+        self.journal
+            .push(Entry::Scored { player, points }, &self.dict);
+    }
+
+    // Logs the hidden premiums under a placement about to be committed, which reveals them.
+    fn reveal_premiums(&mut self, tiles: &[(Vec2, Tile)]) {
+        for (pos, _) in tiles {
+            if self.board.visible_mult_at(pos).is_some() {
+                continue;
+            }
+            if let Some(mult) = self.board.mult_at(pos.x, pos.y) {
+                self.log.push(format!(
+                    "{} was a hidden {} square.",
+                    readout::square_name(pos),
+                    readout::premium_name(mult)
+                ));
+            }
+        }
+    }
+
+    // A successful challenge takes the previous placement back, along with its points
+    // and the letters drawn after it. An unsuccessful one costs the challenger the turn.
+    fn challenge(&mut self) {
+        if self.options.phonies == PhonyRule::Void {
+            self.log
+                .push("Words are checked on placement in this game.".to_string());
+            return;
+        }
+        let Some(play) = self.last_play.take() else {
+            self.log.push("There is no play to challenge.".to_string());
+            return;
+        };
+        if play.player == self.current_player {
+            self.log
+                .push("You can't challenge your own play.".to_string());
+            self.last_play = Some(play);
+            return;
+        }
+
+        self.outbox = Some(self.move_file(MailMove::Challenge));
+        let mut cleared = self.board.clear_tentative_from_board();
+        self.current_player_mut().letters.append(&mut cleared);
+        let challenger = self.current_player().name.clone();
+        if play.phonies.is_empty() {
+            self.log.push(format!(
+                "{challenger} challenged unsuccessfully and loses their turn."
+            ));
+            let rack = self.current_player().letters.clone();
+            let entry = Entry::Challenged {
+                player: self.current_player,
+                withdrawn: None,
+            };
+            self.record(entry, self.current_player, rack, Action::Pass, 0);
+            self.scoreless += 1;
+            self.next_turn();
+            return;
+        }
+
+        // The house rules may have taken points off since, and scores don't go below 0.
+        let lost = play.score.min(self.players[play.player].score);
+        let entry = Entry::Challenged {
+            player: self.current_player,
+            withdrawn: Some(Withdrawal {
+                player: play.player,
+                tiles: play.tiles.clone(),
+                drawn: play.drawn.clone(),
+                score: lost as isize,
+            }),
+        };
+        let positions = play.tiles.iter().map(|&(pos, _)| pos).collect::<Vec<_>>();
+        self.board.retract(&positions, &self.dict);
+        let player = &mut self.players[play.player];
+        player.score -= lost;
+        for tile in &play.drawn {
+            if let Some(idx) = player.letters.iter().position(|t| t == tile) {
+                self.letters_bag.push(player.letters.swap_remove(idx));
+            }
+        }
+        player
+            .letters
+            .extend(play.tiles.iter().map(|&(_, tile)| tile.drawn()));
+        self.letters_bag.shuffle(&mut self.rng);
+        self.log.push(format!(
+            "{challenger} challenged successfully, {:?} not in dictionary. {} loses {lost} points.",
+            play.phonies, player.name
+        ));
+        let rack = player.letters.clone();
+        self.record(
+            entry,
+            play.player,
+            rack,
+            Action::Withdrawn,
+            -(lost as isize),
+        );
+    }
+
+    fn maybe_toggle_letter(&mut self, letter: char) {
+        if let Some(idx) = self
+            .current_player()
+            .letters
+            .iter()
+            .position(|tile| !tile.is_blank && tile.letter == letter)
+        {
+            self.place_focused_from_rack(idx, letter);
+        } else {
+            self.log
+                .push("No such letter belonging to player.".to_string());
+        }
+    }
+
+    fn maybe_place_blank(&mut self, letter: char) {
+        if let Some(idx) = self
+            .current_player()
+            .letters
+            .iter()
+            .position(|tile| tile.is_blank)
+        {
+            self.place_focused_from_rack(idx, letter);
+        } else {
+            self.log.push("No blank belonging to player.".to_string());
+        }
+    }
+
+    // Places the tile at `idx` of the current player's rack on the focused square, a blank
+    // played as `letter`, and puts whatever tile was there back on the rack.
+    fn place_focused_from_rack(&mut self, idx: usize, letter: char) {
+        let tile = self.current_player().letters[idx].played_as(letter);
+        match self.board.place_focused_tentative(tile) {
+            Ok(Some(tile)) => self.current_player_mut().letters.push(tile.drawn()),
+            Err(e) => {
+                self.log.push(e.to_string());
+                return;
+            }
+            Ok(None) => (),
+        };
+        self.current_player_mut().letters.swap_remove(idx);
+    }
+
+    // Uses up one of the current player's hints, if they have any left.
+    fn take_hint(&mut self) -> bool {
+        match self.current_player().hints_left {
+            Some(0) => {
+                self.log.push("You have no hints left.".to_string());
+                return false;
+            }
+            Some(hints) => self.current_player_mut().hints_left = Some(hints - 1),
+            None => (),
+        }
+        true
+    }
+
+    // The current player's letters, the ones placed on the board included.
+    fn whole_rack(&self) -> Vec<Tile> {
+        let mut rack = self.current_player().letters.clone();
+        rack.extend(
+            self.board
+                .tentative()
+                .iter()
+                .filter_map(|pos| self.board.tile_at(pos))
+                .map(Tile::drawn),
+        );
+        rack
+    }
+
+    // Looks for a bingo with the current rack once a turn, for the indicator.
+    fn check_bingo(&mut self) {
+        let (turn, player) = (self.turn, self.current_player);
+        if !self.options.bingo_indicator
+            || self.bingo.is_some_and(|(t, p, _)| (t, p) == (turn, player))
+        {
+            return;
+        }
+        let rack = self.whole_rack();
+        let found = Solver::new(&self.dict, &self.board)
+            .with_openings(&self.openings)
+            .has_bingo(&rack);
+        self.bingo = Some((turn, player, found));
+        self.stale = true;
+    }
+
+    // Shades the board by where the current player's letters score the most, or hides the
+    // shading. Showing it takes a hint.
+    fn toggle_heatmap(&mut self) {
+        if self.board.has_heat() {
+            self.board.clear_heat();
+            return;
+        }
+        if !self.take_hint() {
+            return;
+        }
+        let rack = self.whole_rack();
+        let heat = Solver::new(&self.dict, &self.board)
+            .with_openings(&self.openings)
+            .best_by_square(&rack);
+        match heat.iter().max_by_key(|&(_, score)| score) {
+            Some((pos, score)) => self.log.push(format!(
+                "Hottest square: {} for {score} points.",
+                readout::square_name(pos)
+            )),
+            None => self
+                .log
+                .push("Your letters can't be played anywhere.".to_string()),
+        }
+        self.board.show_heat(heat);
+    }
+
+    // Shows the best placement on the board, and lists the best few with what goes into
+    // their ranking.
+    fn suggest(&mut self) -> EventResult {
+        if !self.take_hint() {
+            return EventResult::Consumed(None);
+        }
+        let top = self
+            .ranked_candidates()
+            .into_iter()
+            .filter_map(|evaluated| match evaluated.candidate {
+                Candidate::Play(play) => Some((play, evaluated.equity)),
+                _ => None,
+            })
+            .take(SUGGESTIONS)
+            .collect::<Vec<_>>();
+        let evaluations = self.evaluations(&top);
+        let best = top.into_iter().next();
+        if let Some((best, _)) = &best {
+            self.board.show_ghost(best.tiles.clone());
+        }
+        self.log.push(match best {
+            Some((best, equity)) => format!(
+                "Suggestion: {} {} from ({}, {}) for {} points, equity {:.1}.",
+                best.word,
+                if best.alignment == Alignment::Horizontal {
+                    "across"
+                } else {
+                    "down"
+                },
+                best.start.x,
+                best.start.y,
+                best.score,
+                equity
+            ),
+            None => "No possible placements found.".to_string(),
+        });
+        if evaluations.is_empty() {
+            return EventResult::Consumed(None);
+        }
+        EventResult::Consumed(Some(Callback::from_fn(move |s| {
+            s.add_layer(Dialog::info(evaluations.clone()).title("Best placements"));
+        })))
+    }
+
+    // Every candidate for the current player's whole rack, the best first, worked out
+    // again only once the board, the rack or the bag has changed since the last time.
+    fn ranked_candidates(&mut self) -> Vec<Evaluated> {
+        let mut rack = self.whole_rack();
+        rack.sort_unstable();
+        let key = (self.board.revision(), rack, self.letters_bag.len());
+        if let Some((cached, candidates)) = &self.hints {
+            if *cached == key {
+                return candidates.clone();
+            }
+        }
+        // Placements are ranked by their equity, the score plus the value of the leave,
+        // so a suggestion doesn't trade good letters for a couple of points.
+        let candidates = Solver::new(&self.dict, &self.board)
+            .with_openings(&self.openings)
+            .candidates(&key.1, &self.leaves, self.letters_bag.len());
+        self.hints = Some((key, candidates.clone()));
+        candidates
+    }
+
+    // How `plays` with their equities come by their ranking: the score, the value of the
+    // letters kept and what the play opens up for the opponent, which bots weigh too.
+    fn evaluations(&self, plays: &[(Move, f64)]) -> String {
+        let open = strategy::premiums_in_reach(&self.board);
+        let lines = plays
+            .iter()
+            .enumerate()
+            .map(|(i, (play, equity))| {
+                let openness = strategy::openness(&self.dict, &self.board, &open, play);
+                format!(
+                    "{}. {} {} at {}: {} points {:+.1} leave = {:.1} equity, opens {:.1}",
+                    i + 1,
+                    play.word,
+                    if play.alignment == Alignment::Horizontal {
+                        "across"
+                    } else {
+                        "down"
+                    },
+                    readout::square_name(&play.start),
+                    play.score,
+                    equity - play.score as f64,
+                    equity,
+                    openness,
+                )
+            })
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            return String::new();
+        }
+        format!(
+            "{}\n\nRanked by equity, the score plus the value of the letters kept. \
+             \"Opens\" is roughly what the premiums a play brings within reach are worth \
+             to the opponent.",
+            lines.join("\n")
+        )
+    }
+
+    // Readouts replace each other on their own line in readout mode, and are logged
+    // like anything else otherwise.
+    fn announce(&mut self, text: String) {
+        if self.display.readout {
+            self.readout = text;
+        } else {
+            self.log.push(text);
+        }
+    }
+
+    fn read_last_play(&mut self) {
+        let text = match &self.last_play {
+            Some(play) => readout::play(&self.players[play.player].name, &play.tiles, play.score),
+            None => "No placement to read.".to_string(),
+        };
+        self.announce(text);
+    }
+
+    // Asks the seat of the player to move, who isn't at the screen, for their move. A
+    // challenge is made at once, and the seat asked again on the next tick.
+    fn request_move(&mut self) -> EventResult {
+        let player = self.current_player;
+        let rack = &self.players[player].letters;
+        // The opponent's rack is known exactly once the bag is empty, and the endgame
+        // can be looked into instead of guessed at.
+        let opponent = self.endgame_opponent(rack);
+        let lexicon = self.players[player].lexicon.as_ref();
+        let phonies: &[String] = match &self.last_play {
+            Some(play) if play.player != player => &play.phonies,
+            _ => &[],
+        };
+        let position = Position {
+            dict: lexicon.unwrap_or(&self.dict),
+            // Openings are cached for the game's dictionary only.
+            openings: lexicon.is_none().then_some(&self.openings),
+            board: &self.board,
+            rack,
+            turn: self.turn,
+            opponent,
+            phonies,
+        };
+        let Some(decision) = self.providers[player].request(&position) else {
+            return EventResult::Consumed(None);
+        };
+        self.stale = true;
+        if decision == Decision::Challenge {
+            self.challenge();
+            return EventResult::Consumed(None);
+        }
+        match self.check_decision(&decision) {
+            Ok(()) => {
+                let score = match &decision {
+                    Decision::Play(tiles) => self.placement_score(tiles),
+                    _ => 0,
+                };
+                self.bot_turn = Some(BotTurn {
+                    started: Instant::now(),
+                    decision,
+                    shown: 0,
+                    score,
+                });
+            }
+            Err(e) => self.log.push(format!(
+                "{} can't make that move: {e}",
+                self.current_player().name
+            )),
+        }
+        EventResult::Consumed(None)
+    }
+
+    // Advances the turn of a player who isn't at the screen on every refresh: asks their
+    // seat for a move, places the tiles that are due, then confirms the move a tile's
+    // time after the last one appeared.
+    fn tick(&mut self) -> EventResult {
+        if self.at_screen(self.current_player) {
+            self.check_bingo();
+            return EventResult::Ignored;
+        }
+        let Some(turn) = &mut self.bot_turn else {
+            return self.request_move();
+        };
+        let due = self.display.pacing.tiles_due(turn.started.elapsed());
+        let Decision::Play(play) = &turn.decision else {
+            if due == 0 {
+                return EventResult::Consumed(None);
+            }
+            let Some(turn) = self.bot_turn.take() else {
+                return EventResult::Consumed(None);
+            };
+            self.stale = true;
+            return self.carry_out(turn.decision).unwrap_or_else(|e| {
+                self.log.push(e.to_string());
+                self.pass()
+            });
+        };
+
+        let tiles = play[turn.shown..due.min(play.len())].to_vec();
+        let finished = due > play.len();
+        turn.shown += tiles.len();
+        self.stale |= !tiles.is_empty() || finished;
+        for (pos, tile) in tiles {
+            self.place_from_rack(pos, tile);
+        }
+        // The move was checked when it was handed over, but a seat shouldn't hang on
+        // one that doesn't stand.
+        if finished {
+            self.bot_turn = None;
+            if let Err(e) = self.confirm_or_take_back() {
+                self.log.push(e.to_string());
+                return self.pass();
+            }
+        }
+        EventResult::Consumed(None)
+    }
+
+    // The bot's score counts up as the tiles of its move appear.
+    fn shown_score(&self, player: PlayerIndex) -> usize {
+        let player_score = self.players[player].score;
+        match &self.bot_turn {
+            Some(BotTurn {
+                decision: Decision::Play(tiles),
+                shown,
+                score,
+                ..
+            }) if player == self.current_player && !tiles.is_empty() => {
+                player_score + score * shown / tiles.len()
+            }
+            _ => player_score,
+        }
+    }
+
+    // What placing `tiles` scores, nothing if they don't form a word.
+    fn placement_score(&self, tiles: &[(Vec2, Tile)]) -> usize {
+        let mut board = self.board.clone();
+        for &(pos, tile) in tiles {
+            board.place_at(tile, &pos);
+        }
+        rules::validate(&board, &self.options.placement).map_or(0, |word_squares| {
+            word_squares
+                .iter()
+                .map(|squares| Self::word_score(squares))
+                .sum()
+        })
+    }
+
+    // Moves `tile`, as drawn, from the current player's rack onto the board at `pos`.
+    fn place_from_rack(&mut self, pos: Vec2, tile: Tile) {
+        self.board.place_at(tile, &pos);
+        let letters = &mut self.current_player_mut().letters;
+        if let Some(idx) = letters.iter().position(|&held| held == tile.drawn()) {
+            letters.swap_remove(idx);
+        }
+    }
+
+    // Whether the current player can make `decision`: a play's tiles on their rack and
+    // its squares free, or an exchange's tiles on their rack and enough in the bag.
+    fn check_decision(&self, decision: &Decision) -> Result<(), ScrabbleError> {
+        let mut rack = self.whole_rack();
+        let mut take = |tile: Tile| match rack.iter().position(|&held| held == tile.drawn()) {
+            Some(idx) => Ok(rack.swap_remove(idx)),
+            None => Err(ScrabbleError::NotOnRack(tile.label().to_string())),
+        };
+        match decision {
+            Decision::Play(tiles) => {
+                let size = self.board.size;
+                for &(pos, tile) in tiles {
+                    take(tile)?;
+                    if pos.x >= size.x || pos.y >= size.y || self.board.letter_at(&pos).is_some() {
+                        return Err(ScrabbleError::IllegalMove(format!(
+                            "{} can't be played on {}",
+                            tile.label(),
+                            readout::square_name(&pos)
+                        )));
+                    }
+                }
+                Ok(())
+            }
+            Decision::Exchange(tiles) => {
+                for &tile in tiles {
+                    take(tile)?;
+                }
+                self.check_exchange(tiles.len())
+            }
+            Decision::Pass | Decision::Challenge => Ok(()),
+        }
+    }
+
+    // Makes the move the current player's seat decided on, as it would be made at the
+    // screen, with any tiles they'd placed taken back first.
+    fn carry_out(&mut self, decision: Decision) -> Result<EventResult, ScrabbleError> {
+        let mut cleared = self.board.clear_tentative_from_board();
+        self.current_player_mut().letters.append(&mut cleared);
+        self.check_decision(&decision)?;
+        let result = match decision {
+            Decision::Play(tiles) => {
+                for (pos, tile) in tiles {
+                    self.place_from_rack(pos, tile);
+                }
+                self.confirm_or_take_back()?;
+                EventResult::Consumed(None)
+            }
+            Decision::Exchange(tiles) => {
+                let letters = &mut self.current_player_mut().letters;
+                let mut taken = Vec::new();
+                for tile in tiles {
+                    if let Some(idx) = letters.iter().position(|&held| held == tile.drawn()) {
+                        taken.push(letters.swap_remove(idx));
+                    }
+                }
+                self.exchange(taken);
+                self.end_exchange()
+            }
+            Decision::Pass => self.pass(),
+            Decision::Challenge => {
+                self.challenge();
+                EventResult::Consumed(None)
+            }
+        };
+        // Moves made off the screen, by bots or through `apply_move`, show up all the same.
+        self.stale = true;
+        Ok(result)
+    }
+
+    // Confirms the tiles placed, or takes them back onto the rack, with why, if they
+    // can't stand.
+    fn confirm_or_take_back(&mut self) -> Result<(), ScrabbleError> {
+        let logged = self.log.len();
+        self.confirm();
+        if self.board.tentative().is_empty() {
+            return Ok(());
+        }
+        let mut cleared = self.board.clear_tentative_from_board();
+        self.current_player_mut().letters.append(&mut cleared);
+        let reason = self.log.get(logged..).and_then(<[_]>::last).cloned();
+        Err(ScrabbleError::IllegalMove(reason.unwrap_or_default()))
+    }
+
+    fn scoreless_limit(&self) -> usize {
+        self.options
+            .scoreless_turns
+            .unwrap_or(self.active_players())
+    }
+
+    fn active_players(&self) -> usize {
+        self.players
+            .iter()
+            .filter(|player| !player.resigned)
+            .count()
+    }
+
+    // The current player leaves the game with their letters and score. Once a single
+    // player is left the game is over.
+    fn resign(&mut self) -> EventResult {
+        let mut cleared = self.board.clear_tentative_from_board();
+        self.current_player_mut().letters.append(&mut cleared);
+        self.current_player_mut().resigned = true;
+        self.log
+            .push(format!("{} resigned.", self.current_player().name));
+        if self.active_players() <= 1 {
+            return self.game_over();
+        }
+        self.bot_turn = None;
+        self.last_play = None;
+        self.clock.end_turn(self.current_player);
+        self.current_player = self.next_active();
+        self.turn_started();
+        EventResult::Consumed(None)
+    }
+
+    // The next player after the current one who hasn't resigned.
+    fn next_active(&self) -> PlayerIndex {
+        let players = self.players.len();
+        (1..=players)
+            .map(|i| (self.current_player + i) % players)
+            .find(|&i| !self.players[i].resigned)
+            .unwrap_or(self.current_player)
+    }
+
+    fn game_over(&mut self) -> EventResult {
+        let scores = (0..self.players.len())
+            .map(|i| (self.players[i].name.clone(), self.end_score(i)))
+            .collect();
+        self.emit(GameEvent::GameEnded { scores });
+        let standings = self.final_standings();
+        let analysis = format!("{}\n\n{}", self.charts(), self.word_odds());
+        let archived = self.archive_game();
+        let mut table = Vec::new();
+        // Writing to memory doesn't fail.
+        let _ = csv::write(&self.names(), self.journal.turns(), &mut table);
+        let tracking = self.tracking_sheet();
+        let sheet = tracking.to_string();
+        let mut sheets = Vec::new();
+        let _ = printout::write(
+            &self.names(),
+            self.journal.turns(),
+            Some(&tracking),
+            &mut sheets,
+        );
+        EventResult::Consumed(Some(Callback::from_fn(move |s| {
+            let (table, analysis) = (table.clone(), analysis.clone());
+            let (sheet, sheets) = (sheet.clone(), sheets.clone());
+            s.add_layer(
+                Dialog::around(TextView::new(format!("{standings}{archived}")).scrollable())
+                    .title("GAME OVER")
+                    .button("Rematch", |s| {
+                        s.pop_layer();
+                        Self::on_game(s, Self::rematch);
+                    })
+                    .button("View analysis", move |s| {
+                        s.add_layer(
+                            Dialog::around(TextView::new(analysis.clone()).scrollable())
+                                .title("Analysis")
+                                .dismiss_button("Close"),
+                        );
+                    })
+                    .button("Export CSV", move |s| {
+                        const PATH: &str = "scrabbler.csv";
+                        s.add_layer(Dialog::info(match fs::write(PATH, &table) {
+                            Ok(()) => format!("Wrote the scores to {PATH}."),
+                            Err(e) => format!("Failed to write {PATH}: {e}"),
+                        }));
+                    })
+                    .button("Export tracking sheet", move |s| {
+                        const PATH: &str = "scrabbler-tracking.txt";
+                        s.add_layer(Dialog::info(match fs::write(PATH, &sheet) {
+                            Ok(()) => format!("Wrote the tracking sheet to {PATH}."),
+                            Err(e) => format!("Failed to write {PATH}: {e}"),
+                        }));
+                    })
+                    .button("Export for printing", move |s| {
+                        const PATH: &str = "scrabbler-sheets.md";
+                        s.add_layer(Dialog::info(match fs::write(PATH, &sheets) {
+                            Ok(()) => format!("Wrote the score and tracking sheets to {PATH}."),
+                            Err(e) => format!("Failed to write {PATH}: {e}"),
+                        }));
+                    })
+                    // Back to the setup screen, under the game.
+                    .button("Close", |s| {
+                        s.pop_layer();
+                        s.pop_layer();
+                    }),
+            );
+        })))
+    }
+
+    // The seat that goes first by `rule`, with the tiles drawn for it to tell in the log.
+    fn pick_first(
+        profiles: &[PlayerProfile],
+        all_tiles: &[Tile],
+        rng: &mut StdRng,
+        rule: FirstPlayer,
+    ) -> (PlayerIndex, Vec<String>) {
+        // Draws that keep tying, with a bag of a single kind of tile, go to the first
+        // player listed of those left.
+        const DRAWS: usize = 10;
+        let mut drawing = (0..profiles.len()).collect::<Vec<_>>();
+        let mut told = Vec::new();
+        match rule {
+            FirstPlayer::Listed => (),
+            FirstPlayer::Random => drawing = drawing.choose(rng).into_iter().copied().collect(),
+            FirstPlayer::Draw => {
+                for _ in 0..DRAWS {
+                    if drawing.len() < 2 {
+                        break;
+                    }
+                    let drawn = all_tiles
+                        .choose_multiple(rng, drawing.len())
+                        .copied()
+                        .collect::<Vec<_>>();
+                    told.push(format!(
+                        "{}.",
+                        drawing
+                            .iter()
+                            .zip(&drawn)
+                            .map(|(&i, tile)| format!("{} drew {tile}", profiles[i].name))
+                            .join(", ")
+                    ));
+                    // The blank, a space, comes before A.
+                    let Some(&closest) = drawn.iter().min() else {
+                        break;
+                    };
+                    drawing = drawing
+                        .into_iter()
+                        .zip(drawn)
+                        .filter(|&(_, tile)| tile == closest)
+                        .map(|(i, _)| i)
+                        .collect();
+                }
+            }
+        }
+        (drawing.first().copied().unwrap_or(0), told)
+    }
+
+    /// Starts over with the same players and settings, and a fresh bag, the player after
+    /// the one who went first last time going first. Seeded games stay seeded, by a seed
+    /// drawn from the one before, so copies played by mail agree.
+    pub fn rematch(&mut self) {
+        let rng = StdRng::seed_from_u64(self.rng.gen());
+        let mut fresh = Self::deal(
+            self.dict.clone(),
+            &self.profiles,
+            self.distribution.clone(),
+            rng,
+            self.options,
+            self.display.clone(),
+            Some((self.first + 1) % self.players.len()),
+        );
+        fresh.archive = self.archive.take();
+        fresh.house_rules = self.house_rules.take();
+        fresh.house_words = mem::take(&mut self.house_words);
+        fresh.definitions = self.definitions.clone();
+        if let Some(layout) = self.layout.take() {
+            fresh.board = Self::dress(layout.clone(), &fresh.options, &fresh.display);
+            fresh.layout = Some(layout);
+        }
+        fresh.leaves = mem::take(&mut self.leaves);
+        fresh.openings = mem::take(&mut self.openings);
+        fresh.subscribers = mem::take(&mut self.subscribers);
+        fresh.providers = mem::take(&mut self.providers);
+        fresh.word_lists = self.word_lists.take();
+        for (player, before) in fresh.players.iter_mut().zip(&mut self.players) {
+            player.lexicon = before.lexicon.take();
+            player.rack_order = before.rack_order;
+            player.arrange();
+        }
+        fresh.log.push("Rematch!".to_string());
+        *self = fresh;
+    }
+
+    fn pass(&mut self) -> EventResult {
+        self.scoreless += 1;
+        self.outbox = Some(self.move_file(MailMove::Pass));
+        self.log
+            .push(format!("{} passed their turn.", self.current_player().name));
+        let mut cleared = self.board.clear_tentative_from_board();
+        self.current_player_mut().letters.append(&mut cleared);
+        let rack = self.current_player().letters.clone();
+        let entry = Entry::Passed {
+            player: self.current_player,
+        };
+        self.record(entry, self.current_player, rack, Action::Pass, 0);
+        if self.scoreless >= self.scoreless_limit() {
+            return self.game_over();
+        }
+        self.next_turn();
+        EventResult::Consumed(None)
+    }
+
+    /// Tells `subscriber` about every turn from here on and about the end of the game.
+    pub fn subscribe(mut self, subscriber: impl Subscriber + 'static) -> Self {
+        self.subscribers.push(Box::new(subscriber));
+        self
+    }
+
+    /// Plays by the house rules of a script, see `HouseRules`.
+    pub fn with_house_rules(mut self, rules: Arc<HouseRules>) -> Self {
+        self.house_rules = Some(rules);
+        self
+    }
+
+    /// Marks the words of `words` in the log when they're played, see `Lexicon::house_words`.
+    pub fn with_house_words(mut self, words: HashSet<String>) -> Self {
+        self.house_words = words;
+        self
+    }
+
+    /// Looks words up in `definitions`, see `Lexicon::definitions`.
+    pub fn with_definitions(mut self, definitions: Arc<HashMap<String, String>>) -> Self {
+        self.definitions = definitions;
+        self
+    }
+
+    /// Ranks suggestions by `leaves` as well as by score.
+    pub fn with_leaves(mut self, leaves: Leaves) -> Self {
+        self.leaves = leaves;
+        self
+    }
+
+    /// Continues the game of `record` from its last turn: its plays are put on the
+    /// board, the scores set to their totals and the seats named after its players. The
+    /// bag is made of the tiles not on the board, and the racks are dealt from it anew,
+    /// as the record doesn't tell what they held after the last turn.
+    pub fn resume(mut self, record: gcg::Record) -> Result<Self, ScrabbleError> {
+        if record.players.len() != self.players.len() {
+            return Err(ScrabbleError::Seats {
+                record: record.players.len(),
+                seats: self.players.len(),
+            });
+        }
+
+        // Blanks are lower case in the record, and taken from the bag as blanks.
+        gcg::replay(
+            &mut self.board,
+            &self.dict,
+            &self.distribution,
+            &record.turns,
+        )?;
+        for turn in &record.turns {
+            self.players[turn.player].score = turn.total.max(0) as usize;
+        }
+
+        let mut bag = self.all_tiles.clone();
+        for y in 0..self.board.size.y {
+            for x in 0..self.board.size.x {
+                let pos = Vec2::new(x, y);
+                let Some(tile) = self.board.committed_tile_at(&pos) else {
+                    continue;
+                };
+                if let Some(idx) = bag.iter().position(|t| t.letter == tile.drawn().letter) {
+                    bag.swap_remove(idx);
+                }
+            }
+        }
+        bag.shuffle(&mut self.rng);
+        for (player, name) in self.players.iter_mut().zip(record.players) {
+            let size = player.rack_size.min(bag.len());
+            player.letters = bag.drain(0..size).collect();
+            player.name = name;
+        }
+
+        self.letters_bag = bag;
+        self.turn = record.turns.len();
+        self.current_player = record.turns.last().map_or(self.current_player, |turn| {
+            (turn.player + 1) % self.players.len()
+        });
+        self.first = record.turns.first().map_or(self.first, |turn| turn.player);
+        self.journal = Journal::resumed(self.snapshot(), record.turns);
+        self.log
+            .push(format!("Resumed the game after {} turns.", self.turn));
+        Ok(self)
+    }
+
+    /// Keeps the game in `archive` once it's over.
+    pub fn with_archive(mut self, archive: Archive) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    // Saves the finished game to the archive, if any, and says whether it could.
+    fn archive_game(&self) -> String {
+        let Some(archive) = &self.archive else {
+            return String::new();
+        };
+        let names = self.names();
+        let scores = (0..self.players.len())
+            .map(|i| self.end_score(i))
+            .collect::<Vec<_>>();
+        let mut record = Vec::new();
+        let saved = gcg::write(&names, self.journal.turns(), &mut record)
+            .map_err(ScrabbleError::from)
+            .and_then(|()| archive.save(&names, &scores, &String::from_utf8_lossy(&record)));
+        match saved {
+            Ok(()) => "\n\nThe game was added to the history.".to_string(),
+            Err(e) => format!("\n\nFailed to add the game to the history: {e}"),
+        }
+    }
+
+    /// Plays on a board with the premiums of `layout` instead, see `Board::from_layout`,
+    /// which may be any shape.
+    pub fn with_layout(mut self, layout: &str) -> Result<Self, ScrabbleError> {
+        let layout = Board::from_layout(layout)?;
+        self.board = Self::dress(layout.clone(), &self.options, &self.display);
+        self.layout = Some(layout);
+        Ok(self)
+    }
+
+    // `board` as the options and display options have it shown.
+    fn dress(board: Board, options: &Options, display: &DisplayOptions) -> Board {
+        let board = board
+            .with_palette(display.palette)
+            .with_premiums(display.premiums.clone());
+        if options.blind_premiums {
+            board.with_hidden_premiums()
+        } else {
+            board
+        }
+    }
+
+    /// Sets up the position of `scenario`, with its rack for the first player who isn't
+    /// a bot to move, and the other racks and the bag made of the tiles left.
+    pub fn with_scenario(mut self, scenario: &Scenario) -> Result<Self, ScrabbleError> {
+        if !scenario.layout.trim().is_empty() {
+            self = self.with_layout(&scenario.layout)?;
+        }
+        let tiles = scenario.tiles(self.board.size)?;
+        let rack = scenario.rack()?;
+        let mut bag = self.all_tiles.clone();
+        // Blanks are taken from the bag as blanks, and played as the letter they stand for.
+        let mut take = |letter: char| match bag.iter().position(|t| t.letter == letter) {
+            Some(idx) => Ok(bag.swap_remove(idx)),
+            None => {
+                let label = if letter == ' ' { '?' } else { letter };
+                Err(scenario.invalid(format!("more {label} than the bag holds")))
+            }
+        };
+        let mut placed = Vec::new();
+        for &(pos, letter, blank) in &tiles {
+            let tile = take(if blank { ' ' } else { letter })?;
+            placed.push((pos, tile.played_as(letter)));
+        }
+        let rack = rack
+            .iter()
+            .map(|&letter| take(letter))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (pos, tile) in placed {
+            self.board.place_at(tile, &pos);
+        }
+        self.board.commit_tentative(&self.dict);
+
+        bag.shuffle(&mut self.rng);
+        if let Some(first) = (0..self.players.len()).find(|&i| self.at_screen(i)) {
+            self.current_player = first;
+            self.first = first;
+        }
+        let current = self.current_player;
+        for (i, player) in self.players.iter_mut().enumerate() {
+            player.letters = if i == current {
+                rack.clone()
+            } else {
+                let size = player.rack_size.min(bag.len());
+                bag.drain(0..size).collect()
+            };
+        }
+        if scenario.empty_bag {
+            bag.clear();
+        }
+        self.letters_bag = bag;
+        self.journal = Journal::new(self.snapshot());
+        self.log
+            .push(format!("{}: {}", scenario.name, scenario.goal));
+        Ok(self)
+    }
+
+    /// Has the moves of seat `player` come from `provider`, instead of the screen or the
+    /// bot its profile sets up.
+    pub fn with_provider(
+        mut self,
+        player: usize,
+        provider: impl MoveProvider<D> + 'static,
+    ) -> Self {
+        if let Some(seat) = self.providers.get_mut(player) {
+            *seat = Box::new(provider);
+        }
+        self
+    }
+
+    // Where the moves of the player set up as `profile` come from.
+    fn provider(profile: &PlayerProfile) -> Box<dyn MoveProvider<D>> {
+        match &profile.mailbox {
+            Some(path) => Box::new(Mailbox::new(path.clone())),
+            None if profile.bot => Box::new(Bot::new(profile.effort)),
+            None => Box::new(Screen),
+        }
+    }
+
+    fn at_screen(&self, player: PlayerIndex) -> bool {
+        self.providers[player].at_screen()
+    }
+
+    /// Has the bot in seat `player` find its moves in `lexicon`, see `PlayerProfile`.
+    pub fn with_lexicon(mut self, player: usize, lexicon: D) -> Self {
+        if let Some(player) = self.players.get_mut(player) {
+            player.lexicon = Some(lexicon);
+        }
+        self
+    }
+
+    fn endgame_outlook(&mut self) {
+        let mut board = self.board.clone();
+        let mut rack = self.current_player().letters.clone();
+        rack.append(&mut board.clear_tentative_from_board());
+        let Some((opponent, lead)) = self.endgame_opponent(&rack) else {
+            self.announce(
+                "The endgame outlook is for two player games with an empty bag.".to_string(),
+            );
+            return;
+        };
+        let outlook = endgame::outlook(
+            &self.dict,
+            &board,
+            &rack,
+            &opponent,
+            lead,
+            Effort::default(),
+        );
+
+        let play = match &outlook.play {
+            Some(play) => format!("{} for {}", play.notation(), play.score),
+            None => "passing".to_string(),
+        };
+        self.announce(match outlook.margin {
+            margin if margin > 0 => format!("Endgame: {play} wins by at least {margin}."),
+            0 => format!("Endgame: {play} ties at worst."),
+            margin => format!(
+                "Endgame: no play guarantees a win, {play} loses by at most {}.",
+                -margin
+            ),
+        });
+    }
+
+    // The tiles in the bag, a letter with its count at a time, and the letters on every
+    // rack, blanks as '?'.
+    fn coach_view(&self) -> String {
+        let letters = |tiles: &[Tile]| {
+            tiles
+                .iter()
+                .map(|tile| tile.label())
+                .sorted()
+                .collect::<String>()
+        };
+        let bag = letters(&self.letters_bag)
+            .chars()
+            .dedup_with_count()
+            .map(|(count, letter)| format!("{letter}{count}"))
+            .join(" ");
+        let mut lines = vec![format!("Bag ({}): {bag}", self.letters_bag.len())];
+        lines.extend(
+            self.players
+                .iter()
+                .map(|player| format!("{}: {}", player.name, letters(&player.letters))),
+        );
+        lines.join("\n")
+    }
+
+    // The tiles neither on the board nor on `rack`: what's left in the bag and on the
+    // racks of the other players still in the game. Resigned players took theirs along.
+    fn unseen(&self, rack: &[Tile]) -> Vec<Tile> {
+        let mut unseen = self.all_tiles.clone();
+        let on_board = (0..self.board.size.y)
+            .flat_map(|y| (0..self.board.size.x).map(move |x| Vec2::new(x, y)))
+            .filter_map(|pos| self.board.committed_tile_at(&pos))
+            .map(Tile::drawn);
+        let resigned = self
+            .players
+            .iter()
+            .filter(|player| player.resigned)
+            .flat_map(|player| player.letters.iter().copied());
+        for tile in on_board.chain(resigned).chain(rack.iter().copied()) {
+            if let Some(idx) = unseen.iter().position(|&t| t == tile) {
+                unseen.swap_remove(idx);
+            }
+        }
+        unseen
+    }
+
+    // Every tile of the game with the ones on the board crossed off, which are the ones
+    // neither in the bag nor on a rack, blanks included.
+    fn tracking_sheet(&self) -> TrackingSheet {
+        let unplayed = self
+            .players
+            .iter()
+            .flat_map(|player| player.letters.iter())
+            .chain(&self.letters_bag)
+            .copied()
+            .collect::<Vec<_>>();
+        TrackingSheet::new(&self.all_tiles, &unplayed)
+    }
+
+    // The rack of the single opponent left once the bag is empty, all the unseen tiles,
+    // and how far the current player leads them.
+    fn endgame_opponent(&self, rack: &[Tile]) -> Option<(Vec<Tile>, isize)> {
+        if !self.letters_bag.is_empty() {
+            return None;
+        }
+        let opponent = self.opponent()?;
+        Some((self.unseen(rack), self.lead(opponent)))
+    }
+
+    // The one other player still in the game, if there's only one.
+    fn opponent(&self) -> Option<PlayerIndex> {
+        let mut others = (0..self.players.len())
+            .filter(|&i| i != self.current_player && !self.players[i].resigned);
+        match (others.next(), others.next()) {
+            (Some(opponent), None) => Some(opponent),
+            _ => None,
+        }
+    }
+
+    fn lead(&self, opponent: PlayerIndex) -> isize {
+        self.current_player().score as isize - self.players[opponent].score as isize
+    }
+
+    fn remove_focused(&mut self) {
+        if let Some(letter) = self.board.clear_focused() {
+            self.current_player_mut().letters.push(letter);
+        }
+    }
+
+    fn current_player(&self) -> &Player<D> {
+        &self.players[self.current_player]
+    }
+
+    fn current_player_mut(&mut self) -> &mut Player<D> {
+        &mut self.players[self.current_player]
+    }
+
+    fn exchange_letters(&mut self) -> Result<(), ScrabbleError> {
+        self.check_exchange(self.board.tentative().len())?;
+        let tiles = self.board.clear_tentative_from_board();
+        self.outbox = Some(self.move_file(MailMove::Exchange(
+            tiles.iter().map(|tile| tile.label()).collect(),
+        )));
+        self.exchange(tiles);
+        Ok(())
+    }
+
+    // Exchanges the tiles written `labels` straight from the rack, with any tentative
+    // letters put back on it first.
+    fn exchange_rack_letters(&mut self, labels: Vec<char>) -> Result<(), ScrabbleError> {
+        self.check_exchange(labels.len())?;
+        let mut cleared = self.board.clear_tentative_from_board();
+        self.current_player_mut().letters.append(&mut cleared);
+        let mut rack = self.current_player().letters.clone();
+        let mut tiles = Vec::new();
+        for &label in &labels {
+            match rack.iter().position(|tile| tile.is_written(label)) {
+                Some(idx) => tiles.push(rack.swap_remove(idx)),
+                None => return Err(ScrabbleError::NotOnRack(labels.iter().collect())),
+            };
+        }
+        self.current_player_mut().letters = rack;
+        self.outbox = Some(self.move_file(MailMove::Exchange(labels)));
+        self.exchange(tiles);
+        Ok(())
+    }
+
+    fn check_exchange(&self, wanted: usize) -> Result<(), ScrabbleError> {
+        let left = self.letters_bag.len();
+        if !can_exchange(wanted, left) {
+            return Err(ScrabbleError::Exchange { wanted, left });
+        }
+        Ok(())
+    }
+
+    fn end_exchange(&mut self) -> EventResult {
+        self.next_turn();
+        if self.scoreless >= self.scoreless_limit() {
+            return self.game_over();
+        }
+        EventResult::Consumed(None)
+    }
+
+    // Swaps tiles already taken off the current player's rack with ones from the bag.
+    // They go into the bag in a fixed order, so a seeded bag shuffles the same way
+    // whatever order they were taken off the rack in.
+    fn exchange(&mut self, mut tiles: Vec<Tile>) {
+        let mut rack = self.current_player().letters.clone();
+        rack.extend(&tiles);
+        let action = Action::Exchange(tiles.iter().map(|tile| tile.letter).collect());
+        tiles.sort_unstable();
+        let entry = Entry::Exchanged {
+            player: self.current_player,
+            tiles: tiles.clone(),
+        };
+        self.record(entry, self.current_player, rack, action, 0);
+        self.scoreless += 1;
+        self.letters_bag.append(&mut tiles);
+        self.letters_bag.shuffle(&mut self.rng);
+        self.refill(self.current_player);
+    }
+
+    // Adds `entry`, which starts a turn, to the journal along with the turn as it goes in
+    // the game record. Racks are recorded as drawn, blanks as spaces.
+    fn record(
+        &mut self,
+        entry: Entry,
+        player: PlayerIndex,
+        rack: Vec<Tile>,
+        action: Action,
+        score: isize,
+    ) {
+        let name = self.players[player].name.clone();
+        let event = match &action {
+            Action::Play { .. } => GameEvent::MovePlayed {
+                player: name,
+                play: action.to_string(),
+                score,
+            },
+            Action::Exchange(letters) => GameEvent::TilesExchanged {
+                player: name,
+                count: letters.len(),
+            },
+            Action::Pass => GameEvent::Passed { player: name },
+            Action::Withdrawn => GameEvent::PlayWithdrawn {
+                player: name,
+                score: -score,
+            },
+        };
+        let turn = Turn {
+            player,
+            rack: rack.iter().map(|tile| tile.drawn().letter).collect(),
+            action,
+            score,
+            total: self.players[player].score as isize,
+            notes: Vec::new(),
+        };
+        self.journal.push_turn(entry, turn, &self.dict);
+        self.emit(event);
+    }
+
+    fn turn_started(&mut self) {
+        self.emit(GameEvent::TurnStarted {
+            player: self.current_player().name.clone(),
+            turn: self.turn,
+        });
+    }
+
+    fn emit(&mut self, event: GameEvent) {
+        for subscriber in &mut self.subscribers {
+            subscriber.notify(&event);
+        }
+    }
+
+    /// Attaches a comment to the latest turn, kept as a note in the GCG export.
+    pub fn annotate(&mut self, note: &str) {
+        let note = note.trim();
+        match self.journal.last_turn_mut() {
+            _ if note.is_empty() => (),
+            Some(turn) => {
+                turn.notes.push(note.to_string());
+                self.log.push(format!("Noted: {note}"));
+            }
+            None => self
+                .log
+                .push("There is no move to annotate yet.".to_string()),
         }
+    }
 
-        Self {
-            board: Board::new(BOARD_SIZE),
-            current_player: 0,
-            dict,
-            letters_bag: letters,
-            log: vec!["Game started! Good luck :)".to_string()],
-            passes: 0,
-            players,
-            turn: 0,
+    // The scores over the game, and in two player games how the lead swung.
+    fn charts(&self) -> String {
+        const HEIGHT: usize = 10;
+        let names = self.names();
+        let scores = chart::scores(&names, self.journal.turns(), HEIGHT);
+        if self.players.len() == 2 {
+            let spread = chart::spread(self.journal.turns(), HEIGHT);
+            format!("{scores}\n\n{}'s lead\n{spread}", names[0])
+        } else {
+            scores
         }
     }
 
-    fn validate_placement(&mut self) -> Result<Vec<Vec<Cell>>, String> {
-        if self.board.tentative().is_empty() {
-            return Err("No letters placed.".to_string());
+    // Every word played and not withdrawn, with how likely its letters were to be drawn
+    // and how it ranks by that among the words of its length.
+    fn word_odds(&self) -> String {
+        let mut odds = Odds::new(&self.dict, &self.all_tiles);
+        let lines = self
+            .kept_plays()
+            .into_iter()
+            .filter_map(|(player, coordinate, word, score)| {
+                let played = word.chars().filter(|&letter| letter != '.').count();
+                let word = self.full_word(coordinate, word)?;
+                let rarity = odds.rarity(&word);
+                let chance = match rarity.probability {
+                    p if p > 0.0 => format!("1 in {:.0} draws", 1.0 / p),
+                    _ => "only drawn with blanks".to_string(),
+                };
+                Some(format!(
+                    "{word}{} by {} for {score}: {chance}, {} of {} words of {} letters",
+                    if played >= N_LETTERS { " (bingo)" } else { "" },
+                    self.players[player].name,
+                    rarity.rank,
+                    rarity.of,
+                    word.len()
+                ))
+            })
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            "No words played.".to_string()
+        } else {
+            format!("Words played\n{}", lines.join("\n"))
         }
+    }
 
-        if self.turn > 0 && !self.board.is_connected() {
-            return Err("Letters not connected to existing grid.".to_string());
-        } else if self.turn == 0 && !self.board.tentative().contains(&self.board.center_pos()) {
-            return Err("First placement must contain center square.".to_string());
+    fn export_gcg(&mut self) {
+        const PATH: &str = "scrabbler.gcg";
+        let names = self.names();
+        let result = fs::File::create(PATH)
+            .and_then(|file| gcg::write(&names, self.journal.turns(), io::BufWriter::new(file)));
+        self.log.push(match result {
+            Ok(()) => format!("Wrote the game to {PATH}."),
+            Err(e) => format!("Failed to write {PATH}: {e}"),
+        });
+    }
+
+    fn move_file(&self, play: MailMove) -> MoveFile {
+        MoveFile {
+            turn: self.turn,
+            board: self.board.hash(),
+            play,
         }
+    }
 
-        Ok(self.board.collect_tentative()?)
+    fn export_move(&mut self) {
+        let Some(file) = &self.outbox else {
+            self.log.push("There is no move to export yet.".to_string());
+            return;
+        };
+        let name = file.file_name();
+        self.log.push(match fs::write(&name, file.to_string()) {
+            Ok(()) => format!("Wrote the last move to {name}."),
+            Err(e) => format!("Failed to write {name}: {e}"),
+        });
     }
 
-    // Returns words and their scores if dictionary contains words, otherwise returns
-    // all the words that are not in the dictionary
-    fn try_score(
-        &mut self,
-        word_squares: &Vec<Vec<Cell>>,
-    ) -> Result<Vec<(String, usize)>, Vec<String>> {
-        let mut words_and_scores = Vec::new();
-        let mut not_accepted = Vec::new();
-        for squares in word_squares {
-            let word = squares.iter().filter_map(|sq| sq.ch).collect::<String>();
-            if !self.dict.accepts(&word) {
-                not_accepted.push(word);
-                continue;
+    /// Runs a line typed after ':', either the name of a square to go to, "H8", or an
+    /// exchange of the letters after it, "e QXZ", '?' standing for a blank.
+    pub fn command(&mut self, line: &str) -> EventResult {
+        match line.trim().split_once(' ') {
+            Some(("d", word)) => {
+                let definition = define(&self.dict, &self.definitions, word);
+                return EventResult::Consumed(Some(Callback::from_fn(move |s| {
+                    s.add_layer(Dialog::info(definition.clone()).title("Definition"));
+                })));
             }
-            let mut word_score = 0;
-            let mut word_mults = Vec::new();
-            for square in squares {
-                let letter_score = Self::score_of(square.ch.unwrap());
-                word_score += match square.mult {
-                    None => letter_score,
-                    Some(word_mult @ (Multiplier::Dw | Multiplier::Tw)) => {
-                        word_mults.push(word_mult);
-                        letter_score
-                    }
-                    Some(letter_mult @ (Multiplier::Dl | Multiplier::Tl)) => {
-                        letter_score * letter_mult.as_factor()
-                    }
-                };
+            Some(("e", letters)) => {
+                let labels = letters
+                    .trim()
+                    .chars()
+                    .map(|letter| letter.to_ascii_uppercase())
+                    .collect();
+                match self.exchange_rack_letters(labels) {
+                    Ok(()) => return self.end_exchange(),
+                    Err(e) => self.log.push(e.to_string()),
+                }
             }
-            words_and_scores.push((
-                word,
-                word_mults
-                    .iter()
-                    .fold(word_score, |acc, mult| acc * mult.as_factor()),
-            ));
+            _ => self.jump(line),
         }
+        EventResult::Consumed(None)
+    }
 
-        if not_accepted.is_empty() {
-            let score_tot = words_and_scores.iter().map(|(_, score)| score).sum();
-            self.current_player_mut().add_score(score_tot);
-            self.log.push(if words_and_scores.len() == 1 {
-                format!(
-                    "{} played {} for {} points.",
-                    self.current_player().name,
-                    words_and_scores.iter().next().unwrap().0,
-                    score_tot
-                )
-            } else {
-                format!(
-                    "{} played {:?}, {} points total.",
-                    self.current_player().name,
-                    words_and_scores,
-                    score_tot,
-                )
-            });
-            Ok(words_and_scores)
-        } else {
-            Err(not_accepted)
+    /// Moves the focus to the square named `square`, e.g. "H8".
+    pub fn jump(&mut self, square: &str) {
+        match readout::parse_square(square) {
+            Some(pos) if self.board.focus_on(pos) => {
+                if self.display.readout {
+                    self.readout = readout::square(&self.board, self.board.focus());
+                }
+            }
+            _ => self.log.push(format!("There is no square {square}.")),
         }
     }
 
-    fn next_turn(&mut self) {
-        let curr_player = &mut self.players[self.current_player];
-        // check BINGO
-        let letters_placed = N_LETTERS - curr_player.letters.len();
-        if letters_placed == N_LETTERS {
-            curr_player.add_score(50);
-        }
-        // add new letters for player
-        for _ in 0..letters_placed {
-            if let Some(letter) = self.letters_bag.pop() {
-                curr_player.letters.push(letter);
+    /// Tells the chance of drawing the letters of `query` from the tiles the current
+    /// player hasn't seen: any of the letters if they're separated by '/', as in
+    /// "S/?/E", otherwise all of them. The number of tiles drawn can follow, as in
+    /// "ER 3", and is otherwise the number of letters placed, or a full rack if none are.
+    pub fn draw_chance(&mut self, query: &str) {
+        let mut fields = query.split_whitespace();
+        let letters = fields.next().unwrap_or_default().to_ascii_uppercase();
+        let draws = match fields.next().map(str::parse::<usize>) {
+            Some(Ok(draws)) => draws,
+            Some(Err(_)) => {
+                self.log
+                    .push(format!("Invalid number of tiles in {query}."));
+                return;
             }
+            None if self.board.tentative().is_empty() => self.current_player().rack_size,
+            None => self.board.tentative().len(),
+        };
+        let tiles = letters
+            .chars()
+            .filter(|&letter| letter != '/')
+            .map(|letter| if letter == '?' { ' ' } else { letter })
+            .collect::<Vec<_>>();
+        if tiles.is_empty() {
+            return;
         }
 
-        self.current_player += 1;
-        if self.current_player >= self.players.len() {
-            self.current_player = 0;
-            self.passes = 0;
+        let pool = self.unseen(&self.whole_rack());
+        let names = tiles
+            .iter()
+            .map(|&tile| match tile {
+                ' ' => "blank".to_string(),
+                letter => letter.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let (chance, wanted) = if letters.contains('/') {
+            (
+                stats::chance_of_any(&pool, &tiles, draws),
+                names.join(" or "),
+            )
+        } else {
+            (
+                stats::chance_of_all(&pool, &tiles, draws),
+                names.join(" and "),
+            )
+        };
+        self.log.push(format!(
+            "Chance of drawing {wanted} in {draws} tiles: {:.1}%.",
+            chance * 100.0
+        ));
+    }
+
+    /// Every play open to the player to move with their whole rack, tiles they've placed
+    /// on the board included, the best equity first.
+    pub fn legal_moves(&self) -> Vec<ScoredMove> {
+        Solver::new(&self.dict, &self.board)
+            .with_openings(&self.openings)
+            .candidates(&self.whole_rack(), &self.leaves, self.letters_bag.len())
+            .into_iter()
+            .filter_map(|evaluated| match evaluated.candidate {
+                Candidate::Play(play) => Some(ScoredMove {
+                    play,
+                    equity: evaluated.equity,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Plays `play` for the player to move as if they'd placed its tiles and confirmed,
+    /// taking back any tiles they'd placed first. A play that isn't legal here leaves
+    /// their rack as it was.
+    pub fn apply_move(&mut self, play: &Move) -> Result<(), ScrabbleError> {
+        self.carry_out(Decision::Play(play.tiles.clone()))?;
+        Ok(())
+    }
+
+    /// Every turn since the racks were dealt, see `journal`.
+    pub fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    /// Plays the move in the move file at `path`, made in another copy of the game.
+    pub fn import_move(&mut self, path: &str) {
+        match self.try_import(path) {
+            Ok(()) => self.outbox = None,
+            Err(e) => self.log.push(e.to_string()),
         }
-        self.turn += 1;
     }
 
-    fn maybe_toggle_letter(&mut self, letter: char) {
-        if let Some(idx) = self
-            .current_player()
-            .letters
+    fn try_import(&mut self, path: &str) -> Result<(), ScrabbleError> {
+        let file = MoveFile::parse(&fs::read_to_string(path)?)?;
+        if file.turn != self.turn || file.board != self.board.hash() {
+            return Err(ScrabbleError::MoveFile(
+                "it was written for a different position".to_string(),
+            ));
+        }
+        let decision = file.play.decision(&self.whole_rack())?;
+        self.carry_out(decision)?;
+        Ok(())
+    }
+
+    // The score of `player` less the letters left on their rack and time penalties.
+    fn end_score(&self, player: PlayerIndex) -> isize {
+        let p = &self.players[player];
+        p.score as isize
+            - p.letters
+                .iter()
+                .map(|tile| tile.value as isize)
+                .sum::<isize>()
+            - self.clock.penalty(player) as isize
+    }
+
+    // Whether the letters in line with the tentative ones begin any word, once there are
+    // two or more of them.
+    fn prefix_feedback(&self) -> Option<(String, bool)> {
+        let first = self
+            .board
+            .tentative()
             .iter()
-            .position(|&p_ch| p_ch == letter)
-        {
-            match self.board.place_focused_tentative(letter) {
-                Ok(Some(letter)) => self.current_player_mut().letters.push(letter),
-                Err(e) => {
-                    self.log.push(e.to_string());
-                    return;
-                }
-                Ok(None) => (),
-            };
-            self.current_player_mut().letters.swap_remove(idx);
-        } else {
-            self.log
-                .push("No such letter belonging to player.".to_string());
+            .min_by_key(|pos| (pos.y, pos.x))?;
+        let alignment = match self.board.tentative_alignment() {
+            Some(alignment @ (Alignment::Horizontal | Alignment::Vertical)) => alignment,
+            Some(Alignment::Invalid) => return None,
+            None if self.board.run_through(first, Alignment::Horizontal).len() > 1 => {
+                Alignment::Horizontal
+            }
+            None => Alignment::Vertical,
+        };
+        let run = self.board.run_through(first, alignment);
+        if run.len() < 2 || !self.board.tentative().iter().all(|pos| run.contains(pos)) {
+            return None;
         }
+        let word = run
+            .iter()
+            .filter_map(|pos| self.board.letter_at(pos))
+            .collect::<String>()
+            .to_uppercase();
+        Some(match self.dict.starts_word(&word) {
+            true => (format!("{word}... leads to words"), true),
+            false => (format!("No word starts with {word}"), false),
+        })
     }
 
-    fn remove_focused(&mut self) {
-        if let Some(letter) = self.board.clear_focused() {
-            self.current_player_mut().letters.push(letter);
+    // The letters that form words across the play on the focused square, when it's
+    // empty and next to tiles, for a play in either direction.
+    fn hooks(&self) -> Vec<String> {
+        let pos = self.board.focus();
+        if self.board.letter_at(pos).is_some() {
+            return Vec::new();
+        }
+        let mut lines = Vec::new();
+        for (name, alignment) in [
+            ("Across", Alignment::Horizontal),
+            ("Down", Alignment::Vertical),
+        ] {
+            let check = self.board.crosscheck(pos, alignment);
+            if check == CrossCheck::ANY {
+                continue;
+            }
+            let letters = check.letters();
+            let letters = match letters.is_empty() {
+                true => "none".to_string(),
+                false => letters.into_iter().collect(),
+            };
+            // Wrapped to the width of the side panel.
+            for (i, chunk) in letters.chars().chunks(10).into_iter().enumerate() {
+                let label = if i == 0 {
+                    format!("{name}:")
+                } else {
+                    String::new()
+                };
+                lines.push(format!("{label:8}{}", chunk.collect::<String>()));
+            }
         }
+        lines
     }
 
-    fn current_player(&self) -> &Player {
-        self.players.get(self.current_player).unwrap()
+    // Every play not withdrawn, in order, with who made it and what it scored.
+    fn kept_plays(&self) -> Vec<(PlayerIndex, &str, &str, isize)> {
+        let mut latest = vec![None; self.players.len()];
+        let mut kept = Vec::new();
+        for turn in self.journal.turns() {
+            match &turn.action {
+                Action::Play { coordinate, word } => {
+                    latest[turn.player] = Some(kept.len());
+                    kept.push(Some((
+                        turn.player,
+                        coordinate.as_str(),
+                        word.as_str(),
+                        turn.score,
+                    )));
+                }
+                Action::Withdrawn => {
+                    if let Some(idx) = latest[turn.player].take() {
+                        kept[idx] = None;
+                    }
+                }
+                Action::Exchange(_) | Action::Pass => (),
+            }
+        }
+        kept.into_iter().flatten().collect()
     }
 
-    fn current_player_mut(&mut self) -> &mut Player {
-        self.players.get_mut(self.current_player).unwrap()
+    // The word of a play with the letters it was played through, which are read off the
+    // board.
+    fn full_word(&self, coordinate: &str, word: &str) -> Option<String> {
+        let tiles = mail::parse_play(coordinate, &word.replace('.', "?")).ok()?;
+        tiles
+            .iter()
+            .map(|&(pos, letter)| match letter {
+                '?' => self.board.committed_letter_at(&pos),
+                letter => Some(letter),
+            })
+            .collect()
     }
 
-    fn exchange_letters(&mut self) -> Result<(), String> {
-        if self.board.tentative().len() > self.letters_bag.len() {
-            return Err("Can't exchange more letters than are left in bag.".to_string());
-        }
-        let amount = self.board.tentative().len();
-        self.letters_bag
-            .append(&mut self.board.clear_tentative_from_board());
-        self.letters_bag.shuffle(&mut rand::thread_rng());
-        for _ in 0..amount {
-            if let Some(letter) = self.letters_bag.pop() {
-                self.current_player_mut().letters.push(letter);
+    // The players ranked by their end scores, with what the letters left on their racks
+    // and overtime took off, and their best plays.
+    fn final_standings(&self) -> String {
+        let mut best = vec![None; self.players.len()];
+        for (player, coordinate, word, score) in self.kept_plays() {
+            if best[player].is_none_or(|(_, _, best)| score > best) {
+                best[player] = Some((coordinate, word, score));
             }
         }
-        Ok(())
+        self.rank_end_scores()
+            .into_iter()
+            .map(|(rank, i, score)| {
+                let player = &self.players[i];
+                let resigned = if player.resigned { " (resigned)" } else { "" };
+                let mut line = format!("{rank}: {}{resigned} scored {score} points", player.name);
+                let left = player.letters.iter().map(|tile| tile.value).sum::<usize>();
+                let overtime = self.clock.penalty(i);
+                if left > 0 || overtime > 0 {
+                    let mut adjustments = vec![format!("{} in play", player.score)];
+                    if left > 0 {
+                        let rack = player
+                            .letters
+                            .iter()
+                            .map(|tile| tile.label())
+                            .collect::<String>();
+                        adjustments.push(format!("-{left} for {rack} on the rack"));
+                    }
+                    if overtime > 0 {
+                        adjustments.push(format!("-{overtime} for overtime"));
+                    }
+                    line += &format!(": {}", adjustments.join(", "));
+                }
+                line += &match best[i] {
+                    Some((coordinate, word, score)) => {
+                        let word = self
+                            .full_word(coordinate, word)
+                            .unwrap_or_else(|| word.to_string());
+                        format!(".\n   Best play: {coordinate} {word} for {score}.")
+                    }
+                    None => ".\n   No plays.".to_string(),
+                };
+                line
+            })
+            .join("\n")
     }
 
-    //  Returns a vector of tuples where the first element is the placement of the player,
-    //  the second element element is the player name,
-    //  and the third element the player's score.
-    fn rank_end_scores(&self) -> Vec<(usize, String, isize)> {
+    fn names(&self) -> Vec<&str> {
         self.players
             .iter()
-            .map(|p| {
-                (
-                    p.name.clone(),
-                    p.score as isize
-                        - p.letters
-                            .iter()
-                            .map(|&letter| Self::score_of(letter) as isize)
-                            .sum::<isize>(),
-                )
+            .map(|player| player.name.as_str())
+            .collect()
+    }
+
+    //  Returns a vector of tuples where the first element is the placement of the player,
+    //  the second element element is the player's seat,
+    //  and the third element the player's score.
+    fn rank_end_scores(&self) -> Vec<(usize, PlayerIndex, isize)> {
+        (0..self.players.len())
+            .map(|i| (self.players[i].resigned, i, self.end_score(i)))
+            // Players who resigned rank below everyone who saw the game through.
+            .sorted_unstable_by_key(|&(resigned, _, score)| (resigned, -score))
+            .fold(Vec::new(), |mut ranking, (resigned, i, score)| {
+                let rank = match ranking.last() {
+                    Some(&(prev_rank, prev_resigned, _, prev_score))
+                        if (prev_resigned, prev_score) == (resigned, score) =>
+                    {
+                        prev_rank
+                    }
+                    Some(&(prev_rank, ..)) => prev_rank + 1,
+                    None => 1,
+                };
+                ranking.push((rank, resigned, i, score));
+                ranking
             })
-            .sorted_unstable_by_key(|(_, score)| -score.clone())
-            .fold(Vec::new(), |mut ranking, (p_name, p_score)| {
-                if let Some(&(prev_rank, _, prev_p_score)) = ranking.last() {
-                    if prev_p_score == p_score {
-                        ranking.push((prev_rank, p_name, p_score));
+            .into_iter()
+            .map(|(rank, _, i, score)| (rank, i, score))
+            .collect()
+    }
+}
+
+impl<D: Dictionary> Game<D> {
+    // The parts of the game shown, each in a named view that `refresh_screen` fills in.
+    fn layout_screen(coach_mode: bool) -> LinearLayout {
+        LinearLayout::horizontal()
+            .child(
+                LinearLayout::vertical()
+                    .child(Board::new(BOARD_SIZE).with_name("board"))
+                    .child(RackView::new(String::new(), None, Vec::new()).with_name("rack"))
+                    .child(
+                        LogView::new(Vec::new(), None)
+                            .with_name("log")
+                            .full_height(),
+                    ),
+            )
+            .child(DummyView.fixed_width(2))
+            .child(
+                LinearLayout::vertical()
+                    .child(ScorePanel::new(Vec::new(), (0, 0)).with_name("scores"))
+                    .child(DummyView)
+                    .child(Board::new(BOARD_SIZE).legend().with_name("legend"))
+                    .child(DummyView)
+                    .child(TextView::empty().with_name("volatility"))
+                    .child(DummyView)
+                    .child(TextView::empty().with_name("hooks"))
+                    .child(TextView::new(if coach_mode {
+                        "\nCOACH MODE: <F2> shows\nthe bag and every rack"
                     } else {
-                        ranking.push((prev_rank + 1, p_name, p_score));
-                    }
-                } else {
-                    ranking.push((1, p_name, p_score))
+                        ""
+                    })),
+            )
+    }
+
+    // Brings the screen up to date with the game. Bots' letters are kept hidden.
+    fn refresh_screen(&mut self) {
+        let player = self.current_player();
+        let rack = if !self.at_screen(self.current_player) {
+            RackView::new(format!("{} is playing.", player.name), None, Vec::new())
+        } else {
+            let tentative = self
+                .board
+                .tentative()
+                .iter()
+                .filter_map(|pos| self.board.tile_at(pos))
+                .collect();
+            let turn = format!("{}'s turn. Letters:", player.name);
+            let bingo = self.bingo == Some((self.turn, self.current_player, true));
+            RackView::new(turn, Some(player.letters.clone()), tentative)
+                .with_feedback(self.prefix_feedback())
+                .with_bingo(bingo)
+        };
+        let readout = self.display.readout.then(|| self.readout.clone());
+        let log = LogView::new(self.log.clone(), readout);
+        let board = self.board.clone();
+        let legend = self.board.legend();
+        let volatility = volatility_line(&self.board);
+        let hooks = self.hooks().join("\n");
+
+        let screen = &mut self.screen;
+        screen.call_on_name("board", |view: &mut Board| *view = board);
+        screen.call_on_name("rack", |view: &mut RackView| *view = rack);
+        screen.call_on_name("log", |view: &mut LogView| *view = log);
+        screen.call_on_name("legend", |view: &mut Legend| *view = legend);
+        screen.call_on_name("volatility", |view: &mut TextView| {
+            view.set_content(volatility)
+        });
+        screen.call_on_name("hooks", |view: &mut TextView| view.set_content(hooks));
+        self.stale = false;
+    }
+
+    // Brings the scores up to date, which the clocks change without anything happening
+    // in the game.
+    fn refresh_scores(&mut self) {
+        let standings = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(i, player)| {
+                let current = i == self.current_player;
+                let mut status = vec![format!("{} pts", self.shown_score(i))];
+                if let Some(hints) = player.hints_left {
+                    status.push(format!("{hints} hints"));
+                }
+                if player.resigned {
+                    status.push("resigned".to_string());
+                } else if let Some((left, overtime)) = self.clock.left(i, current) {
+                    status.push(clock::format(left, overtime));
+                }
+                Standing {
+                    name: player.name.clone(),
+                    status: status.join(", "),
+                    current,
                 }
-                ranking
             })
+            .collect();
+        let scores = ScorePanel::new(standings, (self.scoreless, self.scoreless_limit()));
+        self.screen
+            .call_on_name("scores", |view: &mut ScorePanel| *view = scores);
     }
 
-    fn score_of(letter: char) -> usize {
-        match letter {
-            'A' | 'E' | 'I' | 'L' | 'N' | 'O' | 'R' | 'S' | 'T' | 'U' => 1,
-            'D' | 'G' => 2,
-            'B' | 'C' | 'M' | 'P' => 3,
-            'F' | 'H' | 'V' | 'W' | 'Y' => 4,
-            'K' => 5,
-            'J' | 'X' => 8,
-            'Q' | 'Z' => 10,
-            ' ' => 0,
-            _ => unreachable!(),
-        }
+    // Calls `f` on the game shown in `s`, from outside of its event handling, and marks
+    // the screen to be brought up to date.
+    fn on_game<R>(s: &mut Cursive, f: impl FnOnce(&mut Self) -> R) -> Option<R> {
+        s.call_on_name("game", |game: &mut Self| {
+            game.stale = true;
+            f(game)
+        })
     }
 }
 
-impl cursive::View for Game {
-    fn draw(&self, printer: &cursive::Printer) {
-        let board = self.board.size;
-        let square_size = Cell::size();
-        self.board.draw(printer);
-        printer.print_hline(board.keep_y().map_y(|y| y), board.x * square_size, "—");
-        printer.print(
-            (0, board.y + 1),
-            &format!("{}'s turn. Letters:", self.current_player().name),
-        );
+// Whether `word` is in the lexicon, with its definition if it has one.
+fn define(dict: &impl Dictionary, definitions: &HashMap<String, String>, word: &str) -> String {
+    let word = word.trim().to_uppercase();
+    match (dict.accepts(&word), definitions.get(&word)) {
+        (true, Some(definition)) => format!("{word}: {definition}"),
+        (true, None) => format!("{word} is a word, without a definition in this lexicon."),
+        (false, _) => format!("{word} is not a word."),
+    }
+}
 
-        // Print player letters
-        let letter_disp_len = 6;
-        let letter_disp_offset = 2;
-        printer.print((0, board.y + letter_disp_len), &String::from("|"));
-        for (x, ch) in self.current_player().letters.iter().enumerate() {
-            printer.print(
-                (
-                    letter_disp_len * x + letter_disp_offset,
-                    board.y + letter_disp_offset,
-                ),
-                &format!("{ch} {}", Self::score_of(*ch)),
-            );
-            printer.print(
-                (
-                    letter_disp_len * x + letter_disp_len,
-                    board.y + letter_disp_offset,
-                ),
-                "|",
-            );
+// How open the board is, for the side panel.
+fn volatility_line(board: &Board) -> String {
+    let volatility = Volatility::of(board);
+    format!(
+        "Board: {}\n{} lanes, {} hotspots",
+        volatility.label(),
+        volatility.lanes,
+        volatility.hotspots
+    )
+}
+
+// The log entries matching `query`, newest first, with the matches highlighted. The
+// query is a case insensitive regular expression, or plain text if it isn't one.
+fn log_matches(entries: &[String], query: &str) -> StyledString {
+    let pattern = RegexBuilder::new(query)
+        .case_insensitive(true)
+        .build()
+        .or_else(|_| {
+            RegexBuilder::new(&regex::escape(query))
+                .case_insensitive(true)
+                .build()
+        });
+    let mut matches = StyledString::new();
+    let Ok(pattern) = pattern else {
+        return matches;
+    };
+    for entry in entries.iter().rev() {
+        if !pattern.is_match(entry) {
+            continue;
         }
-        printer.print(
-            (
-                letter_disp_len * self.current_player().letters.len() + letter_disp_offset,
-                board.y + letter_disp_offset,
-            ),
-            "->",
-        );
-        for (x, pos) in self.board.tentative().iter().enumerate() {
-            let ch = self.board.letter_at(&pos).unwrap();
-            printer.with_effect(cursive::theme::Effect::Dim, |printer| {
-                printer.print(
-                    (
-                        x * letter_disp_len
-                            + 3
-                            + (self.current_player().letters.len() * letter_disp_len
-                                + letter_disp_offset),
-                        board.y + letter_disp_offset,
-                    ),
-                    &format!("{ch} {}", Self::score_of(ch)),
-                );
-                printer.print(
-                    (
-                        x * letter_disp_len
-                            + 7
-                            + (self.current_player().letters.len() * letter_disp_len
-                                + letter_disp_offset),
-                        board.y + letter_disp_offset,
-                    ),
-                    "|",
-                );
-            });
+        let mut shown = 0;
+        for found in pattern
+            .find_iter(entry)
+            .filter(|found| found.start() < found.end())
+        {
+            matches.append_plain(&entry[shown..found.start()]);
+            matches.append_styled(&entry[found.start()..found.end()], Effect::Reverse);
+            shown = found.end();
         }
+        matches.append_plain(&entry[shown..]);
+        matches.append_plain("\n");
+    }
+    matches
+}
 
-        // Print log
-        printer.print_hline(board.keep_y().map_y(|y| y + 3), board.x * square_size, "—");
-        let mut lines = 0;
-        for entry in self.log.iter().rev() {
-            printer.print((0, board.y + square_size + lines), "-");
-            for line in entry
-                .chars()
-                .collect::<Vec<char>>()
-                .chunks(board.x * square_size - 2)
-            {
-                printer.print(
-                    (2, board.y + square_size + lines),
-                    &line.into_iter().collect::<String>(),
-                );
-                lines += 1;
-            }
-        }
+impl<D: Dictionary> cursive::View for Game<D> {
+    fn draw(&self, printer: &cursive::Printer) {
+        self.screen.draw(printer);
+    }
 
-        // Print player scores
-        let player_window_x = board.x * 4 + 2;
-        for (i, player) in self.players.iter().enumerate() {
-            printer.with_effect(
-                if i == self.current_player {
-                    cursive::theme::Effect::Underline
-                } else {
-                    cursive::theme::Effect::Dim
-                },
-                |printer| {
-                    printer.print((player_window_x, i * 3), &format!("{}", player.name));
-                },
-            );
-            printer.print(
-                (player_window_x, i * 3 + 1),
-                &format!("{} pts", player.score),
-            );
-            printer.print_hline((player_window_x, i * 3 + 2), 10, "-");
+    fn layout(&mut self, size: Vec2) {
+        if self.stale {
+            self.refresh_screen();
         }
+        self.refresh_scores();
+        self.screen.layout(size);
     }
 
-    fn required_size(&mut self, _: Vec2) -> Vec2 {
-        self.board.size.map_x(|x| x * 4 + 12).map_y(|y| y + 10)
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        if self.stale {
+            self.refresh_screen();
+        }
+        self.refresh_scores();
+        self.screen.required_size(constraint)
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
-        match SEvent::from(event) {
-            SEvent::Move(direction) => {
-                self.board.move_focus(&direction);
+        let event = self.input.map(event);
+        if let SEvent::Tick = event {
+            return self.tick();
+        }
+        // The board can be read during a bot's turn, but it's not for anyone to play on.
+        if !self.at_screen(self.current_player)
+            && !matches!(
+                event,
+                SEvent::ReadLastPlay
+                    | SEvent::ReadBoard
+                    | SEvent::CoachView
+                    | SEvent::TrackingSheet
+                    | SEvent::Dictionary
+            )
+        {
+            return EventResult::Ignored;
+        }
+        self.stale = true;
+        let moves_focus = matches!(
+            event,
+            SEvent::Move(..)
+                | SEvent::Anchor { .. }
+                | SEvent::Letter(_)
+                | SEvent::Blank(_)
+                | SEvent::Delete
+        );
+        match event {
+            SEvent::Count => (),
+            SEvent::Move(direction, times) => {
+                for _ in 0..times {
+                    self.board.move_focus(&direction);
+                }
                 self.current_player_mut().previous_move = Some(direction);
             }
-            SEvent::Letter(ch) => self.maybe_toggle_letter(ch.to_ascii_uppercase()).to_owned(),
+            SEvent::Anchor { forward } => self.board.focus_anchor(forward),
+            SEvent::SearchLog => {
+                let entries = self.log.clone();
+                return EventResult::Consumed(Some(Callback::from_fn(move |s| {
+                    let entries = entries.clone();
+                    let all = log_matches(&entries, "");
+                    let search = EditView::new().on_edit(move |s, query, _| {
+                        let matches = log_matches(&entries, query);
+                        s.call_on_name("log-matches", |view: &mut TextView| {
+                            view.set_content(matches)
+                        });
+                    });
+                    s.add_layer(
+                        Dialog::around(
+                            LinearLayout::vertical()
+                                .child(search)
+                                .child(TextView::new(all).with_name("log-matches").scrollable()),
+                        )
+                        .title("Search the log by name, word or pattern")
+                        .dismiss_button("Close"),
+                    );
+                })));
+            }
+            SEvent::WordLists => {
+                let lists = self
+                    .word_lists
+                    .get_or_insert_with(|| WordLists::new(&self.dict))
+                    .clone();
+                let (dict, definitions) = (self.dict.clone(), self.definitions.clone());
+                return EventResult::Consumed(Some(Callback::from_fn(move |s| {
+                    let first = lists.table(List::ALL[0], 8);
+                    let (dict, definitions) = (dict.clone(), definitions.clone());
+                    let lookup = EditView::new().on_edit(move |s, word, _| {
+                        let found = match word.trim().is_empty() {
+                            true => String::new(),
+                            false => define(&dict, &definitions, word),
+                        };
+                        s.call_on_name("word-lookup", |view: &mut TextView| {
+                            view.set_content(found)
+                        });
+                    });
+                    let shown = lists.clone();
+                    let select = SelectView::new()
+                        .popup()
+                        .with_all(List::ALL.map(|list| (list.name(), list)))
+                        .on_select(move |s, &list| {
+                            let table = shown.table(list, 8);
+                            s.call_on_name("word-list", |view: &mut TextView| {
+                                view.set_content(table)
+                            });
+                        });
+                    s.add_layer(
+                        Dialog::around(
+                            LinearLayout::vertical()
+                                .child(TextView::new("Look up a word:"))
+                                .child(lookup)
+                                .child(TextView::new("").with_name("word-lookup"))
+                                .child(DummyView)
+                                .child(select)
+                                .child(TextView::new(first).with_name("word-list").scrollable()),
+                        )
+                        .title("Word lists")
+                        .dismiss_button("Close"),
+                    );
+                })));
+            }
+            SEvent::Command => {
+                return EventResult::Consumed(Some(Callback::from_fn(|s| {
+                    s.add_layer(
+                        Dialog::around(EditView::new().on_submit(|s, line| {
+                            s.pop_layer();
+                            if let Some(result) = Self::on_game(s, |game| game.command(line)) {
+                                result.process(s);
+                            }
+                        }))
+                        .title("Go to a square, H8, exchange, e QXZ, or define, d QI"),
+                    );
+                })));
+            }
+            SEvent::Letter(ch) => self.maybe_toggle_letter(ch.to_ascii_uppercase()),
+            SEvent::Blank(ch) => self.maybe_place_blank(ch.to_ascii_uppercase()),
 
             SEvent::Delete => self.remove_focused(),
-            SEvent::Confirm => match self.validate_placement() {
-                Ok(word_squares) => {
-                    if let Err(e) = self.try_score(&word_squares) {
-                        self.log
-                            .push(format!("Word(s) not in dictionary: {:?}.", e));
-                    } else {
-                        // TODO self.validator.update_crosscheck(&self.board.tentative());
-                        self.board.clear_tentative();
-                        self.next_turn();
-                    }
-                }
-                Err(e) => self.log.push(e.to_string()),
-            },
-            SEvent::Pass => {
-                self.passes += 1;
-                if self.passes >= self.players.len() {
-                    let scores_ranked = self.rank_end_scores();
-                    return EventResult::Consumed(Some(Callback::from_fn(move |s| {
-                        s.pop_layer();
-                        s.add_layer(
-                            Dialog::new().title("GAME OVER").content(Dialog::info(
-                                scores_ranked
-                                    .iter()
-                                    .map(|(rank, name, score)| {
-                                        format!("{rank}: {name} scored {score} points.")
-                                    })
-                                    .join("\n"),
-                            )),
-                        );
-                    })));
-                }
-                self.log
-                    .push(format!("{} passed their turn.", self.current_player().name));
-                let mut cleared = self.board.clear_tentative_from_board();
-                self.current_player_mut().letters.append(&mut cleared);
-                self.next_turn();
+            SEvent::Confirm => {
+                let Some(summary) = self.words_summary() else {
+                    self.confirm();
+                    return EventResult::Consumed(None);
+                };
+                return EventResult::Consumed(Some(Callback::from_fn(move |s| {
+                    s.add_layer(
+                        Dialog::text(summary.clone())
+                            .title("Play these words?")
+                            .button("Play", |s| {
+                                s.pop_layer();
+                                Self::on_game(s, Self::confirm);
+                            })
+                            .dismiss_button("Back"),
+                    );
+                })));
+            }
+            SEvent::Challenge => self.challenge(),
+            SEvent::Pass => return self.pass(),
+            SEvent::Resign => {
+                return EventResult::Consumed(Some(Callback::from_fn(|s| {
+                    s.add_layer(
+                        Dialog::text("Leave the game for good?")
+                            .title("Resign")
+                            .button("Resign", |s| {
+                                s.pop_layer();
+                                if let Some(result) = Self::on_game(s, |game| game.resign()) {
+                                    result.process(s);
+                                }
+                            })
+                            .dismiss_button("Cancel"),
+                    );
+                })));
             }
             SEvent::Shuffle => self.current_player_mut().shuffle_letters(),
-            SEvent::Exchange => {
-                if let Err(e) = self.exchange_letters() {
-                    self.log.push(e)
-                } else {
-                    self.next_turn()
-                }
+            SEvent::Sort => self.current_player_mut().sort_letters(),
+            SEvent::Suggest => return self.suggest(),
+            SEvent::Heatmap => self.toggle_heatmap(),
+            SEvent::ExportMove => self.export_move(),
+            SEvent::ExportGcg => self.export_gcg(),
+            SEvent::Chart => {
+                let charts = self.charts();
+                return EventResult::Consumed(Some(Callback::from_fn(move |s| {
+                    s.add_layer(Dialog::info(charts.clone()).title("Scores"));
+                })));
+            }
+            SEvent::Annotate => {
+                return EventResult::Consumed(Some(Callback::from_fn(|s| {
+                    s.add_layer(
+                        Dialog::around(EditView::new().on_submit(|s, note| {
+                            s.pop_layer();
+                            Self::on_game(s, |game| game.annotate(note));
+                        }))
+                        .title("Comment on the last move"),
+                    );
+                })));
             }
+            SEvent::DrawChance => {
+                return EventResult::Consumed(Some(Callback::from_fn(|s| {
+                    s.add_layer(
+                        Dialog::around(EditView::new().on_submit(|s, query| {
+                            s.pop_layer();
+                            Self::on_game(s, |game| game.draw_chance(query));
+                        }))
+                        .title("Letters to draw, e.g. S/?/E or ER 3"),
+                    );
+                })));
+            }
+            SEvent::ImportMove => {
+                return EventResult::Consumed(Some(Callback::from_fn(|s| {
+                    s.add_layer(
+                        Dialog::around(EditView::new().on_submit(|s, path| {
+                            s.pop_layer();
+                            Self::on_game(s, |game| game.import_move(path));
+                        }))
+                        .title("Import move file"),
+                    );
+                })));
+            }
+            SEvent::Endgame => self.endgame_outlook(),
+            SEvent::Exchange => match self.exchange_letters() {
+                Ok(()) => return self.end_exchange(),
+                Err(e) => self.log.push(e.to_string()),
+            },
 
             SEvent::DeleteAll => {
                 let cleared = &mut self.board.clear_tentative_from_board();
                 self.current_player_mut().letters.append(cleared);
             }
+            SEvent::ReadRack => self.announce(readout::rack(&self.current_player().letters)),
+            SEvent::ReadLastPlay => self.read_last_play(),
+            SEvent::ReadBoard => {
+                let rows = readout::board(&self.board).join("\n");
+                return EventResult::Consumed(Some(Callback::from_fn(move |s| {
+                    s.add_layer(Dialog::info(rows.clone()).title("Board"));
+                })));
+            }
+            SEvent::TakeBack => self.take_back(),
+            SEvent::Dictionary => {
+                let (dict, definitions) = (self.dict.clone(), self.definitions.clone());
+                return EventResult::Consumed(Some(Callback::from_fn(move |s| {
+                    browser::show(s, dict.clone(), definitions.clone())
+                })));
+            }
+            SEvent::TrackingSheet => {
+                let sheet = self.tracking_sheet().to_string();
+                return EventResult::Consumed(Some(Callback::from_fn(move |s| {
+                    s.add_layer(
+                        Dialog::around(TextView::new(sheet.clone()).scrollable())
+                            .title("Tracking sheet")
+                            .dismiss_button("Close"),
+                    );
+                })));
+            }
+            SEvent::CoachView if self.options.coach_mode => {
+                let view = self.coach_view();
+                self.log
+                    .push("Coach mode: the coach looked at the bag and racks.".to_string());
+                return EventResult::Consumed(Some(Callback::from_fn(move |s| {
+                    s.add_layer(
+                        Dialog::info(view.clone()).title("Coach mode: the bag and every rack"),
+                    );
+                })));
+            }
             _ => return EventResult::Ignored,
         };
 
+        // Letters put back on the rack go where the player keeps them.
+        self.current_player_mut().arrange();
+        if self.display.readout && moves_focus {
+            self.readout = readout::square(&self.board, self.board.focus());
+        }
         EventResult::Consumed(None)
     }
 
@@ -461,21 +3021,35 @@ impl cursive::View for Game {
     }
 }
 
-struct Player {
+struct Player<D> {
+    lexicon: Option<D>,
+    hints_left: Option<usize>,
+    rack_size: usize,
     name: String,
-    letters: Vec<char>,
+    letters: Vec<Tile>,
     score: usize,
     previous_move: Option<Direction>,
+    rack_order: RackOrder,
+    // Resigned players keep their letters and score, and their turns are skipped.
+    resigned: bool,
 }
 
-impl Player {
-    fn new(chars: Vec<char>, name: String) -> Self {
-        Self {
-            letters: chars,
-            score: 0,
+impl<D> Player<D> {
+    fn new(tiles: Vec<Tile>, profile: &PlayerProfile, hints: Option<usize>) -> Self {
+        let handicap = profile.handicap;
+        let mut player = Self {
+            lexicon: None,
+            hints_left: hints.map(|hints| hints + handicap.extra_hints),
+            rack_size: N_LETTERS + handicap.extra_tiles,
+            letters: tiles,
+            score: handicap.bonus,
             previous_move: None,
-            name,
-        }
+            rack_order: profile.rack_order,
+            resigned: false,
+            name: profile.name.clone(),
+        };
+        player.arrange();
+        player
     }
 
     fn add_score(&mut self, score: usize) {
@@ -483,6 +3057,235 @@ impl Player {
     }
 
     fn shuffle_letters(&mut self) {
+        self.rack_order = RackOrder::Drawn;
         self.letters.shuffle(&mut rand::thread_rng());
     }
+
+    fn sort_letters(&mut self) {
+        self.rack_order = RackOrder::Alphabetical;
+        self.arrange();
+    }
+
+    // Puts the letters in the order the player keeps them in.
+    fn arrange(&mut self) {
+        if self.rack_order == RackOrder::Alphabetical {
+            self.letters.sort_by_key(|&tile| (tile.is_blank, tile));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+    };
+
+    use cursive::{event::Key, View};
+
+    use super::*;
+
+    fn game() -> Game {
+        let dict = Gaddag::from_words(["AT", "TA"].map(String::from)).unwrap();
+        Game::builder(dict)
+            .with_seats([PlayerProfile::human("Ann"), PlayerProfile::human("Bo")])
+            .with_phonies(PhonyRule::Challenge)
+            .with_seed(1)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn subscribers_are_told_about_every_turn() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut game = game().subscribe({
+            let seen = seen.clone();
+            move |event: &GameEvent| seen.lock().unwrap().push(event.clone())
+        });
+        game.carry_out(Decision::Pass).unwrap();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [
+                GameEvent::Passed {
+                    player: "Ann".to_string()
+                },
+                GameEvent::TurnStarted {
+                    player: "Bo".to_string(),
+                    turn: game.turn
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn endgame_opponent_is_the_other_player_left() {
+        let dict = Gaddag::from_words(["AT", "TA"].map(String::from)).unwrap();
+        let mut game = Game::builder(dict)
+            .with_seats(["Ann", "Bo", "Cy"].map(PlayerProfile::human))
+            .with_seed(1)
+            .build()
+            .unwrap();
+        // The bag played out, with only the racks left.
+        game.letters_bag.clear();
+        game.all_tiles = game
+            .players
+            .iter()
+            .flat_map(|player| player.letters.clone())
+            .collect();
+        game.players[1].resigned = true;
+        game.players[0].score = 50;
+        game.players[2].score = 30;
+        let sorted = |mut tiles: Vec<Tile>| {
+            tiles.sort();
+            tiles
+        };
+
+        game.current_player = 2;
+        let rack = game.current_player().letters.clone();
+        let (opponent, lead) = game.endgame_opponent(&rack).unwrap();
+        assert_eq!(lead, -20);
+        assert_eq!(sorted(opponent), sorted(game.players[0].letters.clone()));
+
+        game.current_player = 0;
+        let rack = game.current_player().letters.clone();
+        let (opponent, lead) = game.endgame_opponent(&rack).unwrap();
+        assert_eq!(lead, 20);
+        assert_eq!(sorted(opponent), sorted(game.players[2].letters.clone()));
+
+        game.players[1].resigned = false;
+        assert!(game.endgame_opponent(&rack).is_none());
+    }
+
+    #[test]
+    fn build_needs_a_seat() {
+        let dict = Gaddag::from_words(["AT"].map(String::from)).unwrap();
+        let built = Game::builder(dict).build();
+        assert!(matches!(built, Err(ScrabbleError::NoSeats)));
+        assert_eq!(game().players.len(), 2);
+    }
+
+    #[test]
+    fn build_rejects_board_sizes_out_of_range() {
+        for size in [0, 6, 16, MAX_SIZE + 1] {
+            let options = Options {
+                board_size: Some(size),
+                ..Options::default()
+            };
+            let dict = Gaddag::from_words(["AT"].map(String::from)).unwrap();
+            let built = Game::builder(dict)
+                .with_seat(PlayerProfile::human("Ann"))
+                .with_options(options)
+                .build();
+            assert!(matches!(built, Err(ScrabbleError::BoardSize(s)) if s == size));
+        }
+        let options = Options {
+            board_size: Some(MIN_SIZE),
+            ..Options::default()
+        };
+        assert_eq!(options.checked_board_size().unwrap(), MIN_SIZE);
+        assert_eq!(Options::default().checked_board_size().unwrap(), BOARD_SIZE);
+    }
+
+    #[test]
+    fn screen_is_brought_up_to_date_once_stale() {
+        let mut game = game();
+        let size = Vec2::new(120, 50);
+        let shown = |game: &mut Game| {
+            game.screen.call_on_name("board", |view: &mut Board| {
+                view.committed_letter_at(&Vec2::new(7, 7))
+            })
+        };
+        game.layout(size);
+        assert!(!game.stale);
+        assert_eq!(shown(&mut game), Some(None));
+
+        let rack = game.current_player().letters.clone();
+        let tiles = vec![(Vec2::new(7, 7), rack[0]), (Vec2::new(8, 7), rack[1])];
+        game.carry_out(Decision::Play(tiles)).unwrap();
+        assert!(game.stale);
+        game.layout(size);
+        assert!(!game.stale);
+        assert_eq!(shown(&mut game), Some(Some(rack[0].letter)));
+    }
+
+    #[test]
+    fn take_back_undoes_the_turn_and_its_record() {
+        let mut game = game();
+        game.options.takebacks = true;
+        let player = game.current_player;
+        let rack = game.current_player().letters.clone();
+        let tiles = vec![(Vec2::new(7, 7), rack[0]), (Vec2::new(8, 7), rack[1])];
+        game.carry_out(Decision::Play(tiles)).unwrap();
+        assert_eq!(game.journal().turns().len(), 1);
+
+        game.take_back();
+        assert!(game.journal().turns().is_empty());
+        assert_eq!(game.current_player, player);
+        assert_eq!(game.players[player].score, 0);
+        let mut letters = game.players[player].letters.clone();
+        let mut rack = rack;
+        letters.sort();
+        rack.sort();
+        assert_eq!(letters, rack);
+    }
+
+    #[test]
+    fn the_pass_ending_the_game_is_recorded() {
+        let mut game = game();
+        game.carry_out(Decision::Pass).unwrap();
+        game.carry_out(Decision::Pass).unwrap();
+        let passes = game.journal().entries().iter();
+        assert_eq!(passes.filter(|entry| entry.is_turn()).count(), 2);
+        assert_eq!(game.journal().turns().len(), 2);
+        assert!(game
+            .journal()
+            .turns()
+            .iter()
+            .all(|turn| matches!(turn.action, Action::Pass)));
+    }
+
+    #[test]
+    fn challenge_after_house_rules_penalty_leaves_score_at_zero() {
+        let file = format!("scrabbler-penalty-{}.rhai", std::process::id());
+        let path = std::env::temp_dir().join(file);
+        fs::write(&path, "fn turn_end(player, score, turn) { -1000 }").unwrap();
+        let rules = HouseRules::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let mut game = game().with_house_rules(Arc::new(rules));
+
+        let player = game.current_player;
+        let rack = game.current_player().letters.clone();
+        let tiles = vec![(Vec2::new(7, 7), rack[0]), (Vec2::new(8, 7), rack[1])];
+        game.carry_out(Decision::Play(tiles)).unwrap();
+        assert_eq!(game.players[player].score, 0);
+
+        game.carry_out(Decision::Challenge).unwrap();
+        assert_eq!(game.players[player].score, 0);
+        assert_eq!(game.journal().state().scores[player], 0);
+        assert!(game.board.committed_letter_at(&Vec2::new(7, 7)).is_none());
+    }
+
+    #[test]
+    fn blanks_are_placed_with_a_question_mark_before_the_letter() {
+        let mut game = game();
+        game.current_player_mut().letters = vec![Tile::BLANK, Tile::new('A', 1)];
+
+        game.on_event(Event::Char('?'));
+        game.on_event(Event::Char('t'));
+        assert_eq!(game.board.focused_letter(), Some('T'));
+        assert_eq!(game.current_player().letters, [Tile::new('A', 1)]);
+
+        // The blank played as another letter goes back on the rack as a blank.
+        game.on_event(Event::Char('a'));
+        assert_eq!(game.board.focused_letter(), Some('A'));
+        assert_eq!(game.current_player().letters, [Tile::BLANK]);
+
+        game.on_event(Event::Char('?'));
+        game.on_event(Event::Key(Key::Right));
+        game.on_event(Event::Char('t'));
+        assert_eq!(
+            game.log.last().unwrap(),
+            "No such letter belonging to player."
+        );
+    }
 }
@@ -0,0 +1,423 @@
+//! The record of a game, written as a GCG file the way Quackle and other Scrabble
+//! programs read them: a pragma per player followed by a line per turn,
+//!
+//! ```text
+//! >John: AEINRST 8D NASTIER +64 64
+//! #note Always look for the bingo
+//! ```
+//!
+//! with `#note` lines carrying the comments attached to the turn above them. Records
+//! of unfinished games are read back by `parse` to be resumed.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{self, Write},
+};
+
+use cursive::Vec2;
+
+use crate::{
+    bag::Distribution,
+    board::{Alignment, Board},
+    dictionary::Dictionary,
+    error::{Result, ScrabbleError},
+    mail,
+    tile::Tile,
+};
+
+/// A game record read from a GCG file.
+pub struct Record {
+    /// Full names of the players, in seat order.
+    pub players: Vec<String>,
+    pub turns: Vec<Turn>,
+}
+
+/// A turn in the game record, with the rack it was played from.
+#[derive(Clone)]
+pub struct Turn {
+    pub player: usize,
+    pub rack: Vec<char>,
+    pub action: Action,
+    pub score: isize,
+    pub total: isize,
+    pub notes: Vec<String>,
+}
+
+#[derive(Clone)]
+pub enum Action {
+    Play {
+        coordinate: String,
+        word: String,
+    },
+    Exchange(Vec<char>),
+    Pass,
+    /// The previous play of the same player, taken back after a challenge.
+    Withdrawn,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::Play { coordinate, word } => write!(f, "{coordinate} {word}"),
+            Action::Exchange(letters) => write!(f, "-{}", tiles(letters)),
+            Action::Pass => write!(f, "-"),
+            Action::Withdrawn => write!(f, "--"),
+        }
+    }
+}
+
+/// The play formed by the tentative letters on `board`, in the notation of `Move`:
+/// its coordinate and its main word, with '.' for the letters played through.
+pub fn tentative_play(board: &Board) -> Action {
+    let Some(first) = board.tentative().iter().next() else {
+        return Action::Pass;
+    };
+    // A single letter plays along whichever direction it forms a word in.
+    let alignment = match board.tentative_alignment() {
+        Some(alignment @ (Alignment::Horizontal | Alignment::Vertical)) => alignment,
+        _ if board.run_through(first, Alignment::Horizontal).len() > 1 => Alignment::Horizontal,
+        _ => Alignment::Vertical,
+    };
+    let run = board.run_through(first, alignment);
+    let start = run[0];
+    let word = run
+        .iter()
+        .map(|pos| match board.committed_letter_at(pos) {
+            Some(_) => '.',
+            None => board.tile_at(pos).map_or('?', Tile::label),
+        })
+        .collect();
+    Action::Play {
+        coordinate: coordinate(&start, alignment),
+        word,
+    }
+}
+
+/// Puts the plays of `turns` on `board` with the tiles of `distribution`, taking back
+/// the ones withdrawn. Letters the record writes in lower case are played as blanks.
+pub fn replay(
+    board: &mut Board,
+    dict: &impl Dictionary,
+    distribution: &Distribution,
+    turns: &[Turn],
+) -> Result<()> {
+    let mut plays: HashMap<usize, Vec<(Vec2, char)>> = HashMap::new();
+    for turn in turns {
+        match &turn.action {
+            Action::Play { coordinate, word } => {
+                let tiles = mail::parse_play(coordinate, word)?;
+                for &(pos, label) in &tiles {
+                    board.place_at(distribution.written(label), &pos);
+                }
+                board.commit_tentative(dict);
+                plays.insert(turn.player, tiles);
+            }
+            Action::Withdrawn => {
+                let tiles = plays.remove(&turn.player).unwrap_or_default();
+                let positions = tiles.iter().map(|&(pos, _)| pos).collect::<Vec<_>>();
+                board.retract(&positions, dict);
+            }
+            Action::Exchange(_) | Action::Pass => {
+                plays.remove(&turn.player);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The main words of the plays in `turns` with the index of the turn that played them,
+/// leaving out the plays withdrawn. Letters played through are read off the plays
+/// before them.
+pub fn words(turns: &[Turn]) -> Vec<(usize, String)> {
+    let mut grid: HashMap<Vec2, char> = HashMap::new();
+    let mut plays: HashMap<usize, (Vec<Vec2>, usize)> = HashMap::new();
+    let mut words = Vec::new();
+    for (i, turn) in turns.iter().enumerate() {
+        match &turn.action {
+            Action::Play { coordinate, word } => {
+                let Ok(tiles) = mail::parse_play(coordinate, &word.replace('.', "?")) else {
+                    continue;
+                };
+                let word = tiles
+                    .iter()
+                    .map(|(pos, letter)| match letter {
+                        '?' => grid.get(pos).copied().unwrap_or('?'),
+                        letter => letter.to_uppercase().next().unwrap_or(*letter),
+                    })
+                    .collect();
+                let placed = tiles
+                    .into_iter()
+                    .filter(|&(_, letter)| letter != '?')
+                    .map(|(pos, letter)| {
+                        grid.insert(pos, letter.to_uppercase().next().unwrap_or(letter));
+                        pos
+                    })
+                    .collect();
+                plays.insert(turn.player, (placed, words.len()));
+                words.push(Some((i, word)));
+            }
+            Action::Withdrawn => {
+                if let Some((placed, play)) = plays.remove(&turn.player) {
+                    placed.iter().for_each(|pos| {
+                        grid.remove(pos);
+                    });
+                    words[play] = None;
+                }
+            }
+            Action::Exchange(_) | Action::Pass => {
+                plays.remove(&turn.player);
+            }
+        }
+    }
+    words.into_iter().flatten().collect()
+}
+
+/// Writes the game record for `players`, in seat order.
+pub fn write(players: &[&str], turns: &[Turn], mut out: impl Write) -> io::Result<()> {
+    writeln!(out, "#character-encoding UTF-8")?;
+    for (i, name) in players.iter().enumerate() {
+        writeln!(out, "#player{} {} {name}", i + 1, nickname(name))?;
+    }
+    for turn in turns {
+        writeln!(
+            out,
+            ">{}: {} {} {:+} {}",
+            nickname(players[turn.player]),
+            tiles(&turn.rack),
+            turn.action,
+            turn.score,
+            turn.total
+        )?;
+        for note in &turn.notes {
+            writeln!(out, "#note {note}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the players and turns of a GCG file. Pragmas other than the players and notes
+/// are skipped. Turns that only adjust the score, like the penalties at the end of a
+/// game, are refused.
+pub fn parse(content: &str) -> Result<Record> {
+    let mut nicknames = Vec::new();
+    let mut record = Record {
+        players: Vec::new(),
+        turns: Vec::new(),
+    };
+    for (i, line) in content.lines().enumerate() {
+        let invalid = |reason: &str| ScrabbleError::Gcg {
+            line: i + 1,
+            reason: reason.to_string(),
+        };
+        let line = line.trim();
+        if let Some(note) = line.strip_prefix("#note ") {
+            let turn = record
+                .turns
+                .last_mut()
+                .ok_or(invalid("note before any turn"))?;
+            turn.notes.push(note.to_string());
+        } else if let Some(player) = line.strip_prefix("#player") {
+            let mut fields = player.splitn(3, ' ').skip(1);
+            let (Some(nickname), Some(name)) = (fields.next(), fields.next()) else {
+                return Err(invalid("invalid player"));
+            };
+            nicknames.push(nickname.to_string());
+            record.players.push(name.to_string());
+        } else if let Some(turn) = line.strip_prefix('>') {
+            let Some((nickname, turn)) = turn.split_once(':') else {
+                return Err(invalid("invalid turn"));
+            };
+            let player = nicknames
+                .iter()
+                .position(|nick| nick == nickname)
+                .ok_or(invalid("unknown player"))?;
+            record
+                .turns
+                .push(parse_turn(player, turn).ok_or(invalid("invalid turn"))?);
+        } else if !line.is_empty() && !line.starts_with('#') {
+            return Err(invalid("unexpected line"));
+        }
+    }
+    Ok(record)
+}
+
+// The part of a turn line after the nickname: the rack, the move, its score and the
+// total.
+fn parse_turn(player: usize, turn: &str) -> Option<Turn> {
+    let fields = turn.split_whitespace().collect::<Vec<_>>();
+    let (rack, action, score, total) = match fields[..] {
+        [rack, coordinate, word, score, total] => (
+            rack,
+            Action::Play {
+                coordinate: coordinate.to_string(),
+                word: word.to_string(),
+            },
+            score,
+            total,
+        ),
+        [rack, "-", score, total] => (rack, Action::Pass, score, total),
+        [rack, "--", score, total] => (rack, Action::Withdrawn, score, total),
+        // Exchanges may be recorded by the number of tiles alone.
+        [rack, exchange, score, total] if exchange.starts_with('-') => {
+            let letters = exchange[1..]
+                .chars()
+                .filter(|ch| !ch.is_ascii_digit())
+                .map(untile)
+                .collect();
+            (rack, Action::Exchange(letters), score, total)
+        }
+        _ => return None,
+    };
+    Some(Turn {
+        player,
+        rack: rack.chars().map(untile).collect(),
+        action,
+        score: score.parse().ok()?,
+        total: total.parse().ok()?,
+        notes: Vec::new(),
+    })
+}
+
+fn coordinate(start: &Vec2, alignment: Alignment) -> String {
+    let column = (b'A' + start.x as u8) as char;
+    let row = start.y + 1;
+    match alignment {
+        Alignment::Vertical => format!("{column}{row}"),
+        _ => format!("{row}{column}"),
+    }
+}
+
+// Nicknames can't contain whitespace in GCG.
+fn nickname(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+// Blanks are written '?' in GCG, and ' ' on the rack.
+fn untile(letter: char) -> char {
+    if letter == '?' {
+        ' '
+    } else {
+        letter
+    }
+}
+
+/// The letters as the record writes them, blanks as '?'.
+pub fn tiles(letters: &[char]) -> String {
+    letters
+        .iter()
+        .map(|&letter| if letter == ' ' { '?' } else { letter })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gaddag::Gaddag;
+
+    fn turn(player: usize, rack: &str, action: Action, score: isize, total: isize) -> Turn {
+        Turn {
+            player,
+            rack: rack.chars().map(untile).collect(),
+            action,
+            score,
+            total,
+            notes: Vec::new(),
+        }
+    }
+
+    fn play(coordinate: &str, word: &str) -> Action {
+        Action::Play {
+            coordinate: coordinate.to_string(),
+            word: word.to_string(),
+        }
+    }
+
+    const RECORD: &str = "\
+#character-encoding UTF-8
+#player1 John_Smith John Smith
+#player2 Ann Ann
+>John_Smith: AEINRST 8D NASTIER +64 64
+#note Always look for the bingo
+>Ann: ?QUVWXZ -Q? +0 0
+>Ann: UVWXZAB - +0 0
+>John_Smith: ABCDEFG -- -64 0
+";
+
+    #[test]
+    fn records_read_back_as_written() {
+        let mut first = turn(0, "AEINRST", play("8D", "NASTIER"), 64, 64);
+        first.notes.push("Always look for the bingo".to_string());
+        let turns = [
+            first,
+            turn(1, "?QUVWXZ", Action::Exchange(vec!['Q', ' ']), 0, 0),
+            turn(1, "UVWXZAB", Action::Pass, 0, 0),
+            turn(0, "ABCDEFG", Action::Withdrawn, -64, 0),
+        ];
+        let mut out = Vec::new();
+        write(&["John Smith", "Ann"], &turns, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), RECORD);
+
+        let record = parse(RECORD).unwrap();
+        assert_eq!(record.players, ["John Smith", "Ann"]);
+        let mut again = Vec::new();
+        let players = record
+            .players
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        write(&players, &record.turns, &mut again).unwrap();
+        assert_eq!(String::from_utf8(again).unwrap(), RECORD);
+    }
+
+    #[test]
+    fn exchanges_may_be_counted() {
+        let record = parse("#player1 Al Al\n>Al: ABCDEFG -3 +0 0\n").unwrap();
+        assert!(matches!(&record.turns[0].action, Action::Exchange(letters) if letters.is_empty()));
+    }
+
+    #[test]
+    fn invalid_lines_are_refused_by_number() {
+        for (content, at, why) in [
+            ("#note early\n", 1, "note before any turn"),
+            ("#player1 Al Al\n>Bo: AB - +0 0\n", 2, "unknown player"),
+            ("#player1 Al Al\n\n>Al: AB 8D +5 5\n", 3, "invalid turn"),
+            ("#player1 Al\n", 1, "invalid player"),
+            ("hello\n", 1, "unexpected line"),
+        ] {
+            match parse(content) {
+                Err(ScrabbleError::Gcg { line, reason }) => {
+                    assert_eq!((line, reason.as_str()), (at, why), "{content}")
+                }
+                _ => panic!("{content} should be refused"),
+            }
+        }
+    }
+
+    #[test]
+    fn words_leave_out_withdrawn_plays() {
+        let turns = [
+            turn(0, "", play("8H", "cAT"), 5, 5),
+            turn(1, "", play("I7", "B.D"), 6, 6),
+            turn(1, "", Action::Withdrawn, -6, 0),
+            turn(1, "", play("I7", "B.G"), 6, 6),
+        ];
+        assert_eq!(
+            words(&turns),
+            [(0, "CAT".to_string()), (3, "BAG".to_string())]
+        );
+    }
+
+    #[test]
+    fn plays_are_replayed_and_written() {
+        let dict = Gaddag::from_words(["AA", "CAT"].map(String::from)).unwrap();
+        let mut board = Board::new(15);
+        let turns = [turn(0, "", play("8H", "cAT"), 5, 5)];
+        replay(&mut board, &dict, &Distribution::default(), &turns).unwrap();
+        assert_eq!(board.committed_letter_at(&Vec2::new(7, 7)), Some('C'));
+        assert_eq!(board.committed_letter_at(&Vec2::new(9, 7)), Some('T'));
+
+        board.place_at(Tile::new('A', 1), &Vec2::new(8, 6));
+        assert_eq!(tentative_play(&board).to_string(), "I7 A.");
+    }
+}
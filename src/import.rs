@@ -0,0 +1,231 @@
+//! Word lists in the formats club players already have, for making lexicons of:
+//!
+//! - Zyzzyva word lists, a word per line followed by its definition, if it has one:
+//!
+//!   ```text
+//!   AA rough, cindery lava [n AAS]
+//!   ```
+//!
+//! - Quackle `.dawg` files. A node takes 7 bytes: the index of its first child in 3,
+//!   then its letter, A being 0, in the low 5 bits of a byte whose next bits tell that a
+//!   word ends at the node and that it's the last of its siblings, then the playability
+//!   of the word ending there in 3. The root is the first node. Version 1 files start
+//!   with their version, a 16 byte hash and the word count in 3 bytes, version 0 files
+//!   with the root. Only lexicons of the letters A to Z are read.
+//!
+//! What the words come with, definitions and playability, is kept next to the word list,
+//! see `write_metadata`.
+
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+};
+
+use crate::error::{Result, ScrabbleError};
+
+const NODE_SIZE: usize = 7;
+const V1_HEADER: usize = 1 + 16 + 3;
+const MAX_WORD: usize = 64;
+
+/// A word with what the list it came from says about it.
+pub struct Entry {
+    pub word: String,
+    pub definition: Option<String>,
+    /// How likely the word is to come up, as Quackle rates it.
+    pub playability: Option<u32>,
+}
+
+/// The words of the Zyzzyva word list or Quackle dawg at `path`, told apart by the
+/// `.dawg` extension, sorted for dawgs and in the order of the list otherwise.
+pub fn read(path: &Path) -> Result<Vec<Entry>> {
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("dawg"))
+    {
+        read_dawg(&fs::read(path)?)
+    } else {
+        read_zyzzyva(BufReader::new(fs::File::open(path)?))
+    }
+}
+
+fn read_zyzzyva(list: impl BufRead) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    for line in list.lines() {
+        let line = line?;
+        let line = line.trim();
+        // Zyzzyva writes a comment line at the top of the lists it saves.
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (word, definition) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let definition = definition.trim();
+        entries.push(Entry {
+            word: word.to_uppercase(),
+            definition: (!definition.is_empty()).then(|| definition.to_string()),
+            playability: None,
+        });
+    }
+    Ok(entries)
+}
+
+fn read_dawg(bytes: &[u8]) -> Result<Vec<Entry>> {
+    let nodes = match bytes.first() {
+        Some(0) => bytes,
+        Some(1) if bytes.len() >= V1_HEADER => &bytes[V1_HEADER..],
+        Some(version) => return Err(invalid(&format!("unknown dawg version {version}"))),
+        None => return Err(invalid("empty dawg")),
+    };
+    let node = |index: usize| -> Result<Node> {
+        let at = index * NODE_SIZE;
+        let Some(node) = nodes.get(at..at + NODE_SIZE) else {
+            return Err(invalid(&format!(
+                "node {index} is past the end of the dawg"
+            )));
+        };
+        let number = |bytes: &[u8]| bytes.iter().fold(0, |n, &byte| n << 8 | byte as usize);
+        let letter = node[3] & 0x1f;
+        if letter >= 26 {
+            return Err(invalid("only the letters A to Z can be read"));
+        }
+        Ok(Node {
+            children: number(&node[..3]),
+            letter: (b'A' + letter) as char,
+            terminal: node[3] & 0x20 != 0,
+            last: node[3] & 0x40 != 0,
+            playability: number(&node[4..]) as u32,
+        })
+    };
+
+    let mut entries = Vec::new();
+    // The nodes whose siblings are still to be read, with the word up to them.
+    let mut stack = vec![(node(0)?.children, String::new())];
+    while let Some((index, prefix)) = stack.pop() {
+        if index == 0 {
+            continue;
+        }
+        // Nodes are shared by the words ending alike, but one that leads back to itself
+        // makes words without end.
+        if prefix.len() >= MAX_WORD {
+            return Err(invalid("a word runs past the longest word there can be"));
+        }
+        let node = node(index)?;
+        if !node.last {
+            stack.push((index + 1, prefix.clone()));
+        }
+        let word = format!("{prefix}{}", node.letter);
+        stack.push((node.children, word.clone()));
+        if node.terminal {
+            entries.push(Entry {
+                word,
+                definition: None,
+                playability: Some(node.playability),
+            });
+        }
+    }
+    entries.sort_by(|a, b| a.word.cmp(&b.word));
+    Ok(entries)
+}
+
+struct Node {
+    children: usize,
+    letter: char,
+    terminal: bool,
+    last: bool,
+    playability: u32,
+}
+
+/// Writes what `entries` say about their words, a line per word that has anything to
+/// say, its playability and definition after it, tab separated and empty if unknown.
+pub fn write_metadata(entries: &[Entry], mut out: impl Write) -> io::Result<()> {
+    for entry in entries {
+        if entry.definition.is_none() && entry.playability.is_none() {
+            continue;
+        }
+        writeln!(
+            out,
+            "{}\t{}\t{}",
+            entry.word,
+            entry.playability.map(|p| p.to_string()).unwrap_or_default(),
+            entry.definition.as_deref().unwrap_or_default()
+        )?;
+    }
+    Ok(())
+}
+
+fn invalid(reason: &str) -> ScrabbleError {
+    ScrabbleError::Import(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A node of a dawg, its flags being 0x20 for a word ending and 0x40 for the last of
+    // its siblings.
+    fn node(children: usize, letter: char, flags: u8, playability: u32) -> Vec<u8> {
+        let [_, high, middle, low] = (children as u32).to_be_bytes();
+        let [_, p1, p2, p3] = playability.to_be_bytes();
+        vec![high, middle, low, flags | (letter as u8 - b'A'), p1, p2, p3]
+    }
+
+    // AT and B.
+    fn dawg() -> Vec<u8> {
+        [
+            node(1, 'A', 0x40, 0),
+            node(3, 'A', 0, 0),
+            node(0, 'B', 0x60, 5),
+            node(0, 'T', 0x60, 7),
+        ]
+        .concat()
+    }
+
+    fn words(entries: &[Entry]) -> Vec<(&str, Option<u32>)> {
+        entries
+            .iter()
+            .map(|entry| (entry.word.as_str(), entry.playability))
+            .collect()
+    }
+
+    #[test]
+    fn zyzzyva_lists_keep_their_definitions() {
+        let list = "#Zyzzyva word list\naa rough, cindery lava [n AAS]\n\nAB\n";
+        let entries = read_zyzzyva(list.as_bytes()).unwrap();
+        assert_eq!(words(&entries), [("AA", None), ("AB", None)]);
+        assert_eq!(
+            entries[0].definition.as_deref(),
+            Some("rough, cindery lava [n AAS]")
+        );
+        assert_eq!(entries[1].definition, None);
+
+        let mut out = Vec::new();
+        write_metadata(&entries, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "AA\t\trough, cindery lava [n AAS]\n"
+        );
+    }
+
+    #[test]
+    fn dawgs_of_either_version_are_read() {
+        let v0 = dawg();
+        let v1 = [vec![1; V1_HEADER], dawg()].concat();
+        for bytes in [v0, v1] {
+            assert_eq!(
+                words(&read_dawg(&bytes).unwrap()),
+                [("AT", Some(7)), ("B", Some(5))]
+            );
+        }
+    }
+
+    #[test]
+    fn broken_dawgs_are_refused() {
+        let looping = [node(1, 'A', 0x40, 0), node(1, 'A', 0x40, 0)].concat();
+        let past_the_end = [node(1, 'A', 0x40, 0), node(9, 'A', 0x40, 0)].concat();
+        let mut not_a_letter = dawg();
+        not_a_letter[NODE_SIZE + 3] = 30;
+        for bytes in [vec![], vec![2], looping, past_the_end, not_a_letter] {
+            assert!(matches!(read_dawg(&bytes), Err(ScrabbleError::Import(_))));
+        }
+    }
+}
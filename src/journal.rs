@@ -0,0 +1,457 @@
+//! The game as the list of what happened in it since the racks were dealt. Every entry
+//! can be applied to the state before it and reverted from the state after it, and
+//! both are deterministic: racks and the bag are kept sorted, so the tiles in them
+//! come out the same whatever order they were drawn or shuffled in. Taking turns back
+//! reverts the last entries, and the state after any number of them can be played out
+//! from the start. Each turn is kept with how it goes in the game record, so the record
+//! is taken back with it.
+
+use cursive::Vec2;
+
+use crate::{board::Board, dictionary::Dictionary, gcg::Turn, tile::Tile};
+
+/// What happened, turn by turn. A turn is a placement, exchange, pass or challenge,
+/// followed by the points the house rules give and the tiles drawn.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Entry {
+    /// `tiles` placed and committed, scoring `score` with the bingo bonus.
+    TilesPlaced {
+        player: usize,
+        tiles: Vec<(Vec2, Tile)>,
+        score: isize,
+    },
+    /// `tiles` put back in the bag.
+    Exchanged {
+        player: usize,
+        tiles: Vec<Tile>,
+    },
+    Passed {
+        player: usize,
+    },
+    /// A challenge of the last play, which is withdrawn if it formed a phony.
+    Challenged {
+        player: usize,
+        withdrawn: Option<Withdrawal>,
+    },
+    Drew {
+        player: usize,
+        tiles: Vec<Tile>,
+    },
+    /// Points given or taken off outside of a placement, by the house rules.
+    Scored {
+        player: usize,
+        points: isize,
+    },
+}
+
+impl Entry {
+    /// The player the entry is about: who played, passed, challenged, drew or scored.
+    pub fn player(&self) -> usize {
+        match self {
+            Self::TilesPlaced { player, .. }
+            | Self::Exchanged { player, .. }
+            | Self::Passed { player }
+            | Self::Challenged { player, .. }
+            | Self::Drew { player, .. }
+            | Self::Scored { player, .. } => *player,
+        }
+    }
+
+    /// Whether the entry starts a turn, rather than following one.
+    pub fn is_turn(&self) -> bool {
+        !matches!(self, Self::Drew { .. } | Self::Scored { .. })
+    }
+}
+
+/// A play taken back after a challenge: its tiles go back on the rack of the player who
+/// made it, the tiles they drew after it back in the bag, and its score is taken off.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Withdrawal {
+    pub player: usize,
+    pub tiles: Vec<(Vec2, Tile)>,
+    pub drawn: Vec<Tile>,
+    pub score: isize,
+}
+
+/// The tiles on the board, on each rack and in the bag, and each player's score.
+#[derive(Clone)]
+pub struct State {
+    pub board: Board,
+    pub racks: Vec<Vec<Tile>>,
+    pub bag: Vec<Tile>,
+    pub scores: Vec<isize>,
+}
+
+impl State {
+    pub fn new(
+        board: Board,
+        mut racks: Vec<Vec<Tile>>,
+        mut bag: Vec<Tile>,
+        scores: Vec<isize>,
+    ) -> Self {
+        for rack in &mut racks {
+            rack.sort_unstable();
+        }
+        bag.sort_unstable();
+        Self {
+            board,
+            racks,
+            bag,
+            scores,
+        }
+    }
+
+    pub fn apply(&mut self, entry: &Entry, dict: &impl Dictionary) {
+        match entry {
+            Entry::TilesPlaced {
+                player,
+                tiles,
+                score,
+            } => {
+                self.place(*player, tiles, dict);
+                self.scores[*player] += score;
+            }
+            Entry::Exchanged { player, tiles } => {
+                move_tiles(&mut self.racks[*player], &mut self.bag, tiles)
+            }
+            Entry::Passed { .. }
+            | Entry::Challenged {
+                withdrawn: None, ..
+            } => (),
+            Entry::Challenged {
+                withdrawn: Some(withdrawal),
+                ..
+            } => self.withdraw(withdrawal, dict),
+            Entry::Drew { player, tiles } => {
+                move_tiles(&mut self.bag, &mut self.racks[*player], tiles)
+            }
+            Entry::Scored { player, points } => self.scores[*player] += points,
+        }
+    }
+
+    pub fn revert(&mut self, entry: &Entry, dict: &impl Dictionary) {
+        match entry {
+            Entry::TilesPlaced {
+                player,
+                tiles,
+                score,
+            } => {
+                self.unplace(*player, tiles, dict);
+                self.scores[*player] -= score;
+            }
+            Entry::Exchanged { player, tiles } => {
+                move_tiles(&mut self.bag, &mut self.racks[*player], tiles)
+            }
+            Entry::Passed { .. }
+            | Entry::Challenged {
+                withdrawn: None, ..
+            } => (),
+            Entry::Challenged {
+                withdrawn: Some(withdrawal),
+                ..
+            } => {
+                let player = withdrawal.player;
+                move_tiles(&mut self.bag, &mut self.racks[player], &withdrawal.drawn);
+                self.place(player, &withdrawal.tiles, dict);
+                self.scores[player] += withdrawal.score;
+            }
+            Entry::Drew { player, tiles } => {
+                move_tiles(&mut self.racks[*player], &mut self.bag, tiles)
+            }
+            Entry::Scored { player, points } => self.scores[*player] -= points,
+        }
+    }
+
+    fn withdraw(&mut self, withdrawal: &Withdrawal, dict: &impl Dictionary) {
+        let player = withdrawal.player;
+        self.unplace(player, &withdrawal.tiles, dict);
+        move_tiles(&mut self.racks[player], &mut self.bag, &withdrawal.drawn);
+        self.scores[player] -= withdrawal.score;
+    }
+
+    // Takes `tiles`, as drawn, off the rack of `player` and commits them to the board.
+    fn place(&mut self, player: usize, tiles: &[(Vec2, Tile)], dict: &impl Dictionary) {
+        for &(pos, tile) in tiles {
+            take(&mut self.racks[player], tile.drawn());
+            self.board.place_at(tile, &pos);
+        }
+        self.board.commit_tentative(dict);
+    }
+
+    fn unplace(&mut self, player: usize, tiles: &[(Vec2, Tile)], dict: &impl Dictionary) {
+        let positions = tiles.iter().map(|&(pos, _)| pos).collect::<Vec<_>>();
+        self.board.retract(&positions, dict);
+        for &(_, tile) in tiles {
+            put(&mut self.racks[player], tile.drawn());
+        }
+    }
+}
+
+/// Every entry since the racks were dealt, and the state they've led to.
+#[derive(Clone)]
+pub struct Journal {
+    start: State,
+    state: State,
+    entries: Vec<Entry>,
+    // The record of the turns played before `start`, and then of each entry that
+    // starts a turn.
+    turns: Vec<Turn>,
+}
+
+impl Journal {
+    pub fn new(start: State) -> Self {
+        Self::resumed(start, Vec::new())
+    }
+
+    /// A journal of a game resumed at `start`, after `turns`.
+    pub fn resumed(start: State, turns: Vec<Turn>) -> Self {
+        Self {
+            state: start.clone(),
+            start,
+            entries: Vec::new(),
+            turns,
+        }
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// The record of every turn, those played before the game was resumed included.
+    pub fn turns(&self) -> &[Turn] {
+        &self.turns
+    }
+
+    /// The record of the latest turn, for notes and points added after it.
+    pub fn last_turn_mut(&mut self) -> Option<&mut Turn> {
+        self.turns.last_mut()
+    }
+
+    /// The state after every entry.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Adds an entry starting a turn, recorded as `turn`.
+    pub fn push_turn(&mut self, entry: Entry, turn: Turn, dict: &impl Dictionary) {
+        debug_assert!(entry.is_turn());
+        self.state.apply(&entry, dict);
+        self.entries.push(entry);
+        self.turns.push(turn);
+    }
+
+    /// Adds an entry following a turn.
+    pub fn push(&mut self, entry: Entry, dict: &impl Dictionary) {
+        debug_assert!(!entry.is_turn());
+        self.state.apply(&entry, dict);
+        self.entries.push(entry);
+    }
+
+    /// Reverts the last turn, with the entries following it, and returns the entry it
+    /// started with. Nothing is reverted before the first turn.
+    pub fn undo(&mut self, dict: &impl Dictionary) -> Option<Entry> {
+        let start = self.entries.iter().rposition(Entry::is_turn)?;
+        let mut reverted = self.entries.split_off(start);
+        for entry in reverted.iter().rev() {
+            self.state.revert(entry, dict);
+        }
+        self.turns.pop();
+        Some(reverted.swap_remove(0))
+    }
+
+    /// The state after the first `count` entries, played out from the deal, for going
+    /// over the game.
+    pub fn replay(&self, count: usize, dict: &impl Dictionary) -> State {
+        let mut state = self.start.clone();
+        for entry in self.entries.iter().take(count) {
+            state.apply(entry, dict);
+        }
+        state
+    }
+}
+
+// Moves `tiles` from one sorted pile of tiles to another, keeping both sorted.
+fn move_tiles(from: &mut Vec<Tile>, to: &mut Vec<Tile>, tiles: &[Tile]) {
+    for &tile in tiles {
+        take(from, tile);
+        put(to, tile);
+    }
+}
+
+fn take(tiles: &mut Vec<Tile>, tile: Tile) {
+    if let Ok(idx) = tiles.binary_search(&tile) {
+        tiles.remove(idx);
+    }
+}
+
+fn put(tiles: &mut Vec<Tile>, tile: Tile) {
+    let idx = tiles.binary_search(&tile).unwrap_or_else(|idx| idx);
+    tiles.insert(idx, tile);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bag::Distribution, gaddag::Gaddag, gcg::Action};
+
+    fn dict() -> Gaddag {
+        Gaddag::from_words(["AT", "TA"].map(String::from)).unwrap()
+    }
+
+    fn tiles(letters: &str) -> Vec<Tile> {
+        let distribution = Distribution::default();
+        letters
+            .chars()
+            .map(|letter| distribution.written(letter))
+            .collect()
+    }
+
+    fn start() -> State {
+        State::new(
+            Board::new(15),
+            vec![tiles("TAE?"), tiles("QIS")],
+            tiles("XYZAT"),
+            vec![0, 0],
+        )
+    }
+
+    // Racks, bag, scores and the tiles on the board.
+    type Contents = (Vec<Vec<Tile>>, Vec<Tile>, Vec<isize>, Vec<(Vec2, Tile)>);
+
+    // What a state holds, to be compared.
+    fn contents(state: &State) -> Contents {
+        let size = state.board.size;
+        let placed = (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| Vec2::new(x, y)))
+            .filter_map(|pos| Some((pos, state.board.committed_tile_at(&pos)?)))
+            .collect();
+        (
+            state.racks.clone(),
+            state.bag.clone(),
+            state.scores.clone(),
+            placed,
+        )
+    }
+
+    fn placement() -> Vec<(Vec2, Tile)> {
+        let rack = tiles("T?");
+        vec![
+            (Vec2::new(7, 7), rack[0]),
+            (Vec2::new(8, 7), rack[1].played_as('A')),
+        ]
+    }
+
+    fn turn(player: usize) -> Turn {
+        Turn {
+            player,
+            rack: Vec::new(),
+            action: Action::Pass,
+            score: 0,
+            total: 0,
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn entries_revert_to_the_state_they_were_applied_to() {
+        let dict = dict();
+        let entries = [
+            Entry::TilesPlaced {
+                player: 0,
+                tiles: placement(),
+                score: 2,
+            },
+            Entry::Exchanged {
+                player: 1,
+                tiles: tiles("QI"),
+            },
+            Entry::Drew {
+                player: 0,
+                tiles: tiles("XZ"),
+            },
+            Entry::Scored {
+                player: 1,
+                points: -5,
+            },
+        ];
+        for entry in &entries {
+            let mut state = start();
+            state.apply(entry, &dict);
+            assert_ne!(contents(&state), contents(&start()), "{entry:?}");
+            state.revert(entry, &dict);
+            assert_eq!(contents(&state), contents(&start()), "{entry:?}");
+        }
+    }
+
+    #[test]
+    fn withdrawal_puts_back_the_play_and_what_was_drawn() {
+        let dict = dict();
+        let mut state = start();
+        let placed = Entry::TilesPlaced {
+            player: 0,
+            tiles: placement(),
+            score: 2,
+        };
+        let drew = Entry::Drew {
+            player: 0,
+            tiles: tiles("AT"),
+        };
+        let challenged = Entry::Challenged {
+            player: 1,
+            withdrawn: Some(Withdrawal {
+                player: 0,
+                tiles: placement(),
+                drawn: tiles("AT"),
+                score: 2,
+            }),
+        };
+        state.apply(&placed, &dict);
+        state.apply(&drew, &dict);
+        let played = contents(&state);
+        state.apply(&challenged, &dict);
+        assert_eq!(contents(&state), contents(&start()));
+        state.revert(&challenged, &dict);
+        assert_eq!(contents(&state), played);
+    }
+
+    #[test]
+    fn undo_takes_back_a_turn_with_its_record() {
+        let dict = dict();
+        let mut journal = Journal::new(start());
+        let placed = Entry::TilesPlaced {
+            player: 0,
+            tiles: placement(),
+            score: 2,
+        };
+        journal.push_turn(placed.clone(), turn(0), &dict);
+        journal.push(
+            Entry::Drew {
+                player: 0,
+                tiles: tiles("XY"),
+            },
+            &dict,
+        );
+        let after_play = contents(journal.state());
+        journal.push_turn(Entry::Passed { player: 1 }, turn(1), &dict);
+        assert_eq!(contents(&journal.replay(2, &dict)), after_play);
+        assert_eq!(journal.turns().len(), 2);
+
+        assert_eq!(journal.undo(&dict), Some(Entry::Passed { player: 1 }));
+        assert_eq!(contents(journal.state()), after_play);
+        assert_eq!(journal.undo(&dict), Some(placed));
+        assert_eq!(contents(journal.state()), contents(&start()));
+        assert!(journal.turns().is_empty());
+        assert_eq!(journal.undo(&dict), None);
+    }
+
+    #[test]
+    fn resumed_turns_stay_on_record() {
+        let dict = dict();
+        let mut journal = Journal::resumed(start(), vec![turn(0), turn(1)]);
+        journal.push_turn(Entry::Passed { player: 0 }, turn(0), &dict);
+        assert_eq!(journal.turns().len(), 3);
+        journal.undo(&dict);
+        assert_eq!(journal.undo(&dict), None);
+        assert_eq!(journal.turns().len(), 2);
+    }
+}
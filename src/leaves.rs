@@ -0,0 +1,153 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+};
+
+use itertools::Itertools;
+
+use crate::tile::Tile;
+
+const BLANK: char = '?';
+
+/// Reasons a leave file can fail to load.
+#[derive(Debug)]
+pub enum LeavesError {
+    Io { line: usize, source: io::Error },
+    InvalidLine { line: usize, content: String },
+}
+
+/// Equity adjustments for the letters kept on the rack after a move, read from the
+/// plain text leave tables exported from Quackle's strategy data. Every line holds
+/// a leave and its value, separated by whitespace or a comma (`AEINST 22.6` or
+/// `AEINST,22.6`), with `?` standing for a blank.
+#[derive(Clone, Default)]
+pub struct Leaves {
+    values: HashMap<String, f64>,
+}
+
+impl Leaves {
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, LeavesError> {
+        let mut values = HashMap::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|source| LeavesError::Io {
+                line: i + 1,
+                source,
+            })?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line
+                .split(|ch: char| ch == ',' || ch.is_whitespace())
+                .filter(|field| !field.is_empty());
+            let (Some(leave), Some(Ok(value)), None) = (
+                fields.next(),
+                fields.next().map(str::parse::<f64>),
+                fields.next(),
+            ) else {
+                return Err(LeavesError::InvalidLine {
+                    line: i + 1,
+                    content: line.to_string(),
+                });
+            };
+            values.insert(Self::key(leave.chars()), value);
+        }
+        Ok(Self { values })
+    }
+
+    /// Value of keeping `leave`, or zero if the table doesn't list it.
+    pub fn value(&self, leave: &[Tile]) -> f64 {
+        self.values
+            .get(&Self::key(leave.iter().map(|tile| tile.drawn().letter)))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // Leaves are looked up by their sorted letters, so the order on the rack
+    // doesn't matter.
+    fn key(letters: impl Iterator<Item = char>) -> String {
+        letters
+            .map(|ch| match ch {
+                ' ' => BLANK,
+                ch => ch.to_ascii_uppercase(),
+            })
+            .sorted_unstable()
+            .collect()
+    }
+}
+
+impl fmt::Display for LeavesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io { line, source } => write!(f, "Failed to read line {line}: {source}"),
+            Self::InvalidLine { line, content } => {
+                write!(
+                    f,
+                    "Expected a leave and a value on line {line}, got \"{content}\""
+                )
+            }
+        }
+    }
+}
+
+impl Error for LeavesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::InvalidLine { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiles(letters: &str) -> Vec<Tile> {
+        letters
+            .chars()
+            .map(|letter| match letter {
+                '?' => Tile::BLANK,
+                letter => Tile::new(letter, 1),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn values_are_looked_up_whatever_the_order() {
+        let table = "# Quackle leaves\nAEINST 22.6\n\n?s,25.0\nQ -7.5\n";
+        let leaves = Leaves::from_reader(table.as_bytes()).unwrap();
+        assert_eq!(leaves.len(), 3);
+        assert_eq!(leaves.value(&tiles("TSENIA")), 22.6);
+        assert_eq!(leaves.value(&tiles("S?")), 25.0);
+        assert_eq!(
+            leaves.value(&[Tile::BLANK.played_as('X'), Tile::new('S', 1)]),
+            25.0
+        );
+        assert_eq!(leaves.value(&tiles("Q")), -7.5);
+        assert_eq!(leaves.value(&tiles("QU")), 0.0);
+    }
+
+    #[test]
+    fn lines_without_a_leave_and_a_value_are_rejected() {
+        for table in ["AEINST", "AEINST lots", "AE 1.0 2.0"] {
+            let text = format!("Q -7.5\n{table}\n");
+            assert!(matches!(
+                Leaves::from_reader(text.as_bytes()),
+                Err(LeavesError::InvalidLine { line: 2, .. })
+            ));
+        }
+        assert!(Leaves::from_reader("".as_bytes()).unwrap().is_empty());
+    }
+}
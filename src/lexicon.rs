@@ -0,0 +1,364 @@
+//! The word lists a game can be played with, under `[[lexicons]]`, each with the
+//! processed dictionary built from it and cached on disk. A line of a word list can go
+//! on after its word with the word's definition, as CSW and NWL exports do:
+//!
+//! ```text
+//! AA rough, cindery lava [n AAS]
+//! ```
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fs::{self, File},
+    io::{BufRead, BufReader, BufWriter, Cursor, Write},
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+};
+
+use serde_derive::Deserialize;
+
+use crate::{error::Result, gaddag::Gaddag, import};
+
+// Where the lexicon picked as the default on the lexicons screen is kept.
+const DEFAULT_FILE: &str = ".scrabbler-lexicon";
+
+#[derive(Clone, Deserialize)]
+pub struct Lexicon {
+    pub name: String,
+    /// The word list, a word per line, each with its definition after it or not.
+    pub raw: Box<Path>,
+    /// Where the processed dictionary is cached.
+    pub processed: Box<Path>,
+    /// Words played at home on top of the word list, like names, from `house_words`.
+    #[serde(skip)]
+    pub house_words: Option<Box<Path>>,
+    /// Words left out of the dictionary, like offensive ones for school use, from
+    /// `excluded_words`.
+    #[serde(skip)]
+    pub excluded_words: Option<Box<Path>>,
+}
+
+/// How the cached dictionary of a lexicon stands.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Cache {
+    Missing,
+    /// Older than its word list.
+    Stale,
+    Built,
+}
+
+impl Lexicon {
+    /// The cached dictionary, or one built from the word list and cached if there's none
+    /// or the word list, house words or excluded words changed since.
+    pub fn load(&self) -> Result<Gaddag> {
+        self.load_with_progress(|_| ControlFlow::Continue(()))
+    }
+
+    /// Loads the dictionary like `load`, telling `progress` how the build goes if it
+    /// comes to one, see `build`.
+    pub fn load_with_progress(
+        &self,
+        progress: impl FnMut(usize) -> ControlFlow<()>,
+    ) -> Result<Gaddag> {
+        if self.cache() == Cache::Built {
+            return Ok(Gaddag::from_bytes(fs::read(&self.processed)?)?);
+        }
+        self.build(progress)
+    }
+
+    /// Builds the dictionary from the word list and the house words, without the excluded
+    /// words, and caches it, telling `progress` how many words are done as
+    /// `Gaddag::from_reader_with_progress` does. Nothing is cached if the build is
+    /// cancelled. The definitions in the word list, if it has any, are kept by word for
+    /// `definitions`.
+    pub fn build(&self, progress: impl FnMut(usize) -> ControlFlow<()>) -> Result<Gaddag> {
+        let excluded = self.excluded_words()?;
+        let mut list = String::new();
+        let mut defined = Vec::new();
+        for line in BufReader::new(File::open(&self.raw)?).lines() {
+            let line = line?;
+            let (word, definition) = split_line(&line);
+            // Excluded words leave their lines blank, so a bad word is still reported
+            // with its line in the word list.
+            if !excluded.contains(&word.to_uppercase()) {
+                list.push_str(word);
+                if let Some(definition) = definition {
+                    defined.push(import::Entry {
+                        word: word.to_uppercase(),
+                        definition: Some(definition.to_string()),
+                        playability: None,
+                    });
+                }
+            }
+            list.push('\n');
+        }
+        for word in self.house_words()?.difference(&excluded) {
+            list.push_str(word);
+            list.push('\n');
+        }
+        let dict = Gaddag::from_reader_with_progress(Cursor::new(list), progress)?;
+        File::create(&self.processed)?.write_all(dict.as_bytes())?;
+        // An imported word list keeps its words' definitions there already.
+        if !defined.is_empty() {
+            let mut metadata = BufWriter::new(File::create(self.metadata())?);
+            import::write_metadata(&defined, &mut metadata)?;
+            metadata.flush()?;
+        }
+        Ok(dict)
+    }
+
+    /// The words of the word list, its lines that aren't blank, that are played with, and
+    /// the ones left out as excluded words.
+    pub fn words(&self) -> Result<(usize, usize)> {
+        let excluded = self.excluded_words()?;
+        let (mut words, mut left_out) = (0, 0);
+        for line in BufReader::new(File::open(&self.raw)?).lines() {
+            let word = split_line(&line?).0.to_uppercase();
+            if excluded.contains(&word) {
+                left_out += 1;
+            } else if !word.is_empty() {
+                words += 1;
+            }
+        }
+        Ok((words, left_out))
+    }
+
+    /// The house words, upper cased as the word list is, or none without a file of them.
+    pub fn house_words(&self) -> Result<HashSet<String>> {
+        read_words(self.house_words.as_deref())
+    }
+
+    /// The excluded words, upper cased, or none without a file of them.
+    pub fn excluded_words(&self) -> Result<HashSet<String>> {
+        read_words(self.excluded_words.as_deref())
+    }
+
+    pub fn cache(&self) -> Cache {
+        let modified = |path: &Path| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+        let listed = [
+            Some(&self.raw),
+            self.house_words.as_ref(),
+            self.excluded_words.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|path| modified(path))
+        .max();
+        match (modified(&self.processed), listed) {
+            (None, _) => Cache::Missing,
+            (Some(built), Some(listed)) if built < listed => Cache::Stale,
+            (Some(_), _) => Cache::Built,
+        }
+    }
+
+    /// Merges the word lists `lists`, a base lexicon and its supplements, into the word
+    /// list of this lexicon, sorted and without duplicates, and builds its dictionary.
+    /// Words are upper cased first, so a supplement in lower case doesn't add them twice.
+    /// Returns how many words the merged list has.
+    pub fn merge(&self, lists: &[&Path]) -> Result<usize> {
+        let mut words = BTreeSet::new();
+        for list in lists {
+            for line in BufReader::new(File::open(list)?).lines() {
+                let word = split_line(&line?).0.to_uppercase();
+                if !word.is_empty() {
+                    words.insert(word);
+                }
+            }
+        }
+        let mut raw = BufWriter::new(File::create(&self.raw)?);
+        for word in &words {
+            writeln!(raw, "{word}")?;
+        }
+        raw.flush()?;
+        self.build(|_| ControlFlow::Continue(()))?;
+        Ok(words.len())
+    }
+
+    /// Converts the Zyzzyva word list or Quackle dawg at `source` into the word list of
+    /// this lexicon and builds its dictionary, keeping the definitions and playability
+    /// the words come with next to the word list, see `metadata`. Returns how many words
+    /// the list has.
+    pub fn import(&self, source: &Path) -> Result<usize> {
+        let mut entries = import::read(source)?;
+        entries.sort_by(|a, b| a.word.cmp(&b.word));
+        entries.dedup_by(|a, b| a.word == b.word);
+        let mut raw = BufWriter::new(File::create(&self.raw)?);
+        for entry in &entries {
+            writeln!(raw, "{}", entry.word)?;
+        }
+        raw.flush()?;
+        let mut metadata = BufWriter::new(File::create(self.metadata())?);
+        import::write_metadata(&entries, &mut metadata)?;
+        metadata.flush()?;
+        self.build(|_| ControlFlow::Continue(()))?;
+        Ok(entries.len())
+    }
+
+    /// Where what the word list says about its words is kept, the word list's path with
+    /// the extension `meta.tsv`.
+    pub fn metadata(&self) -> PathBuf {
+        self.raw.with_extension("meta.tsv")
+    }
+
+    /// The definitions of the words of the word list, by word, as the last build or
+    /// import kept them, or none if it had none.
+    pub fn definitions(&self) -> Result<HashMap<String, String>> {
+        let mut definitions = HashMap::new();
+        let Ok(metadata) = File::open(self.metadata()) else {
+            return Ok(definitions);
+        };
+        for line in BufReader::new(metadata).lines() {
+            let line = line?;
+            let mut fields = line.splitn(3, '\t');
+            if let (Some(word), Some(_), Some(definition)) =
+                (fields.next(), fields.next(), fields.next())
+            {
+                if !definition.is_empty() {
+                    definitions.insert(word.to_string(), definition.to_string());
+                }
+            }
+        }
+        Ok(definitions)
+    }
+
+    pub fn delete_cache(&self) -> Result<()> {
+        Ok(fs::remove_file(&self.processed)?)
+    }
+}
+
+// The word a line of a word list starts with, and the definition after it if any.
+fn split_line(line: &str) -> (&str, Option<&str>) {
+    let line = line.trim();
+    match line.split_once(char::is_whitespace) {
+        Some((word, definition)) => (word, Some(definition.trim())),
+        None => (line, None),
+    }
+}
+
+// The words of the file at `path`, a word per line, upper cased.
+fn read_words(path: Option<&Path>) -> Result<HashSet<String>> {
+    let mut words = HashSet::new();
+    let Some(path) = path else {
+        return Ok(words);
+    };
+    for line in BufReader::new(File::open(path)?).lines() {
+        let word = line?.trim().to_uppercase();
+        if !word.is_empty() {
+            words.insert(word);
+        }
+    }
+    Ok(words)
+}
+
+/// The lexicon picked as the default, if it's one of `lexicons`, and otherwise the first.
+pub fn default(lexicons: &[Lexicon]) -> Option<&Lexicon> {
+    let name = fs::read_to_string(DEFAULT_FILE).unwrap_or_default();
+    lexicons
+        .iter()
+        .find(|lexicon| lexicon.name == name.trim())
+        .or(lexicons.first())
+}
+
+pub fn set_default(lexicon: &Lexicon) -> Result<()> {
+    Ok(fs::write(DEFAULT_FILE, &lexicon.name)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env,
+        time::{Duration, SystemTime},
+    };
+
+    use super::*;
+    use crate::dictionary::Dictionary;
+
+    // A lexicon in a directory of its own, removed when it's dropped.
+    struct Scratch(Lexicon, PathBuf);
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir = env::temp_dir().join(format!("scrabbler-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir(&dir).unwrap();
+            let lexicon = Lexicon {
+                name: name.to_string(),
+                raw: dir.join("words.txt").into(),
+                processed: dir.join("words.gaddag").into(),
+                house_words: None,
+                excluded_words: None,
+            };
+            Self(lexicon, dir)
+        }
+
+        fn file(&self, name: &str, content: &str) -> Box<Path> {
+            let path = self.1.join(name);
+            fs::write(&path, content).unwrap();
+            path.into()
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.1);
+        }
+    }
+
+    #[test]
+    fn builds_take_house_words_in_and_leave_excluded_words_out() {
+        let mut scratch = Scratch::new("lexicon-build");
+        scratch.file("words.txt", "AA rough, cindery lava\nAB\n\nQI\n");
+        scratch.0.house_words = Some(scratch.file("house.txt", "xu\n"));
+        scratch.0.excluded_words = Some(scratch.file("excluded.txt", "qi\n"));
+        let lexicon = &scratch.0;
+        assert!(lexicon.cache() == Cache::Missing);
+
+        let dict = lexicon.load().unwrap();
+        assert!(["AA", "AB", "XU"].iter().all(|word| dict.accepts(word)));
+        assert!(!dict.accepts("QI"));
+        assert!(lexicon.cache() == Cache::Built);
+        assert!(lexicon.load().unwrap().accepts("XU"));
+        assert_eq!(lexicon.words().unwrap(), (2, 1));
+        assert_eq!(
+            lexicon.definitions().unwrap(),
+            HashMap::from([("AA".to_string(), "rough, cindery lava".to_string())])
+        );
+
+        let later = SystemTime::now() + Duration::from_secs(60);
+        File::options()
+            .write(true)
+            .open(&lexicon.raw)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(lexicon.cache() == Cache::Stale);
+        lexicon.delete_cache().unwrap();
+        assert!(lexicon.cache() == Cache::Missing);
+    }
+
+    #[test]
+    fn merged_lists_are_sorted_without_duplicates() {
+        let scratch = Scratch::new("lexicon-merge");
+        let base = scratch.file("base.txt", "AB\nAA\n");
+        let supplement = scratch.file("supplement.txt", "aa\nZO definition\n");
+        assert_eq!(scratch.0.merge(&[&base, &supplement]).unwrap(), 3);
+        assert_eq!(fs::read_to_string(&scratch.0.raw).unwrap(), "AA\nAB\nZO\n");
+        assert!(scratch.0.cache() == Cache::Built);
+    }
+
+    #[test]
+    fn imports_keep_what_the_list_says() {
+        let scratch = Scratch::new("lexicon-import");
+        let source = scratch.file("source.txt", "ZO a cow\nAA lava\nZO a yak\n");
+        assert_eq!(scratch.0.import(&source).unwrap(), 2);
+        assert_eq!(fs::read_to_string(&scratch.0.raw).unwrap(), "AA\nZO\n");
+        assert_eq!(
+            scratch
+                .0
+                .definitions()
+                .unwrap()
+                .get("ZO")
+                .map(String::as_str),
+            Some("a cow")
+        );
+    }
+}
@@ -0,0 +1,39 @@
+pub mod archive;
+pub mod arena;
+pub mod bag;
+pub mod board;
+pub mod browser;
+pub mod chart;
+pub mod clock;
+pub mod csv;
+pub mod dictionary;
+pub mod driver;
+pub mod endgame;
+pub mod engine;
+pub mod error;
+pub mod event;
+pub mod gaddag;
+pub mod game;
+pub mod gcg;
+pub mod import;
+pub mod journal;
+pub mod leaves;
+pub mod lexicon;
+pub mod mail;
+pub mod observer;
+pub mod panels;
+pub mod practice;
+pub mod printout;
+pub mod readout;
+pub mod reference;
+pub mod replay;
+pub mod rules;
+pub mod scenario;
+pub mod script;
+pub mod solver;
+pub mod stats;
+pub mod strategy;
+pub mod tile;
+pub mod tracking;
+pub mod wordlists;
+pub mod zobrist;
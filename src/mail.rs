@@ -0,0 +1,287 @@
+//! Move files for playing by mail. Each player keeps a copy of the game, started with
+//! the same seed so the bags agree, and the move files carry one turn at a time
+//! between them:
+//!
+//! ```text
+//! scrabbler move
+//! turn 12
+//! board 9f1c0a44d2e3b871
+//! play 8H C.T
+//! ```
+//!
+//! The board line is the hash of the letters on the board before the move (see
+//! `Board::hash`), so a file can't be imported into a game it wasn't written for. The
+//! move is `play` followed by its notation (see `Move::notation`), `exchange` followed
+//! by the letters, `pass`, or `challenge`. Blanks are written as tiles are in GCG
+//! files (see `Tile::label`).
+
+use std::fmt;
+
+use cursive::Vec2;
+
+use crate::{
+    board::Alignment,
+    driver::Decision,
+    error::{Result, ScrabbleError},
+    tile::Tile,
+};
+
+const HEADER: &str = "scrabbler move";
+
+/// A move as written, by the labels of its tiles.
+pub enum MailMove {
+    Play { tiles: Vec<(Vec2, char)> },
+    Exchange(Vec<char>),
+    Pass,
+    Challenge,
+}
+
+pub struct MoveFile {
+    pub turn: usize,
+    pub board: u64,
+    pub play: MailMove,
+}
+
+impl MoveFile {
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut lines = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+        if lines.next() != Some(HEADER) {
+            return Err(invalid("missing header"));
+        }
+        let turn = match lines.next().and_then(|line| line.strip_prefix("turn ")) {
+            Some(turn) => turn.parse().map_err(|_| invalid("invalid turn"))?,
+            None => return Err(invalid("missing turn")),
+        };
+        let board = match lines.next().and_then(|line| line.strip_prefix("board ")) {
+            Some(hash) => u64::from_str_radix(hash, 16).map_err(|_| invalid("invalid board"))?,
+            None => return Err(invalid("missing board")),
+        };
+        let mut fields = lines.next().unwrap_or_default().split_whitespace();
+        let play = match (fields.next(), fields.next(), fields.next()) {
+            (Some("play"), Some(notation), Some(word)) => MailMove::Play {
+                tiles: parse_play(notation, word)?,
+            },
+            (Some("exchange"), Some(letters), None) => {
+                MailMove::Exchange(letters.chars().map(|ch| ch.to_ascii_uppercase()).collect())
+            }
+            (Some("pass"), None, None) => MailMove::Pass,
+            (Some("challenge"), None, None) => MailMove::Challenge,
+            _ => return Err(invalid("invalid move")),
+        };
+        Ok(Self { turn, board, play })
+    }
+
+    /// Conventional name of the file for this move.
+    pub fn file_name(&self) -> String {
+        format!("scrabbler-turn-{}.move", self.turn)
+    }
+}
+
+/// Tiles of a play in the "8H C.T" notation by their labels, blanks in lower case,
+/// skipping the letters played through.
+pub fn parse_play(coordinate: &str, word: &str) -> Result<Vec<(Vec2, char)>> {
+    let (alignment, column, row) = match coordinate.chars().next() {
+        Some(ch) if ch.is_ascii_digit() => {
+            let split = coordinate.find(|ch: char| !ch.is_ascii_digit());
+            let (row, column) = coordinate.split_at(split.unwrap_or(coordinate.len()));
+            (Alignment::Horizontal, column, row)
+        }
+        _ => {
+            let (column, row) = coordinate.split_at(1.min(coordinate.len()));
+            (Alignment::Vertical, column, row)
+        }
+    };
+    let (Some(column @ 'A'..='Z'), Ok(row @ 1..)) = (
+        column.chars().next().map(|ch| ch.to_ascii_uppercase()),
+        row.parse::<usize>(),
+    ) else {
+        return Err(invalid("invalid coordinate"));
+    };
+
+    let start = Vec2::new(column as usize - 'A' as usize, row - 1);
+    Ok(word
+        .chars()
+        .enumerate()
+        .filter(|&(_, letter)| letter != '.')
+        .map(|(i, letter)| {
+            let pos = match alignment {
+                Alignment::Vertical => start.map_y(|y| y + i),
+                _ => start.map_x(|x| x + i),
+            };
+            (pos, letter)
+        })
+        .collect())
+}
+
+fn invalid(reason: &str) -> ScrabbleError {
+    ScrabbleError::MoveFile(reason.to_string())
+}
+
+impl MailMove {
+    /// The move as a seat hands it over (see `Decision`), with the tiles taken from
+    /// `rack`.
+    pub fn decision(&self, rack: &[Tile]) -> Result<Decision> {
+        let mut rack = rack.to_vec();
+        let mut take = |label: char| match rack.iter().position(|tile| tile.is_written(label)) {
+            Some(idx) => Ok(rack.swap_remove(idx)),
+            None => Err(ScrabbleError::NotOnRack(label.to_string())),
+        };
+        Ok(match self {
+            Self::Play { tiles } => Decision::Play(
+                tiles
+                    .iter()
+                    .map(|&(pos, label)| {
+                        let letter = label.to_uppercase().next().unwrap_or(label);
+                        Ok((pos, take(label)?.played_as(letter)))
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+            Self::Exchange(labels) => Decision::Exchange(
+                labels
+                    .iter()
+                    .map(|&label| take(label))
+                    .collect::<Result<_>>()?,
+            ),
+            Self::Pass => Decision::Pass,
+            Self::Challenge => Decision::Challenge,
+        })
+    }
+}
+
+impl fmt::Display for MoveFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        writeln!(f, "turn {}", self.turn)?;
+        writeln!(f, "board {:016x}", self.board)?;
+        match &self.play {
+            MailMove::Play { tiles } => writeln!(f, "play {}", play_notation(tiles)),
+            MailMove::Exchange(letters) => {
+                writeln!(f, "exchange {}", letters.iter().collect::<String>())
+            }
+            MailMove::Pass => writeln!(f, "pass"),
+            MailMove::Challenge => writeln!(f, "challenge"),
+        }
+    }
+}
+
+// The notation of a play from its tiles alone. Squares between the tiles are the
+// letters played through, written as '.'; letters the play extends past its first and
+// last tile are left out, as they are implied by the board.
+fn play_notation(tiles: &[(Vec2, char)]) -> String {
+    let first = tiles
+        .iter()
+        .map(|(pos, _)| *pos)
+        .min_by_key(|pos| (pos.y, pos.x));
+    let Some(start) = first else {
+        return String::new();
+    };
+    let vertical = tiles
+        .iter()
+        .any(|(pos, _)| pos.x == start.x && pos.y != start.y);
+    let len = tiles
+        .iter()
+        .map(|(pos, _)| {
+            if vertical {
+                pos.y - start.y
+            } else {
+                pos.x - start.x
+            }
+        })
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let word = (0..len)
+        .map(|i| {
+            let pos = if vertical {
+                start.map_y(|y| y + i)
+            } else {
+                start.map_x(|x| x + i)
+            };
+            tiles
+                .iter()
+                .find(|(p, _)| *p == pos)
+                .map_or('.', |&(_, letter)| letter)
+        })
+        .collect::<String>();
+    let column = (b'A' + start.x as u8) as char;
+    let row = start.y + 1;
+    if vertical {
+        format!("{column}{row} {word}")
+    } else {
+        format!("{row}{column} {word}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "scrabbler move\nturn 12\nboard 9f1c0a44d2e3b871\nplay 8H C.t\n";
+
+    #[test]
+    fn move_files_read_back_as_written() {
+        let file = MoveFile::parse(FILE).unwrap();
+        assert_eq!((file.turn, file.board), (12, 0x9f1c0a44d2e3b871));
+        assert!(matches!(
+            &file.play,
+            MailMove::Play { tiles } if tiles == &[(Vec2::new(7, 7), 'C'), (Vec2::new(9, 7), 't')]
+        ));
+        assert_eq!(file.to_string(), FILE);
+        assert_eq!(file.file_name(), "scrabbler-turn-12.move");
+    }
+
+    #[test]
+    fn plays_down_are_written_by_column_first() {
+        let tiles = [(Vec2::new(2, 4), 'O'), (Vec2::new(2, 3), 'N')];
+        assert_eq!(play_notation(&tiles), "C4 NO");
+        assert_eq!(
+            parse_play("C4", "NO").unwrap(),
+            [(Vec2::new(2, 3), 'N'), (Vec2::new(2, 4), 'O')]
+        );
+        assert!(parse_play("4", "NO").is_err());
+        assert!(parse_play("C0", "NO").is_err());
+    }
+
+    #[test]
+    fn moves_are_made_from_the_rack() {
+        let rack = [Tile::new('C', 3), Tile::BLANK, Tile::new('Q', 10)];
+        assert_eq!(
+            MoveFile::parse(FILE).unwrap().play.decision(&rack).unwrap(),
+            Decision::Play(vec![
+                (Vec2::new(7, 7), Tile::new('C', 3)),
+                (Vec2::new(9, 7), Tile::BLANK.played_as('T')),
+            ])
+        );
+        assert_eq!(
+            MailMove::Exchange(vec!['Q', '?']).decision(&rack).unwrap(),
+            Decision::Exchange(vec![Tile::new('Q', 10), Tile::BLANK])
+        );
+        assert!(matches!(
+            MailMove::Exchange(vec!['Q', 'Q']).decision(&rack),
+            Err(ScrabbleError::NotOnRack(label)) if label == "Q"
+        ));
+    }
+
+    #[test]
+    fn invalid_files_are_refused() {
+        for content in [
+            "",
+            "scrabbler move\nboard 00\npass\n",
+            "scrabbler move\nturn x\nboard 00\npass\n",
+            "scrabbler move\nturn 1\nboard zz\npass\n",
+            "scrabbler move\nturn 1\nboard 00\npass now\n",
+            "scrabbler move\nturn 1\nboard 00\n",
+        ] {
+            assert!(matches!(
+                MoveFile::parse(content),
+                Err(ScrabbleError::MoveFile(_))
+            ));
+        }
+        let exchange =
+            MoveFile::parse("scrabbler move\n turn 1\nboard 00\n\nexchange q?\n").unwrap();
+        assert!(matches!(exchange.play, MailMove::Exchange(letters) if letters == ['Q', '?']));
+    }
+}
@@ -1,51 +1,198 @@
-mod board;
-mod event;
-mod gaddag;
-mod game;
-
-use gaddag::Gaddag;
-use game::Game;
+use scrabbler::{
+    archive::{Archive, Entry, Query},
+    arena::{self, Contender},
+    bag::Distribution,
+    board::Board,
+    browser,
+    clock::Preset,
+    csv, engine,
+    error::{Result, ScrabbleError},
+    gaddag::{BuildError, Gaddag},
+    game::{DisplayOptions, Game, Options, PlayerProfile, BOARD_SIZE, BOARD_SIZES},
+    gcg,
+    leaves::Leaves,
+    lexicon::{self, Cache, Lexicon},
+    observer::EventLog,
+    printout, reference,
+    replay::Replay,
+    scenario::{self, Scenario},
+    script::HouseRules,
+    strategy::Effort,
+};
 
 use std::{
-    error::Error,
-    fs::{self, File},
-    io::{BufRead, BufReader, Write},
+    collections::{HashMap, HashSet},
+    env,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader},
+    ops::ControlFlow,
     path::Path,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
 };
 
 use cursive::{
     align::HAlign,
-    view::{Nameable, Resizable},
-    views::{Button, Dialog, DummyView, EditView, LinearLayout, Panel, SelectView},
+    utils::Counter,
+    view::{Nameable, Resizable, Scrollable},
+    views::{
+        Button, Checkbox, Dialog, DummyView, EditView, LinearLayout, Panel, ProgressBar,
+        SelectView, TextView,
+    },
     Cursive,
 };
 use serde_derive::Deserialize;
 
 #[derive(Deserialize)]
 struct Config {
-    raw_dict: Box<Path>,
-    processed_dict: Box<Path>,
+    // A lexicon of its own, from before there could be several.
+    raw_dict: Option<Box<Path>>,
+    processed_dict: Option<Box<Path>>,
+    #[serde(default)]
+    lexicons: Vec<Lexicon>,
+    leaves: Option<Box<Path>>,
+    history: Option<Box<Path>>,
+    event_log: Option<Box<Path>>,
+    house_rules: Option<Box<Path>>,
+    house_words: Option<Box<Path>>,
+    board_layout: Option<Box<Path>>,
+    excluded_words: Option<Box<Path>>,
+    scenarios: Option<Box<Path>>,
     players: Vec<PlayerProfile>,
+    #[serde(default)]
+    bag: Distribution,
+    #[serde(default)]
+    rules: Options,
+    #[serde(default)]
+    display: DisplayOptions,
 }
 
-#[derive(Deserialize, Clone)]
-struct PlayerProfile {
-    name: String,
+impl Config {
+    // The lexicons set up, the one of `raw_dict` and `processed_dict` first.
+    fn lexicons(&self) -> Vec<Lexicon> {
+        let single = match (&self.raw_dict, &self.processed_dict) {
+            (Some(raw), Some(processed)) => Some(Lexicon {
+                name: raw
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned(),
+                raw: raw.clone(),
+                processed: processed.clone(),
+                house_words: None,
+                excluded_words: None,
+            }),
+            _ => None,
+        };
+        // Every lexicon takes the house words and excluded words.
+        single
+            .into_iter()
+            .chain(self.lexicons.clone())
+            .map(|lexicon| Lexicon {
+                house_words: self.house_words.clone(),
+                excluded_words: self.excluded_words.clone(),
+                ..lexicon
+            })
+            .collect()
+    }
+
+    fn lexicon(&self) -> Result<Lexicon> {
+        lexicon::default(&self.lexicons())
+            .cloned()
+            .ok_or(ScrabbleError::NoLexicon)
+    }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
+fn main() -> Result<()> {
+    let args = env::args().collect::<Vec<_>>();
+    // Needs no config, for preparing the word lists one names.
+    if let Some(at) = args.iter().position(|arg| arg == "--merge") {
+        return merge_lists(&args[at + 1..]);
+    }
+    if let Some(at) = args.iter().position(|arg| arg == "--import") {
+        return import_list(&args[at + 1..]);
+    }
     let conf: Config = toml::from_str(&fs::read_to_string("scrabble_config.toml")?)?;
-    let dict = if let Ok(bytes) = fs::read(&conf.processed_dict) {
-        Gaddag::from_bytes(bytes)?
-    } else {
-        let dict = Gaddag::from_words(
-            BufReader::new(File::open(&conf.raw_dict)?)
-                .lines()
-                .flatten(),
-        );
-        File::create(&conf.processed_dict)?.write_all(dict.as_bytes())?;
-        dict
+    if env::args().any(|arg| arg == "--engine") {
+        engine::run(&load_dict(&conf)?, io::stdin().lock(), io::stdout().lock())?;
+        return Ok(());
+    }
+    if let Some(at) = args.iter().position(|arg| arg == "--match") {
+        return run_match(&conf, &args[at + 1..]);
+    }
+    if env::args().any(|arg| arg == "--check-solver") {
+        let disagreements = reference::check(&load_dict(&conf)?, 1, 0, io::stdout().lock())?;
+        println!("{disagreements} disagreements");
+        return Ok(());
+    }
+
+    // A dictionary built before the screen is set up tells how far it's got on the
+    // terminal.
+    let building = |lexicon: &Lexicon| {
+        let name = lexicon.name.clone();
+        move |done| {
+            eprint!("\rBuilding the dictionary of {name}: {done} words");
+            ControlFlow::Continue(())
+        }
+    };
+    let (lexicon, dict) = match conf
+        .lexicon()
+        .and_then(|lexicon| Ok((lexicon.load_with_progress(building(&lexicon))?, lexicon)))
+    {
+        Ok((dict, lexicon)) => (lexicon, dict),
+        Err(e) => return fail("Failed to load dictionary", e),
+    };
+    let house_words = match lexicon.house_words() {
+        Ok(words) => words,
+        Err(e) => return fail("Failed to load house words", e),
+    };
+    let definitions = match lexicon.definitions() {
+        Ok(definitions) => Arc::new(definitions),
+        Err(e) => return fail("Failed to load definitions", e),
+    };
+    let leaves = match &conf.leaves {
+        Some(path) => match load_leaves(path) {
+            Ok(leaves) => leaves,
+            Err(e) => return fail("Failed to load leaves", e),
+        },
+        None => Leaves::default(),
     };
+    let house_rules = match &conf.house_rules {
+        Some(path) => match HouseRules::load(path) {
+            Ok(rules) => Some(Arc::new(rules)),
+            Err(e) => return fail("Failed to load house rules", e),
+        },
+        None => None,
+    };
+    // Games are filed under the word list they were played with.
+    let archive = conf
+        .history
+        .as_ref()
+        .map(|path| Archive::new(path.to_path_buf(), lexicon.name.clone()));
+    let blank = Board::new(BOARD_SIZE)
+        .with_palette(conf.display.palette)
+        .with_premiums(conf.display.premiums.clone());
+    let history = (archive.clone(), dict.clone(), blank);
+    let lexicons = conf.lexicons();
+    let setup = Setup {
+        dict: dict.clone(),
+        leaves,
+        archive,
+        event_log: conf.event_log.clone(),
+        house_rules,
+        house_words,
+        definitions,
+        layout: conf.board_layout.clone(),
+        distribution: conf.bag.clone(),
+        options: conf.rules,
+        display: conf.display.clone(),
+    };
+    let practice = (setup.clone(), conf.players.clone(), conf.scenarios.clone());
+    let browsed = (setup.dict.clone(), setup.definitions.clone());
 
     let mut siv = cursive::default();
     siv.add_layer(
@@ -54,7 +201,38 @@ fn main() -> Result<(), Box<dyn Error>> {
             .content(
                 LinearLayout::vertical()
                     .child(Button::new_raw("New game", move |s| {
-                        new_game(s, dict.clone(), &conf.players)
+                        new_game(s, setup.clone(), &conf.players)
+                    }))
+                    .child(Button::new_raw("Scenarios", move |s| {
+                        let (setup, players, path) = &practice;
+                        match path {
+                            Some(path) => show_scenarios(s, path, setup, players),
+                            None => s.add_layer(
+                                Dialog::info(
+                                    "Set `scenarios` in scrabble_config.toml to practice positions.",
+                                )
+                                .title("No scenarios"),
+                            ),
+                        }
+                    }))
+                    .child(Button::new_raw("History", move |s| {
+                        let (archive, dict, blank) = &history;
+                        match archive {
+                            Some(archive) => show_history(s, archive, dict, blank),
+                            None => s.add_layer(
+                                Dialog::info(
+                                    "Set `history` in scrabble_config.toml to keep finished games.",
+                                )
+                                .title("No history"),
+                            ),
+                        }
+                    }))
+                    .child(Button::new_raw("Dictionary", move |s| {
+                        let (dict, definitions) = &browsed;
+                        browser::show(s, dict.clone(), definitions.clone())
+                    }))
+                    .child(Button::new_raw("Lexicons", move |s| {
+                        show_lexicons(s, lexicons.clone())
                     }))
                     .child(Button::new_raw("How to play", help))
                     .child(Button::new_raw("Exit", Cursive::quit)),
@@ -68,52 +246,605 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+// Plays the bots styled by the first two of `args` against each other over as many
+// games as the third says, 100 if it doesn't, and prints the report.
+fn run_match(conf: &Config, args: &[String]) -> Result<()> {
+    const GAMES: usize = 100;
+    let leaves = match &conf.leaves {
+        Some(path) => load_leaves(path)?,
+        None => Leaves::default(),
+    };
+    let contender = |name: Option<&String>| {
+        let name = name.map(String::as_str).unwrap_or_default();
+        Contender::parse(name, &leaves).ok_or(ScrabbleError::Arguments(format!(
+            "unknown bot style \"{name}\", expected score, equity, defense or equity-defense"
+        )))
+    };
+    let (first, second) = (contender(args.first())?, contender(args.get(1))?);
+    let games = match args.get(2) {
+        Some(games) => games
+            .parse()
+            .map_err(|_| ScrabbleError::Arguments(format!("invalid number of games {games}")))?,
+        None => GAMES,
+    };
+    println!(
+        "{}",
+        arena::run(&load_dict(conf)?, [&first, &second], games, 0)
+    );
+    Ok(())
+}
+
+// Merges the word lists after the first two arguments into the first, and builds the
+// dictionary from it into the second.
+fn merge_lists(args: &[String]) -> Result<()> {
+    let [raw, processed, lists @ ..] = args else {
+        return Err(ScrabbleError::Arguments(
+            "expected the merged list, its dictionary and the lists to merge".to_string(),
+        ));
+    };
+    if lists.is_empty() {
+        return Err(ScrabbleError::Arguments("no lists to merge".to_string()));
+    }
+    let merged = Lexicon {
+        name: String::new(),
+        raw: Path::new(raw).into(),
+        processed: Path::new(processed).into(),
+        house_words: None,
+        excluded_words: None,
+    };
+    let lists = lists.iter().map(Path::new).collect::<Vec<_>>();
+    let words = merged.merge(&lists)?;
+    println!(
+        "{words} words from {} lists written to {raw} and {processed}",
+        lists.len()
+    );
+    Ok(())
+}
+
+// Converts the word list in the first argument into the second, and builds the
+// dictionary from it into the third.
+fn import_list(args: &[String]) -> Result<()> {
+    let [source, raw, processed] = args else {
+        return Err(ScrabbleError::Arguments(
+            "expected the list to import, the word list and its dictionary".to_string(),
+        ));
+    };
+    let imported = Lexicon {
+        name: String::new(),
+        raw: Path::new(raw).into(),
+        processed: Path::new(processed).into(),
+        house_words: None,
+        excluded_words: None,
+    };
+    let words = imported.import(Path::new(source))?;
+    println!("{words} words from {source} written to {raw} and {processed}");
+    println!(
+        "Their definitions and playability are kept in {}",
+        imported.metadata().display()
+    );
+    Ok(())
+}
+
+// Shows why the game can't start before quitting with the error.
+fn fail<T>(title: &str, e: ScrabbleError) -> Result<T> {
+    let mut siv = cursive::default();
+    siv.add_layer(
+        Dialog::text(e.to_string())
+            .title(title)
+            .button("Quit", Cursive::quit),
+    );
+    siv.run();
+    Err(e)
+}
+
+fn load_dict(conf: &Config) -> Result<Gaddag> {
+    conf.lexicon()?.load()
+}
+
+// Lists the lexicons set up with their words and the state of their caches, to build,
+// delete or pick as the default for the next start.
+fn show_lexicons(s: &mut Cursive, lexicons: Vec<Lexicon>) {
+    let default = lexicon::default(&lexicons).map(|lexicon| lexicon.name.clone());
+    let mut select = SelectView::<Lexicon>::new();
+    for lexicon in lexicons.iter().cloned() {
+        let words = match lexicon.words() {
+            Ok((words, 0)) => format!("{words} words"),
+            Ok((words, excluded)) => format!("{words} words, {excluded} excluded"),
+            Err(e) => e.to_string(),
+        };
+        let cache = match lexicon.cache() {
+            Cache::Missing => "not built",
+            Cache::Stale => "stale",
+            Cache::Built => "built",
+        };
+        let marker = if default.as_ref() == Some(&lexicon.name) {
+            " (default)"
+        } else {
+            ""
+        };
+        select.add_item(
+            format!("{}{marker}: {words}, {cache}", lexicon.name),
+            lexicon,
+        );
+    }
+
+    fn selected(s: &mut Cursive) -> Option<Rc<Lexicon>> {
+        s.find_name::<SelectView<Lexicon>>("select-lexicon")
+            .and_then(|view| view.selection())
+    }
+    let (built, deleted, picked) = (lexicons.clone(), lexicons.clone(), lexicons);
+    s.add_layer(
+        Dialog::around(select.with_name("select-lexicon").scrollable())
+            .title("Lexicons")
+            .button("Build", move |s| {
+                if let Some(lexicon) = selected(s) {
+                    build_lexicon(s, (*lexicon).clone(), built.clone());
+                }
+            })
+            .button("Delete cache", move |s| {
+                let Some(lexicon) = selected(s) else {
+                    return;
+                };
+                let deleted = deleted.clone();
+                match lexicon.delete_cache() {
+                    Ok(()) => {
+                        s.pop_layer();
+                        show_lexicons(s, deleted);
+                    }
+                    Err(e) => s.add_layer(Dialog::info(e.to_string()).title("Failed to delete")),
+                }
+            })
+            .button("Set default", move |s| {
+                let Some(lexicon) = selected(s) else {
+                    return;
+                };
+                match lexicon::set_default(&lexicon) {
+                    Ok(()) => {
+                        s.pop_layer();
+                        show_lexicons(s, picked.clone());
+                        s.add_layer(Dialog::info(format!(
+                            "Games will be played with {} from the next start.",
+                            lexicon.name
+                        )));
+                    }
+                    Err(e) => s.add_layer(Dialog::info(e.to_string()).title("Failed to set")),
+                }
+            })
+            .dismiss_button("Back"),
+    );
+}
+
+// Builds the dictionary of `lexicon` in the background behind a progress bar, and lists
+// `lexicons` anew once it's done.
+fn build_lexicon(s: &mut Cursive, lexicon: Lexicon, lexicons: Vec<Lexicon>) {
+    let words = match lexicon.words() {
+        Ok((words, _)) => words,
+        Err(e) => return s.add_layer(Dialog::info(e.to_string()).title("Failed to build")),
+    };
+    let counter = Counter::new(0);
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let cancel = cancelled.clone();
+    s.add_layer(
+        Dialog::around(
+            ProgressBar::new()
+                .range(0, words)
+                .with_value(counter.clone()),
+        )
+        .title(format!("Building {}", lexicon.name))
+        // The dialog stays until the build notices, which is within a thousand words.
+        .button("Cancel", move |_| cancel.store(true, Ordering::Relaxed)),
+    );
+    let sink = s.cb_sink().clone();
+    thread::spawn(move || {
+        let built = lexicon.build(|done| {
+            counter.set(done);
+            if cancelled.load(Ordering::Relaxed) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        let _ = sink.send(Box::new(move |s| {
+            s.pop_layer();
+            let dict = match built {
+                Ok(dict) => dict,
+                Err(ScrabbleError::Dictionary(BuildError::Cancelled)) => return,
+                Err(e) => return s.add_layer(Dialog::info(e.to_string()).title("Failed to build")),
+            };
+            s.pop_layer();
+            show_lexicons(s, lexicons);
+            s.add_layer(
+                Dialog::info(format!("{}\n\n{}", lexicon.raw.display(), dict.stats()))
+                    .title(format!("Built {}", lexicon.name)),
+            );
+        }));
+    });
+}
+
+// A lexicon is either a processed dictionary or a word list to build one from.
+fn load_lexicon(path: &Path) -> Result<Gaddag> {
+    let bytes = fs::read(path)?;
+    match Gaddag::from_bytes(bytes.clone()) {
+        Ok(lexicon) => Ok(lexicon),
+        Err(_) => Ok(Gaddag::from_reader(bytes.as_slice())?),
+    }
+}
+
+fn load_leaves(path: &Path) -> Result<Leaves> {
+    Ok(Leaves::from_reader(BufReader::new(File::open(path)?))?)
+}
+
 fn help(siv: &mut Cursive) {
     siv.add_layer(Dialog::info(include_str!("../help_msg.txt")).title("Welcome to Scrabbler!"));
 }
 
-fn new_game(siv: &mut Cursive, dict: Gaddag, player_profiles: &[PlayerProfile]) {
+// What every game started from the setup screen is built from.
+#[derive(Clone)]
+struct Setup {
+    dict: Gaddag,
+    leaves: Leaves,
+    archive: Option<Archive>,
+    event_log: Option<Box<Path>>,
+    house_rules: Option<Arc<HouseRules>>,
+    house_words: HashSet<String>,
+    definitions: Arc<HashMap<String, String>>,
+    layout: Option<Box<Path>>,
+    distribution: Distribution,
+    options: Options,
+    display: DisplayOptions,
+}
+
+impl Setup {
+    // A game for the players listed on the setup screen, with the clock and indicator
+    // picked there.
+    fn game(&self, s: &mut Cursive) -> Option<Game> {
+        let profiles = s
+            .call_on_name("select-players", |view: &mut SelectView<PlayerProfile>| {
+                view.iter()
+                    .map(|(_, profile)| profile.clone())
+                    .collect::<Vec<PlayerProfile>>()
+            })
+            .filter(|profiles| !profiles.is_empty())?;
+        let mut options = self.options;
+        if let Some(preset) = s
+            .find_name::<SelectView<Preset>>("select-clock")
+            .and_then(|view| view.selection())
+        {
+            options.clock.preset = *preset;
+        }
+        if let Some(size) = s
+            .find_name::<SelectView<usize>>("select-board-size")
+            .and_then(|view| view.selection())
+        {
+            options.board_size = Some(*size);
+        }
+        if let Some(checkbox) = s.find_name::<Checkbox>("bingo-indicator") {
+            options.bingo_indicator = checkbox.is_checked();
+        }
+        self.game_for(s, &profiles, options)
+    }
+
+    // A game for `profiles`, with their lexicons loaded.
+    fn game_for(
+        &self,
+        s: &mut Cursive,
+        profiles: &[PlayerProfile],
+        options: Options,
+    ) -> Option<Game> {
+        let mut builder = Game::builder(self.dict.clone())
+            .with_seats(profiles.iter().cloned())
+            .with_distribution(self.distribution.clone())
+            .with_options(options)
+            .with_display(self.display.clone());
+        if let Some(path) = &self.layout {
+            match fs::read_to_string(path) {
+                Ok(layout) => builder = builder.with_layout(&layout),
+                Err(e) => {
+                    s.add_layer(Dialog::info(e.to_string()).title("Failed to lay out the board"));
+                    return None;
+                }
+            }
+        }
+        let mut game = match builder.build() {
+            Ok(game) => game,
+            Err(e) => {
+                s.add_layer(Dialog::info(e.to_string()).title("Failed to set up the game"));
+                return None;
+            }
+        }
+        .with_leaves(self.leaves.clone())
+        .with_house_words(self.house_words.clone())
+        .with_definitions(self.definitions.clone());
+        if let Some(archive) = &self.archive {
+            game = game.with_archive(archive.clone());
+        }
+        if let Some(rules) = &self.house_rules {
+            game = game.with_house_rules(rules.clone());
+        }
+        if let Some(path) = &self.event_log {
+            match OpenOptions::new().create(true).append(true).open(path) {
+                Ok(file) => game = game.subscribe(EventLog::new(file)),
+                Err(e) => {
+                    s.add_layer(Dialog::info(e.to_string()).title("Failed to open the event log"));
+                    return None;
+                }
+            }
+        }
+        for (i, profile) in profiles.iter().enumerate() {
+            let Some(path) = &profile.lexicon else {
+                continue;
+            };
+            match load_lexicon(path) {
+                Ok(lexicon) => game = game.with_lexicon(i, lexicon),
+                Err(e) => {
+                    s.add_layer(
+                        Dialog::info(e.to_string())
+                            .title(format!("Failed to load the lexicon of {}", profile.name)),
+                    );
+                    return None;
+                }
+            }
+        }
+        Some(game)
+    }
+}
+
+fn new_game(siv: &mut Cursive, setup: Setup, player_profiles: &[PlayerProfile]) {
+    let mut clock = SelectView::<Preset>::new()
+        .popup()
+        .with_all(Preset::ALL.map(|preset| (preset.name(), preset)));
+    let default = Preset::ALL
+        .iter()
+        .position(|&preset| preset == setup.options.clock.preset);
+    clock.set_selection(default.unwrap_or(0));
+    let mut size = SelectView::<usize>::new()
+        .popup()
+        .with_all(BOARD_SIZES.map(|size| (format!("{size}x{size} board"), size)));
+    let picked = setup.options.board_size.unwrap_or(BOARD_SIZE);
+    size.set_selection(
+        BOARD_SIZES
+            .iter()
+            .position(|&size| size == picked)
+            .unwrap_or(1),
+    );
+    let bingo = LinearLayout::horizontal()
+        .child(
+            Checkbox::new()
+                .with_checked(setup.options.bingo_indicator)
+                .with_name("bingo-indicator"),
+        )
+        .child(TextView::new(" Light up when a bingo can be played"));
+    let resume_setup = setup.clone();
     let buttons = LinearLayout::vertical()
         .child(Button::new("Start game", move |s| {
-            if let Some(player_names) =
-                &s.call_on_name("select-players", |view: &mut SelectView<String>| {
-                    view.iter()
-                        .map(|(_, content)| content.clone())
-                        .collect::<Vec<String>>()
-                })
-            {
-                if !player_names.is_empty() {
-                    start_game(s, Game::new(dict.clone(), player_names));
-                }
+            if let Some(game) = setup.game(s) {
+                start_game(s, game);
             }
         }))
+        .child(Button::new("Resume game", move |s| {
+            resume_game(s, resume_setup.clone())
+        }))
         .child(DummyView)
         .child(Button::new("New player", add_player))
+        .child(Button::new("New bot", add_bot))
         .child(Button::new("Delete", delete_player))
         .child(DummyView)
         .child(Button::new("Back", |s| {
             s.pop_layer();
         }));
-    let select = SelectView::<String>::new()
-        .with_all_str(player_profiles.iter().map(|p| p.name.clone()))
+    let select = SelectView::<PlayerProfile>::new()
+        .with_all(
+            player_profiles
+                .iter()
+                .map(|profile| (label(profile), profile.clone())),
+        )
         .with_name("select-players")
         .fixed_size((10, 5));
 
     siv.add_layer(
         Dialog::around(
-            LinearLayout::horizontal()
-                .child(buttons)
+            LinearLayout::vertical()
+                .child(
+                    LinearLayout::horizontal()
+                        .child(buttons)
+                        .child(DummyView)
+                        .child(select),
+                )
                 .child(DummyView)
-                .child(select),
+                .child(clock.with_name("select-clock"))
+                .child(size.with_name("select-board-size"))
+                .child(bingo),
         )
         .title("Select players"),
     );
 }
 
+// Continues a game recorded in a GCG file, with the players listed taking its seats.
+fn resume_game(s: &mut Cursive, setup: Setup) {
+    s.add_layer(
+        Dialog::around(EditView::new().on_submit(move |s, path| {
+            s.pop_layer();
+            let Some(game) = setup.game(s) else {
+                return;
+            };
+            let resumed = fs::read_to_string(path)
+                .map_err(ScrabbleError::from)
+                .and_then(|content| game.resume(gcg::parse(&content)?));
+            match resumed {
+                Ok(game) => start_game(s, game),
+                Err(e) => s.add_layer(Dialog::info(e.to_string()).title("Failed to resume")),
+            }
+        }))
+        .title("Resume game from GCG file"),
+    );
+}
+
+// Lists the scenarios of the file at `path`, any of which starts a game for `players` in
+// its position once its goal has been read.
+fn show_scenarios(s: &mut Cursive, path: &Path, setup: &Setup, players: &[PlayerProfile]) {
+    let scenarios = match scenario::load(path) {
+        Ok(scenarios) if scenarios.is_empty() => {
+            return s.add_layer(Dialog::info("The file has no scenarios.").title("Scenarios"))
+        }
+        Ok(scenarios) => scenarios,
+        Err(e) => {
+            return s.add_layer(Dialog::info(e.to_string()).title("Failed to load scenarios"))
+        }
+    };
+    let (setup, players) = (setup.clone(), players.to_vec());
+    let select = SelectView::<Scenario>::new()
+        .with_all(
+            scenarios
+                .into_iter()
+                .map(|scenario| (scenario.name.clone(), scenario)),
+        )
+        .on_submit(move |s, scenario: &Scenario| {
+            let Some(game) = setup.game_for(s, &players, setup.options) else {
+                return;
+            };
+            match game.with_scenario(scenario) {
+                Ok(game) => {
+                    start_game(s, game);
+                    s.add_layer(Dialog::info(scenario.goal.clone()).title(scenario.name.clone()));
+                }
+                Err(e) => s.add_layer(Dialog::info(e.to_string()).title("Failed to set up")),
+            }
+        });
+    s.add_layer(
+        Dialog::around(select.scrollable())
+            .title("Scenarios")
+            .dismiss_button("Back"),
+    );
+}
+
+// Lists the finished games kept in `archive`, the latest first, to be searched and
+// replayed.
+fn show_history(s: &mut Cursive, archive: &Archive, dict: &Gaddag, blank: &Board) {
+    let games = match archive.games() {
+        Ok(games) => games,
+        Err(e) => return s.add_layer(Dialog::info(e.to_string()).title("Failed to read history")),
+    };
+    if games.is_empty() {
+        return s.add_layer(Dialog::info("No games have been finished yet.").title("History"));
+    }
+    let (replayed, dict, blank) = (archive.clone(), dict.clone(), blank.clone());
+    let mut select = SelectView::<i64>::new().on_submit(move |s, &id| {
+        let replay = replayed
+            .record(id)
+            .and_then(|record| gcg::parse(&record))
+            .map(|record| Replay::new(dict.clone(), record, blank.clone()));
+        match replay {
+            Ok(replay) => s.add_layer(Dialog::around(replay).title("Replay").button("Back", |s| {
+                s.pop_layer();
+            })),
+            Err(e) => s.add_layer(Dialog::info(e.to_string()).title("Failed to open game")),
+        }
+    });
+    list_games(&mut select, &games);
+    let (searched, exported, printed) = (archive.clone(), archive.clone(), archive.clone());
+    let search = EditView::new().on_submit(move |s, text| {
+        let games =
+            match Query::parse(text) {
+                Some(query) => searched.search(&query),
+                None if text.trim().is_empty() => searched.games(),
+                None => return s.add_layer(Dialog::info(
+                    "Search for a word, e.g. QUIXOTIC, or a score, e.g. over 400 or Jane over 400.",
+                )),
+            };
+        match games {
+            Ok(games) => {
+                s.call_on_name("history-games", |select: &mut SelectView<i64>| {
+                    list_games(select, &games)
+                });
+            }
+            Err(e) => s.add_layer(Dialog::info(e.to_string()).title("Failed to search history")),
+        }
+    });
+    s.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(search)
+                .child(DummyView)
+                .child(
+                    select
+                        .with_name("history-games")
+                        .scrollable()
+                        .max_height(20),
+                ),
+        )
+        .title("History")
+        .button("Export CSV", move |s| export_history(s, &exported))
+        .button("Export for printing", move |s| print_history(s, &printed))
+        .button("Back", |s| {
+            s.pop_layer();
+        }),
+    );
+}
+
+// Writes the turns of the games listed on the history screen to a CSV file.
+fn export_history(s: &mut Cursive, archive: &Archive) {
+    const PATH: &str = "history.csv";
+    let written = listed_games(s, archive).and_then(|games| {
+        Ok(csv::write_games(
+            &games,
+            io::BufWriter::new(File::create(PATH)?),
+        )?)
+    });
+    s.add_layer(Dialog::info(match written {
+        Ok(()) => format!("Wrote the games listed to {PATH}."),
+        Err(e) => format!("Failed to write {PATH}: {e}"),
+    }));
+}
+
+// Writes the score sheets of the games listed on the history screen to a Markdown file.
+fn print_history(s: &mut Cursive, archive: &Archive) {
+    const PATH: &str = "history.md";
+    let written = listed_games(s, archive).and_then(|games| {
+        Ok(printout::write_games(
+            &games,
+            io::BufWriter::new(File::create(PATH)?),
+        )?)
+    });
+    s.add_layer(Dialog::info(match written {
+        Ok(()) => format!("Wrote the score sheets of the games listed to {PATH}."),
+        Err(e) => format!("Failed to write {PATH}: {e}"),
+    }));
+}
+
+// The games listed on the history screen, with their records.
+fn listed_games(s: &mut Cursive, archive: &Archive) -> Result<Vec<(Entry, gcg::Record)>> {
+    let listed = s
+        .call_on_name("history-games", |select: &mut SelectView<i64>| {
+            select.iter().map(|(_, &id)| id).collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    archive.records().and_then(|records| {
+        records
+            .into_iter()
+            .filter(|(entry, _)| listed.contains(&entry.id))
+            .map(|(entry, record)| Ok((entry, gcg::parse(&record)?)))
+            .collect()
+    })
+}
+
+fn list_games(select: &mut SelectView<i64>, games: &[Entry]) {
+    select.clear();
+    select.add_all(games.iter().map(|game| {
+        (
+            format!(
+                "{}  {} ({})  {}",
+                game.played, game.players, game.scores, game.lexicon
+            ),
+            game.id,
+        )
+    }));
+}
+
 fn add_player(s: &mut Cursive) {
     fn ok(s: &mut Cursive, name: &str) {
-        s.call_on_name("select-players", |view: &mut SelectView<String>| {
-            view.add_item_str(name)
+        let profile = PlayerProfile::human(name);
+        s.call_on_name("select-players", |view: &mut SelectView<PlayerProfile>| {
+            view.add_item(label(&profile), profile)
         });
         s.pop_layer();
     }
@@ -138,18 +869,38 @@ fn add_player(s: &mut Cursive) {
     );
 }
 
+fn add_bot(s: &mut Cursive) {
+    s.call_on_name("select-players", |view: &mut SelectView<PlayerProfile>| {
+        let bots = view.iter().filter(|(_, profile)| profile.bot).count();
+        let profile = PlayerProfile::bot(&format!("Bot {}", bots + 1), Effort::default());
+        view.add_item(label(&profile), profile)
+    });
+}
+
+fn label(profile: &PlayerProfile) -> String {
+    if profile.bot {
+        format!("{} (bot)", profile.name)
+    } else {
+        profile.name.clone()
+    }
+}
+
 fn delete_player(s: &mut Cursive) {
-    let mut select = s.find_name::<SelectView<String>>("select-players").unwrap();
+    let mut select = s
+        .find_name::<SelectView<PlayerProfile>>("select-players")
+        .unwrap();
     if let Some(focus) = select.selected_id() {
         select.remove_item(focus);
     }
 }
 
 fn start_game(siv: &mut Cursive, game: Game) {
+    // Bots play their moves out on refreshes
+    siv.set_autorefresh(true);
     siv.add_layer(
         Dialog::new()
             .title("SCRABBLER")
-            .content(LinearLayout::horizontal().child(Panel::new(game)))
+            .content(LinearLayout::horizontal().child(Panel::new(game.with_name("game"))))
             .button("New game", |s| {
                 s.pop_layer();
             })
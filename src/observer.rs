@@ -0,0 +1,165 @@
+use std::{fmt, io::Write};
+
+/// What happened in a game, as told to its subscribers. Players are named rather than
+/// indexed, so the events make sense away from the game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GameEvent {
+    TurnStarted {
+        player: String,
+        turn: usize,
+    },
+    /// `play` is in GCG notation, position then word.
+    MovePlayed {
+        player: String,
+        play: String,
+        score: isize,
+    },
+    TilesExchanged {
+        player: String,
+        count: usize,
+    },
+    Passed {
+        player: String,
+    },
+    /// A play taken back after a successful challenge, `score` being the points lost.
+    PlayWithdrawn {
+        player: String,
+        score: isize,
+    },
+    /// Turns taken back, back to one of `player`'s.
+    TurnsTakenBack {
+        player: String,
+        turns: usize,
+    },
+    /// Final scores, in seat order.
+    GameEnded {
+        scores: Vec<(String, isize)>,
+    },
+}
+
+impl fmt::Display for GameEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameEvent::TurnStarted { player, turn } => write!(f, "turn {turn}: {player}"),
+            GameEvent::MovePlayed {
+                player,
+                play,
+                score,
+            } => write!(f, "{player} played {play} for {score}"),
+            GameEvent::TilesExchanged { player, count } => {
+                write!(f, "{player} exchanged {count} tiles")
+            }
+            GameEvent::Passed { player } => write!(f, "{player} passed"),
+            GameEvent::PlayWithdrawn { player, score } => {
+                write!(f, "{player} withdrew a play worth {score}")
+            }
+            GameEvent::TurnsTakenBack { player, turns } => {
+                write!(f, "{turns} turns taken back to {player}'s")
+            }
+            GameEvent::GameEnded { scores } => write!(
+                f,
+                "game over: {}",
+                scores
+                    .iter()
+                    .map(|(player, score)| format!("{player} {score}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Anything following a game: the display, a log, a connection to other players or a
+/// statistics collector. Subscribers are told about events in the order they happen.
+pub trait Subscriber: Send + Sync {
+    fn notify(&mut self, event: &GameEvent);
+}
+
+impl<F: FnMut(&GameEvent) + Send + Sync> Subscriber for F {
+    fn notify(&mut self, event: &GameEvent) {
+        self(event)
+    }
+}
+
+/// Writes every event on a line of its own.
+pub struct EventLog<W> {
+    out: W,
+}
+
+impl<W: Write> EventLog<W> {
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+}
+
+impl<W: Write + Send + Sync> Subscriber for EventLog<W> {
+    fn notify(&mut self, event: &GameEvent) {
+        // A log that can't be written to shouldn't stop the game.
+        let _ = writeln!(self.out, "{event}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    fn events() -> Vec<GameEvent> {
+        let player = || "Ann".to_string();
+        vec![
+            GameEvent::TurnStarted {
+                player: player(),
+                turn: 1,
+            },
+            GameEvent::MovePlayed {
+                player: player(),
+                play: "8D NASTIER".to_string(),
+                score: 64,
+            },
+            GameEvent::TilesExchanged {
+                player: player(),
+                count: 3,
+            },
+            GameEvent::Passed { player: player() },
+            GameEvent::PlayWithdrawn {
+                player: player(),
+                score: 64,
+            },
+            GameEvent::TurnsTakenBack {
+                player: player(),
+                turns: 2,
+            },
+            GameEvent::GameEnded {
+                scores: vec![(player(), 410), ("Bo".to_string(), -3)],
+            },
+        ]
+    }
+
+    #[test]
+    fn logs_have_a_line_per_event() {
+        let mut log = EventLog::new(Vec::new());
+        events().iter().for_each(|event| log.notify(event));
+        assert_eq!(
+            String::from_utf8(log.out).unwrap(),
+            "turn 1: Ann\n\
+             Ann played 8D NASTIER for 64\n\
+             Ann exchanged 3 tiles\n\
+             Ann passed\n\
+             Ann withdrew a play worth 64\n\
+             2 turns taken back to Ann's\n\
+             game over: Ann 410, Bo -3\n"
+        );
+    }
+
+    #[test]
+    fn closures_subscribe() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut subscriber: Box<dyn Subscriber> = Box::new({
+            let seen = seen.clone();
+            move |event: &GameEvent| seen.lock().unwrap().push(event.clone())
+        });
+        events().iter().for_each(|event| subscriber.notify(event));
+        assert_eq!(*seen.lock().unwrap(), events());
+    }
+}
@@ -0,0 +1,243 @@
+use cursive::{theme::Effect, Printer, Vec2, View};
+
+use crate::{board, tile::Tile};
+
+// Columns a letter of the rack takes, with its score and the bar after it.
+const SLOT: usize = 6;
+// Columns before the first letter of the rack.
+const MARGIN: usize = 2;
+const BINGO: &str = "BINGO";
+
+/// Whose turn it is, their letters and the ones they've put on the board, under a rule
+/// across the width it's given.
+pub struct RackView {
+    turn: String,
+    // Hidden for bots.
+    letters: Option<Vec<Tile>>,
+    tentative: Vec<Tile>,
+    // Whether the letters placed so far can start a word, see `Game::prefix_feedback`.
+    feedback: Option<(String, bool)>,
+    // Lit when the rack can play a bingo, see `Options::bingo_indicator`.
+    bingo: bool,
+}
+
+impl RackView {
+    pub fn new(turn: String, letters: Option<Vec<Tile>>, tentative: Vec<Tile>) -> Self {
+        Self {
+            turn,
+            letters,
+            tentative,
+            feedback: None,
+            bingo: false,
+        }
+    }
+
+    pub fn with_feedback(mut self, feedback: Option<(String, bool)>) -> Self {
+        self.feedback = feedback;
+        self
+    }
+
+    pub fn with_bingo(mut self, bingo: bool) -> Self {
+        self.bingo = bingo;
+        self
+    }
+
+    fn tile(tile: Tile) -> String {
+        format!("{}{}", board::glyph(tile.label()), tile.value)
+    }
+}
+
+impl View for RackView {
+    fn draw(&self, printer: &Printer) {
+        printer.print_hline((0, 0), printer.size.x, "—");
+        printer.print((0, 1), &self.turn);
+        if let Some((feedback, leads)) = &self.feedback {
+            let effect = if *leads { Effect::Dim } else { Effect::Bold };
+            printer.with_effect(effect, |printer| {
+                printer.print((printer.size.x / 2, 1), feedback)
+            });
+        }
+        if self.bingo {
+            let x = printer.size.x.saturating_sub(BINGO.len());
+            printer.with_effect(Effect::Reverse, |printer| printer.print((x, 1), BINGO));
+        }
+        let Some(letters) = &self.letters else {
+            return;
+        };
+
+        printer.print((0, 2), "|");
+        for (x, &tile) in letters.iter().enumerate() {
+            printer.print((SLOT * x + MARGIN, 2), &Self::tile(tile));
+            printer.print((SLOT * x + SLOT, 2), "|");
+        }
+        let placed = SLOT * letters.len() + MARGIN;
+        printer.print((placed, 2), "->");
+        printer.with_effect(Effect::Dim, |printer| {
+            for (x, &tile) in self.tentative.iter().enumerate() {
+                printer.print((placed + SLOT * x + 3, 2), &Self::tile(tile));
+                printer.print((placed + SLOT * x + 7, 2), "|");
+            }
+        });
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        let rack = match &self.letters {
+            Some(letters) => SLOT * (letters.len() + self.tentative.len()) + MARGIN + 4,
+            None => 0,
+        };
+        let turn = self.turn.chars().count() + if self.bingo { BINGO.len() + 1 } else { 0 };
+        let feedback = self
+            .feedback
+            .as_ref()
+            .map_or(0, |(feedback, _)| 2 * feedback.chars().count());
+        Vec2::new(rack.max(turn).max(feedback), 3)
+    }
+}
+
+/// The log, newest entry first, below the readout line in readout mode and a rule
+/// otherwise. Entries are wrapped to the width the log is given, and the oldest ones
+/// left out when they don't fit.
+pub struct LogView {
+    readout: Option<String>,
+    entries: Vec<String>,
+}
+
+impl LogView {
+    // Enough for the latest few moves, when there isn't room for more.
+    const MIN_LINES: usize = 6;
+
+    pub fn new(entries: Vec<String>, readout: Option<String>) -> Self {
+        Self { readout, entries }
+    }
+}
+
+impl View for LogView {
+    fn draw(&self, printer: &Printer) {
+        match &self.readout {
+            Some(readout) => printer.print((0, 0), readout),
+            None => printer.print_hline((0, 0), printer.size.x, "—"),
+        }
+        let width = printer.size.x.saturating_sub(2).max(1);
+        let mut y = 1;
+        for entry in self.entries.iter().rev() {
+            if y >= printer.size.y {
+                break;
+            }
+            printer.print((0, y), "-");
+            for line in entry.chars().collect::<Vec<char>>().chunks(width) {
+                printer.print((2, y), &line.iter().collect::<String>());
+                y += 1;
+            }
+        }
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        Vec2::new(0, 1 + Self::MIN_LINES)
+    }
+}
+
+/// A player's name, score and whatever else there is to know about them in the game.
+pub struct Standing {
+    pub name: String,
+    pub status: String,
+    pub current: bool,
+}
+
+/// The players in seat order, the current one underlined, and how close the game is to
+/// ending on scoreless turns.
+pub struct ScorePanel {
+    standings: Vec<Standing>,
+    scoreless: String,
+}
+
+impl ScorePanel {
+    pub fn new(standings: Vec<Standing>, scoreless: (usize, usize)) -> Self {
+        Self {
+            standings,
+            scoreless: format!("Scoreless: {}/{}", scoreless.0, scoreless.1),
+        }
+    }
+}
+
+impl View for ScorePanel {
+    fn draw(&self, printer: &Printer) {
+        for (i, standing) in self.standings.iter().enumerate() {
+            let effect = if standing.current {
+                Effect::Underline
+            } else {
+                Effect::Dim
+            };
+            printer.with_effect(effect, |printer| printer.print((0, i * 3), &standing.name));
+            printer.print((0, i * 3 + 1), &standing.status);
+            printer.print_hline((0, i * 3 + 2), 10, "-");
+        }
+        printer.print((0, self.standings.len() * 3), &self.scoreless);
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        let width = self
+            .standings
+            .iter()
+            .flat_map(|standing| [&standing.name, &standing.status])
+            .chain([&self.scoreless])
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        Vec2::new(width.max(10), self.standings.len() * 3 + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn racks_ask_for_their_letters_or_the_turn() {
+        let letters = vec![Tile::new('A', 1), Tile::BLANK];
+        let mut rack = RackView::new(
+            "Ann's turn".to_string(),
+            Some(letters),
+            vec![Tile::new('Q', 10)],
+        );
+        assert_eq!(
+            rack.required_size(Vec2::zero()),
+            Vec2::new(SLOT * 3 + MARGIN + 4, 3)
+        );
+
+        let mut hidden = RackView::new("Bot's turn".to_string(), None, Vec::new()).with_bingo(true);
+        assert_eq!(
+            hidden.required_size(Vec2::zero()),
+            Vec2::new(10 + BINGO.len() + 1, 3)
+        );
+        let feedback = "No word starts with QZ";
+        let mut hidden = hidden.with_feedback(Some((feedback.to_string(), false)));
+        assert_eq!(hidden.required_size(Vec2::zero()).x, 2 * feedback.len());
+        assert_eq!(
+            RackView::tile(Tile::new('Q', 10)),
+            format!("{}10", board::glyph('Q'))
+        );
+    }
+
+    #[test]
+    fn logs_and_scores_keep_room_for_what_they_show() {
+        let mut log = LogView::new(vec!["Ann played AT for 2.".to_string()], None);
+        assert_eq!(
+            log.required_size(Vec2::zero()),
+            Vec2::new(0, 1 + LogView::MIN_LINES)
+        );
+
+        let standing = |name: &str, status: &str| Standing {
+            name: name.to_string(),
+            status: status.to_string(),
+            current: false,
+        };
+        let mut scores = ScorePanel::new(
+            vec![standing("Ann", "12"), standing("Bo", "3, resigned")],
+            (1, 6),
+        );
+        assert_eq!(scores.scoreless, "Scoreless: 1/6");
+        assert_eq!(scores.required_size(Vec2::zero()), Vec2::new(14, 7));
+        let mut scores = ScorePanel::new(Vec::new(), (0, 6));
+        assert_eq!(scores.required_size(Vec2::zero()), Vec2::new(14, 1));
+    }
+}
@@ -0,0 +1,125 @@
+//! Racks weighted for practice, under `[rules.practice]`: draws of players who aren't
+//! bots are redrawn, a few times at most, until they suit the skill being worked on.
+
+use serde_derive::Deserialize;
+
+use crate::{
+    board::Board,
+    dictionary::Dictionary,
+    solver::{Openings, Solver},
+    tile::Tile,
+    wordlists::VOWELS,
+};
+
+/// Redraws before a rack is kept as it is, for rules the bag can't meet.
+pub const REDRAWS: usize = 20;
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+pub struct PracticeRacks {
+    /// A blank on every rack, while there are blanks to draw.
+    pub blank: bool,
+    /// A rack that can play a bingo somewhere on the board.
+    pub bingo: bool,
+    /// Vowels every rack holds at least.
+    pub vowels: usize,
+}
+
+impl PracticeRacks {
+    pub fn is_set(self) -> bool {
+        self.blank || self.bingo || self.vowels > 0
+    }
+
+    /// Whether `rack` suits the practice, `blanks_left` telling whether the bag it was
+    /// drawn from held a blank.
+    pub fn accepts(
+        self,
+        rack: &[Tile],
+        blanks_left: bool,
+        board: &Board,
+        dict: &impl Dictionary,
+        openings: &Openings,
+    ) -> bool {
+        if self.blank && blanks_left && !rack.iter().any(|tile| tile.is_blank) {
+            return false;
+        }
+        if rack
+            .iter()
+            .filter(|tile| VOWELS.contains(&tile.letter))
+            .count()
+            < self.vowels
+        {
+            return false;
+        }
+        // The solver is the slowest check, and left for last.
+        !self.bingo
+            || Solver::new(dict, board)
+                .with_openings(openings)
+                .has_bingo(rack)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bag::Distribution, gaddag::Gaddag};
+
+    fn rack(letters: &str) -> Vec<Tile> {
+        letters
+            .chars()
+            .map(|ch| Distribution::default().written(ch))
+            .collect()
+    }
+
+    fn accepts(practice: PracticeRacks, letters: &str, blanks_left: bool) -> bool {
+        let dict = Gaddag::from_words(["RETAINS", "TA"].map(String::from)).unwrap();
+        let board = Board::new(15);
+        practice.accepts(
+            &rack(letters),
+            blanks_left,
+            &board,
+            &dict,
+            &Openings::default(),
+        )
+    }
+
+    #[test]
+    fn any_rack_suits_no_practice() {
+        let practice = PracticeRacks::default();
+        assert!(!practice.is_set());
+        assert!(accepts(practice, "QQQQQQQ", true));
+    }
+
+    #[test]
+    fn blanks_are_wanted_while_there_are_any() {
+        let practice = PracticeRacks {
+            blank: true,
+            ..PracticeRacks::default()
+        };
+        assert!(practice.is_set());
+        assert!(!accepts(practice, "RETAINS", true));
+        assert!(accepts(practice, "RETAIN?", true));
+        assert!(accepts(practice, "RETAINS", false));
+    }
+
+    #[test]
+    fn racks_hold_enough_vowels() {
+        let vowels = |vowels| PracticeRacks {
+            vowels,
+            ..PracticeRacks::default()
+        };
+        assert!(accepts(vowels(3), "RETAINS", true));
+        assert!(!accepts(vowels(4), "RETAINS", true));
+    }
+
+    #[test]
+    fn bingo_racks_have_a_bingo_to_play() {
+        let practice = PracticeRacks {
+            bingo: true,
+            ..PracticeRacks::default()
+        };
+        assert!(accepts(practice, "STAINER", true));
+        assert!(accepts(practice, "RE?AINS", true));
+        assert!(!accepts(practice, "RETAINT", true));
+    }
+}
@@ -0,0 +1,217 @@
+//! Score sheets and tracking sheets as Markdown, for printing or keeping with a club's
+//! results. The tables are padded, so they read as well printed as plain text:
+//!
+//! ```text
+//! # Jane vs John
+//!
+//! | Turn | Player | Rack    | Move       | Score | Total |
+//! |-----:|--------|---------|------------|------:|------:|
+//! |    1 | Jane   | AEINRST | 8D NASTIER |    64 |    64 |
+//! ```
+
+use std::io::{self, Write};
+
+use crate::{
+    archive::Entry,
+    gcg::{self, Record, Turn},
+    tracking::TrackingSheet,
+};
+
+const HEADINGS: [&str; 6] = ["Turn", "Player", "Rack", "Move", "Score", "Total"];
+
+/// Writes the score sheet of a game between `players`, in seat order, followed by its
+/// tracking sheet if there's one.
+pub fn write(
+    players: &[&str],
+    turns: &[Turn],
+    tracking: Option<&TrackingSheet>,
+    mut out: impl Write,
+) -> io::Result<()> {
+    writeln!(out, "# {}\n", players.join(" vs "))?;
+    score_sheet(players, turns, &mut out)?;
+    if let Some(tracking) = tracking {
+        writeln!(out, "\n## Tracking sheet\n\n```text\n{tracking}\n```")?;
+    }
+    Ok(())
+}
+
+/// Writes the score sheets of every game in `games`, headed by the entry each was kept
+/// under.
+pub fn write_games(games: &[(Entry, Record)], mut out: impl Write) -> io::Result<()> {
+    for (i, (entry, record)) in games.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        writeln!(
+            out,
+            "# Game {}: {}\n\n{}, {}\n",
+            entry.id,
+            record.players.join(" vs "),
+            entry.played,
+            entry.lexicon
+        )?;
+        let players = record
+            .players
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        score_sheet(&players, &record.turns, &mut out)?;
+    }
+    Ok(())
+}
+
+// The table of turns, then each player's total.
+fn score_sheet(players: &[&str], turns: &[Turn], out: &mut impl Write) -> io::Result<()> {
+    let rows = turns
+        .iter()
+        .enumerate()
+        .map(|(i, turn)| {
+            [
+                (i + 1).to_string(),
+                players
+                    .get(turn.player)
+                    .copied()
+                    .unwrap_or_default()
+                    .to_string(),
+                gcg::tiles(&turn.rack),
+                turn.action.to_string(),
+                turn.score.to_string(),
+                turn.total.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let widths = rows.iter().fold(HEADINGS.map(str::len), |mut widths, row| {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+        widths
+    });
+    // The numbers are aligned right.
+    let numeric = |column: usize| matches!(column, 0 | 4 | 5);
+
+    writeln!(
+        out,
+        "{}",
+        table_row(&HEADINGS.map(String::from), &widths, numeric)
+    )?;
+    let rule = widths
+        .iter()
+        .enumerate()
+        .map(|(column, &width)| match numeric(column) {
+            true => format!("{}:", "-".repeat(width + 1)),
+            false => "-".repeat(width + 2),
+        })
+        .collect::<Vec<_>>();
+    writeln!(out, "|{}|", rule.join("|"))?;
+    for row in &rows {
+        writeln!(out, "{}", table_row(row, &widths, numeric))?;
+    }
+
+    let totals = (0..players.len())
+        .map(|player| {
+            let total = turns
+                .iter()
+                .rev()
+                .find(|turn| turn.player == player)
+                .map_or(0, |turn| turn.total);
+            format!("{} {total}", players[player])
+        })
+        .collect::<Vec<_>>();
+    writeln!(out, "\n{}", totals.join(", "))
+}
+
+fn table_row(cells: &[String; 6], widths: &[usize; 6], numeric: impl Fn(usize) -> bool) -> String {
+    let cells = cells
+        .iter()
+        .zip(widths)
+        .enumerate()
+        .map(|(column, (cell, &width))| match numeric(column) {
+            true => format!(" {cell:>width$} "),
+            false => format!(" {cell:<width$} "),
+        })
+        .collect::<Vec<_>>();
+    format!("|{}|", cells.join("|"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gcg::Action, tile::Tile};
+
+    fn turns() -> Vec<Turn> {
+        let turn = |player, rack: &str, action, score, total| Turn {
+            player,
+            rack: rack.chars().collect(),
+            action,
+            score,
+            total,
+            notes: Vec::new(),
+        };
+        vec![
+            turn(
+                0,
+                "AEINRST",
+                Action::Play {
+                    coordinate: "8D".to_string(),
+                    word: "NASTIER".to_string(),
+                },
+                64,
+                64,
+            ),
+            turn(1, "QU VWXZ", Action::Pass, 0, 0),
+        ]
+    }
+
+    #[test]
+    fn score_sheets_are_padded_tables() {
+        let mut out = Vec::new();
+        write(&["Jane", "John"], &turns(), None, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "# Jane vs John
+
+| Turn | Player | Rack    | Move       | Score | Total |
+|-----:|--------|---------|------------|------:|------:|
+|    1 | Jane   | AEINRST | 8D NASTIER |    64 |    64 |
+|    2 | John   | QU?VWXZ | -          |     0 |     0 |
+
+Jane 64, John 0
+"
+        );
+    }
+
+    #[test]
+    fn tracking_sheets_follow_the_score_sheet() {
+        let tiles = [Tile::new('A', 1), Tile::new('B', 3)];
+        let tracking = TrackingSheet::new(&tiles, &tiles[1..]);
+        let mut out = Vec::new();
+        write(&["Jane", "John"], &turns(), Some(&tracking), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with(&format!(
+            "\n## Tracking sheet\n\n```text\n{tracking}\n```\n"
+        )));
+    }
+
+    #[test]
+    fn games_are_headed_by_their_entry() {
+        let game = |id| {
+            let entry = Entry {
+                id,
+                played: "2024-01-02".to_string(),
+                players: String::new(),
+                scores: String::new(),
+                lexicon: "CSW21".to_string(),
+            };
+            let record = Record {
+                players: vec!["Jane".to_string(), "John".to_string()],
+                turns: turns(),
+            };
+            (entry, record)
+        };
+        let mut out = Vec::new();
+        write_games(&[game(1), game(2)], &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("# Game 1: Jane vs John\n\n2024-01-02, CSW21\n\n| Turn |"));
+        assert!(out.contains("Jane 64, John 0\n\n# Game 2: Jane vs John\n"));
+    }
+}
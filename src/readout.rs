@@ -0,0 +1,145 @@
+//! Plain text descriptions of the game for screen readers, one line each, so the
+//! game can be followed without seeing the colors and layout of the board.
+
+use cursive::Vec2;
+use itertools::Itertools;
+
+use crate::{
+    board::{Board, Multiplier},
+    tile::Tile,
+};
+
+/// The name of a square, column letter then row number: "H8".
+pub fn square_name(pos: &Vec2) -> String {
+    format!("{}{}", (b'A' + pos.x as u8) as char, pos.y + 1)
+}
+
+/// The square `name` stands for, as in `square_name`, or row first: "H8" or "8H".
+pub fn parse_square(name: &str) -> Option<Vec2> {
+    let name = name.trim().to_ascii_uppercase();
+    let column = name.chars().find(char::is_ascii_uppercase)?;
+    let row = name.replacen(column, "", 1).parse::<usize>().ok()?;
+    let x = (column as u8 - b'A') as usize;
+    Some(Vec2::new(x, row.checked_sub(1)?))
+}
+
+/// The square at `pos` and what's on it: "H8, double word, empty" or "H8, tentative A".
+pub fn square(board: &Board, pos: &Vec2) -> String {
+    let mut parts = vec![square_name(pos)];
+    if let Some(mult) = board.visible_mult_at(pos) {
+        parts.push(premium_name(mult).to_string());
+    }
+    parts.push(match board.letter_at(pos) {
+        Some(letter) if board.tentative().contains(pos) => format!("tentative {letter}"),
+        Some(letter) => letter.to_string(),
+        None => "empty".to_string(),
+    });
+    parts.join(", ")
+}
+
+/// The letters on a rack, blanks spelled out: "Rack: A, E, blank".
+pub fn rack(tiles: &[Tile]) -> String {
+    if tiles.is_empty() {
+        return "Rack: empty".to_string();
+    }
+    format!(
+        "Rack: {}",
+        tiles.iter().map(|&tile| spoken(tile)).join(", ")
+    )
+}
+
+/// The tiles of a placement and what it scored: "Jane played H8 C, I8 A, J8 T for 10 points."
+pub fn play(player: &str, tiles: &[(Vec2, Tile)], score: usize) -> String {
+    format!(
+        "{player} played {} for {score} points.",
+        tiles
+            .iter()
+            .sorted_by_key(|(pos, _)| (pos.y, pos.x))
+            .map(|&(pos, tile)| format!("{} {}", square_name(&pos), spoken(tile)))
+            .join(", ")
+    )
+}
+
+// A tile as read out: its letter, blanks spelled out with the letter they stand for.
+fn spoken(tile: Tile) -> String {
+    match (tile.is_blank, tile.letter) {
+        (true, ' ') => "blank".to_string(),
+        (true, letter) => format!("blank {letter}"),
+        (false, letter) => letter.to_string(),
+    }
+}
+
+/// The whole board row by row, listing the letters on every row: "Row 8: H8 C, I8 A".
+pub fn board(board: &Board) -> Vec<String> {
+    (0..board.size.y)
+        .map(|y| {
+            let letters = (0..board.size.x)
+                .map(|x| Vec2::new(x, y))
+                .filter_map(|pos| Some(format!("{} {}", square_name(&pos), board.letter_at(&pos)?)))
+                .collect::<Vec<_>>();
+            if letters.is_empty() {
+                format!("Row {}: empty", y + 1)
+            } else {
+                format!("Row {}: {}", y + 1, letters.join(", "))
+            }
+        })
+        .collect()
+}
+
+/// The name a premium is read out by: "double word".
+pub fn premium_name(mult: Multiplier) -> &'static str {
+    match mult {
+        Multiplier::Tw => "triple word",
+        Multiplier::Dw => "double word",
+        Multiplier::Tl => "triple letter",
+        Multiplier::Dl => "double letter",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gaddag::Gaddag;
+
+    #[test]
+    fn squares_are_named_column_first_and_read_either_way() {
+        assert_eq!(square_name(&Vec2::new(7, 7)), "H8");
+        assert_eq!(square_name(&Vec2::new(0, 14)), "A15");
+        for name in ["H8", "8H", " h8 "] {
+            assert_eq!(parse_square(name), Some(Vec2::new(7, 7)));
+        }
+        for name in ["", "H", "88", "H0", "HH8"] {
+            assert_eq!(parse_square(name), None, "{name}");
+        }
+    }
+
+    #[test]
+    fn squares_tell_their_premium_and_letter() {
+        let dict = Gaddag::from_words(["AT"].map(String::from)).unwrap();
+        let mut board = Board::new(15);
+        assert_eq!(square(&board, &Vec2::zero()), "A1, triple word, empty");
+        board.place_at(Tile::new('A', 1), &Vec2::new(7, 7));
+        assert_eq!(square(&board, &Vec2::new(7, 7)), "H8, tentative A");
+        board.commit_tentative(&dict);
+        assert_eq!(square(&board, &Vec2::new(7, 7)), "H8, A");
+        assert_eq!(
+            self::board(&board)[6..8],
+            ["Row 7: empty".to_string(), "Row 8: H8 A".to_string()]
+        );
+    }
+
+    #[test]
+    fn blanks_are_spelled_out() {
+        assert_eq!(rack(&[]), "Rack: empty");
+        assert_eq!(rack(&[Tile::new('A', 1), Tile::BLANK]), "Rack: A, blank");
+        let tiles = [
+            (Vec2::new(8, 7), Tile::BLANK.played_as('T')),
+            (Vec2::new(7, 7), Tile::new('A', 1)),
+        ];
+        assert_eq!(
+            play("Jane", &tiles, 2),
+            "Jane played H8 A, I8 blank T for 2 points."
+        );
+        assert_eq!(premium_name(Multiplier::Dl), "double letter");
+    }
+}
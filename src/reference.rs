@@ -0,0 +1,292 @@
+//! A slow move generator to check the solver against. It tries every word of the
+//! lexicon at every square in both directions and keeps the ones that fit, checking the
+//! words formed across and scoring them by walking the board, without the anchors,
+//! crosschecks and cross sums the solver relies on.
+//!
+//! `check` plays random games with the solver and compares the two generators at every
+//! turn, which `cargo run --release -- --check-solver` does from the command line.
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    io::{self, Write},
+};
+
+use cursive::Vec2;
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{
+    bag::Distribution,
+    board::{Alignment, Board, Multiplier},
+    dictionary::Dictionary,
+    gaddag::Gaddag,
+    game::{BOARD_SIZE, N_LETTERS},
+    solver::{Move, Solver},
+    tile::Tile,
+};
+
+const BINGO_BONUS: usize = 50;
+
+/// Every legal placement of letters from `rack`, found by trying each word in `words`
+/// everywhere, with the blanks standing in for any letter. Placements are told apart
+/// by their tiles alone, like the solver does.
+pub fn moves(dict: &Gaddag, words: &[String], board: &Board, rack: &[Tile]) -> Vec<Move> {
+    let on_board = (0..board.size.y)
+        .flat_map(|y| (0..board.size.x).map(move |x| Vec2::new(x, y)))
+        .filter_map(|pos| board.committed_letter_at(&pos))
+        .collect::<HashSet<_>>();
+    let mut moves = Vec::new();
+    let mut seen = HashSet::new();
+    let blank = rack.iter().any(|tile| tile.is_blank);
+    for word in words {
+        // Letters neither on the rack nor the board can't be played without a blank.
+        if !blank
+            && !word.chars().all(|letter| {
+                rack.iter().any(|tile| tile.letter == letter) || on_board.contains(&letter)
+            })
+        {
+            continue;
+        }
+        for y in 0..board.size.y {
+            for x in 0..board.size.x {
+                for alignment in [Alignment::Horizontal, Alignment::Vertical] {
+                    for play in fit(dict, board, rack, word, Vec2::new(x, y), alignment) {
+                        let tiles = play
+                            .tiles
+                            .iter()
+                            .map(|&(pos, tile)| (pos.x, pos.y, tile))
+                            .collect::<BTreeSet<_>>();
+                        if seen.insert(tiles) {
+                            moves.push(play);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    moves
+}
+
+// `word` played from `start` in every way the rack has its letters, blanks standing
+// in for the letters or not, that makes a legal play.
+fn fit(
+    dict: &Gaddag,
+    board: &Board,
+    rack: &[Tile],
+    word: &str,
+    start: Vec2,
+    alignment: Alignment,
+) -> Vec<Move> {
+    let vacant =
+        |pos: Option<Vec2>| pos.is_none_or(|pos| board.committed_letter_at(&pos).is_none());
+    if !vacant(board.offset(&start, alignment, -1))
+        || !vacant(board.offset(&start, alignment, word.chars().count() as isize))
+    {
+        return Vec::new();
+    }
+
+    let mut needed = Vec::new();
+    let mut squares = Vec::new();
+    for (i, letter) in word.chars().enumerate() {
+        let Some(pos) = board.offset(&start, alignment, i as isize) else {
+            return Vec::new();
+        };
+        match board.committed_letter_at(&pos) {
+            Some(on_board) if on_board != letter => return Vec::new(),
+            Some(_) => (),
+            None => needed.push((pos, letter)),
+        }
+        squares.push(pos);
+    }
+    if needed.is_empty() {
+        return Vec::new();
+    }
+    let mut plays = Vec::new();
+    for tiles in from_rack(rack.to_vec(), &needed) {
+        plays.extend(play(dict, board, word, start, alignment, &squares, tiles));
+    }
+    plays
+}
+
+// Every way of taking the letters of `needed` from `rack`, each either by its own tile
+// or by a blank.
+fn from_rack(rack: Vec<Tile>, needed: &[(Vec2, char)]) -> Vec<Vec<(Vec2, Tile)>> {
+    let Some((&(pos, letter), rest)) = needed.split_first() else {
+        return vec![Vec::new()];
+    };
+    let mut ways = Vec::new();
+    for blank in [false, true] {
+        let Some(idx) = rack
+            .iter()
+            .position(|tile| tile.is_blank == blank && (blank || tile.letter == letter))
+        else {
+            continue;
+        };
+        let mut left = rack.clone();
+        let tile = left.swap_remove(idx).played_as(letter);
+        for mut tiles in from_rack(left, rest) {
+            tiles.insert(0, (pos, tile));
+            ways.push(tiles);
+        }
+    }
+    ways
+}
+
+// The play of `tiles` over `squares`, if it's legal.
+fn play(
+    dict: &Gaddag,
+    board: &Board,
+    word: &str,
+    start: Vec2,
+    alignment: Alignment,
+    squares: &[Vec2],
+    tiles: Vec<(Vec2, Tile)>,
+) -> Option<Move> {
+    // Plays after the first go through or next to a tile on the board, the first one
+    // over the center.
+    let connected = if board.has_committed() {
+        squares.len() > tiles.len()
+            || tiles.iter().any(|(pos, _)| {
+                let (before, after) = board.adjacent_letters(pos, alignment.perpendicular());
+                !before.is_empty() || !after.is_empty()
+            })
+    } else {
+        squares.contains(&board.center_pos())
+    };
+    if !connected {
+        return None;
+    }
+
+    let mut score = word_score(board, squares, &tiles);
+    for &(pos, tile) in &tiles {
+        let (before, after) = board.adjacent_letters(&pos, alignment.perpendicular());
+        if before.is_empty() && after.is_empty() {
+            continue;
+        }
+        let across = format!("{before}{}{after}", tile.letter);
+        if !dict.accepts(&across) {
+            return None;
+        }
+        let first = before.chars().count() as isize;
+        let squares = (0..across.chars().count() as isize)
+            .map(|i| board.offset(&pos, alignment.perpendicular(), i - first))
+            .collect::<Option<Vec<_>>>()?;
+        score += word_score(board, &squares, &[(pos, tile)]);
+    }
+    if tiles.len() == N_LETTERS {
+        score += BINGO_BONUS;
+    }
+
+    Some(Move {
+        alignment,
+        score,
+        start,
+        tiles,
+        word: word.to_string(),
+    })
+}
+
+// The score of the word over `squares`, the tiles of `placed` filling the squares the
+// board leaves vacant, with the premiums under them counted.
+fn word_score(board: &Board, squares: &[Vec2], placed: &[(Vec2, Tile)]) -> usize {
+    let mut factor = 1;
+    let mut sum = 0;
+    for pos in squares {
+        let tile = placed.iter().find(|(p, _)| p == pos).map(|&(_, tile)| tile);
+        let value = tile
+            .or_else(|| board.committed_tile_at(pos))
+            .map_or(0, |tile| tile.value);
+        match board.mult_at(pos.x, pos.y).filter(|_| tile.is_some()) {
+            Some(mult @ (Multiplier::Dl | Multiplier::Tl)) => sum += value * mult.as_factor(),
+            Some(mult @ (Multiplier::Dw | Multiplier::Tw)) => {
+                sum += value;
+                factor *= mult.as_factor();
+            }
+            None => sum += value,
+        }
+    }
+    sum * factor
+}
+
+/// The placements only one of the generators finds for `rack`, or that they score
+/// differently, each as a line saying which.
+pub fn compare(dict: &Gaddag, words: &[String], board: &Board, rack: &[Tile]) -> Vec<String> {
+    let key = |play: &Move| {
+        let tiles = play
+            .tiles
+            .iter()
+            .copied()
+            .sorted_by_key(|(p, _)| (p.y, p.x));
+        (tiles.collect::<Vec<_>>(), play.score)
+    };
+    let solver = Solver::new(dict, board).moves(rack);
+    let reference = moves(dict, words, board, rack);
+    let found = solver.iter().map(key).collect::<HashSet<_>>();
+    let expected = reference.iter().map(key).collect::<HashSet<_>>();
+    let missing = reference
+        .iter()
+        .filter(|play| !found.contains(&key(play)))
+        .map(|play| format!("solver misses {} {}", play.notation(), play.score));
+    let extra = solver
+        .iter()
+        .filter(|play| !expected.contains(&key(play)))
+        .map(|play| format!("solver adds {} {}", play.notation(), play.score));
+    missing.chain(extra).collect()
+}
+
+/// Plays `games` random games with the solver's best moves, comparing the generators
+/// for the rack of every turn and writing out where they disagree. Returns the number
+/// of disagreements.
+pub fn check(dict: &Gaddag, games: usize, seed: u64, mut out: impl Write) -> io::Result<usize> {
+    let words = dict.iter_words().collect::<Vec<_>>();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut disagreements = 0;
+    for game in 1..=games {
+        let mut bag = Distribution::default().tiles();
+        bag.shuffle(&mut rng);
+        let mut board = Board::new(BOARD_SIZE);
+        let mut rack = Vec::new();
+        for turn in 1.. {
+            while rack.len() < N_LETTERS {
+                let Some(tile) = bag.pop() else { break };
+                rack.push(tile);
+            }
+            let rack_text = rack.iter().map(|tile| tile.label()).collect::<String>();
+            for line in compare(dict, &words, &board, &rack) {
+                writeln!(out, "game {game}, turn {turn}, rack {rack_text}: {line}")?;
+                disagreements += 1;
+            }
+            let Some(best) = Solver::new(dict, &board).moves(&rack).into_iter().next() else {
+                break;
+            };
+            for &(pos, tile) in &best.tiles {
+                board.place_at(tile, &pos);
+                let idx = rack.iter().position(|&t| t == tile.drawn()).unwrap();
+                rack.swap_remove(idx);
+            }
+            board.commit_tentative(dict);
+        }
+        writeln!(out, "game {game} checked")?;
+    }
+    Ok(disagreements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: &[&str] = &[
+        "AE", "AT", "EAT", "ETA", "NET", "TEN", "TAN", "ANT", "RAT", "TAR", "ART", "STAR", "RATS",
+        "TARS", "ARTS", "EAST", "SEAT", "TEAS", "EATS", "RATE", "TEAR", "STARE", "NEAR", "EARN",
+        "SANE", "LANE", "LATE", "TALE", "SALT", "LAST", "OAT", "TOE", "NOTE", "TONE", "STONE",
+        "ONSET", "IN", "IT", "TIN", "SIT", "TIE", "TIES",
+    ];
+
+    #[test]
+    fn solver_agrees_with_reference() {
+        let dict = Gaddag::from_words(WORDS.iter().map(|word| word.to_string())).unwrap();
+        let mut out = Vec::new();
+        let disagreements = check(&dict, 2, 7, &mut out).unwrap();
+        assert_eq!(disagreements, 0, "{}", String::from_utf8_lossy(&out));
+    }
+}
@@ -0,0 +1,207 @@
+//! A game from the history, stepped through a turn at a time on a board of its own.
+
+use cursive::{
+    event::{Event, EventResult, Key},
+    view::CannotFocus,
+    views::Dialog,
+    Printer, Vec2, View,
+};
+use itertools::Itertools;
+
+use crate::{
+    bag::Distribution,
+    board::{Board, Cell},
+    error::Result,
+    gaddag::Gaddag,
+    gcg::{self, Action, Record},
+};
+
+pub struct Replay {
+    board: Board,
+    // The empty board the turns are played out on.
+    blank: Board,
+    dict: Gaddag,
+    record: Record,
+    // Turns played out on the board, from none to all of them.
+    step: usize,
+}
+
+impl Replay {
+    /// Starts the replay of `record` on `blank`, an empty board.
+    pub fn new(dict: Gaddag, record: Record, blank: Board) -> Self {
+        Self {
+            board: blank.clone(),
+            blank,
+            dict,
+            record,
+            step: 0,
+        }
+    }
+
+    /// Plays the turns up to `step` out on a new board.
+    fn go_to(&mut self, step: usize) -> Result<()> {
+        let mut board = self.blank.clone();
+        // Only the letters are shown, whatever they're worth.
+        let distribution = Distribution::default();
+        gcg::replay(
+            &mut board,
+            &self.dict,
+            &distribution,
+            &self.record.turns[..step],
+        )?;
+        self.board = board;
+        self.step = step;
+        Ok(())
+    }
+
+    // The latest turn played out, with its notes.
+    fn describe(&self) -> Vec<String> {
+        let Some(turn) = self.step.checked_sub(1).map(|i| &self.record.turns[i]) else {
+            return vec![format!(
+                "Start of the game, {} turns.",
+                self.record.turns.len()
+            )];
+        };
+        let name = &self.record.players[turn.player];
+        let action = match &turn.action {
+            Action::Play { coordinate, word } => format!("played {word} at {coordinate}"),
+            Action::Exchange(letters) => format!("exchanged {}", letters.iter().join("")),
+            Action::Pass => "passed".to_string(),
+            Action::Withdrawn => "had their play withdrawn".to_string(),
+        };
+        let mut lines = vec![
+            format!("Turn {} of {}:", self.step, self.record.turns.len()),
+            format!(
+                "{name} {action} for {:+}, {} in all. Rack: {}",
+                turn.score,
+                turn.total,
+                turn.rack.iter().collect::<String>()
+            ),
+        ];
+        lines.extend(turn.notes.iter().map(|note| format!("Note: {note}")));
+        lines
+    }
+}
+
+impl View for Replay {
+    fn draw(&self, printer: &Printer) {
+        let board = self.board.size;
+        let lines = self.describe();
+        self.board.draw(printer);
+        printer.print_hline((0, board.y), board.x * Cell::size(), "—");
+        for (i, line) in lines.iter().enumerate() {
+            printer.print((0, board.y + 1 + i), line);
+        }
+        printer.print(
+            (0, board.y + 2 + lines.len()),
+            "←/h: previous turn, →/l: next turn, Home/End: first/last turn",
+        );
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        self.board
+            .size
+            .map_x(|x| x * Cell::size())
+            .map_y(|y| y + 4 + self.describe().len())
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let last = self.record.turns.len();
+        let step = match event {
+            Event::Key(Key::Left) | Event::Char('h') => self.step.saturating_sub(1),
+            Event::Key(Key::Right) | Event::Char('l') => (self.step + 1).min(last),
+            Event::Key(Key::Home) => 0,
+            Event::Key(Key::End) => last,
+            _ => return EventResult::Ignored,
+        };
+        if let Err(e) = self.go_to(step) {
+            let message = e.to_string();
+            return EventResult::with_cb(move |s| {
+                s.add_layer(Dialog::info(message.clone()).title("Failed to replay"))
+            });
+        }
+        EventResult::Consumed(None)
+    }
+
+    fn take_focus(
+        &mut self,
+        _: cursive::direction::Direction,
+    ) -> std::result::Result<EventResult, CannotFocus> {
+        Ok(EventResult::Consumed(None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gcg::Turn;
+
+    fn replay(coordinate: &str) -> Replay {
+        let dict = Gaddag::from_words(["AT", "TA"].map(String::from)).unwrap();
+        let turn = |player, action, score, total, notes: &[&str]| Turn {
+            player,
+            rack: "AT".chars().collect(),
+            action,
+            score,
+            total,
+            notes: notes.iter().map(|note| note.to_string()).collect(),
+        };
+        let record = Record {
+            players: vec!["Ann".to_string(), "Bo".to_string()],
+            turns: vec![
+                turn(
+                    0,
+                    Action::Play {
+                        coordinate: coordinate.to_string(),
+                        word: "AT".to_string(),
+                    },
+                    4,
+                    4,
+                    &["Too quiet"],
+                ),
+                turn(1, Action::Pass, 0, 0, &[]),
+            ],
+        };
+        Replay::new(dict, record, Board::new(15))
+    }
+
+    fn letter(replay: &Replay) -> Option<char> {
+        replay.board.committed_letter_at(&Vec2::new(7, 7))
+    }
+
+    #[test]
+    fn turns_are_stepped_through_with_the_keys() {
+        let mut replay = replay("8H");
+        assert_eq!(replay.describe(), ["Start of the game, 2 turns."]);
+
+        assert!(replay.on_event(Event::Char('l')).is_consumed());
+        assert_eq!(letter(&replay), Some('A'));
+        assert_eq!(
+            replay.describe(),
+            [
+                "Turn 1 of 2:",
+                "Ann played AT at 8H for +4, 4 in all. Rack: AT",
+                "Note: Too quiet",
+            ]
+        );
+
+        replay.on_event(Event::Key(Key::End));
+        replay.on_event(Event::Key(Key::Right));
+        assert_eq!(replay.step, 2);
+        assert_eq!(replay.describe()[1], "Bo passed for +0, 0 in all. Rack: AT");
+        replay.on_event(Event::Key(Key::Home));
+        replay.on_event(Event::Key(Key::Left));
+        assert_eq!((replay.step, letter(&replay)), (0, None));
+        assert!(!replay.on_event(Event::Char('x')).is_consumed());
+    }
+
+    #[test]
+    fn records_that_cant_be_played_out_are_reported() {
+        let mut replay = replay("8");
+        assert!(matches!(
+            replay.on_event(Event::Key(Key::End)),
+            EventResult::Consumed(Some(_))
+        ));
+        assert_eq!(replay.step, 0);
+    }
+}
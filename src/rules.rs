@@ -0,0 +1,213 @@
+//! The rules a placement has to follow before its words are looked up and scored.
+
+use std::{error::Error, fmt};
+
+use cursive::Vec2;
+use serde_derive::Deserialize;
+
+use crate::board::{Alignment, Board, Cell};
+
+/// The rules placements are held to beyond forming words, set in `[rules.placement]`.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct PlacementRules {
+    /// Letters the main word of the first placement needs at least.
+    pub min_first_word: usize,
+    /// The first placement has to cover the center square.
+    pub first_crosses_center: bool,
+    /// Placements of a single letter are allowed.
+    pub single_tiles: bool,
+}
+
+impl Default for PlacementRules {
+    fn default() -> Self {
+        Self {
+            min_first_word: 2,
+            first_crosses_center: true,
+            single_tiles: true,
+        }
+    }
+}
+
+/// Reasons the tentative letters on a board don't make a legal placement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementError {
+    NoLetters,
+    SquareOccupied,
+    NotAligned,
+    NotContiguous,
+    NotConnected,
+    MissesCenter,
+    FirstWordTooShort(usize),
+    SingleTile,
+}
+
+/// Checks the tentative letters on `board` and collects the cells of every word they
+/// form, the main word last. Premiums are only kept on the cells the tentative
+/// letters cover, so squares played on in earlier turns count at face value.
+pub fn validate(board: &Board, rules: &PlacementRules) -> Result<Vec<Vec<Cell>>, PlacementError> {
+    let Some(&first) = board.tentative().iter().next() else {
+        return Err(PlacementError::NoLetters);
+    };
+    if !rules.single_tiles && board.tentative().len() == 1 {
+        return Err(PlacementError::SingleTile);
+    }
+
+    // Only the first placement can do without the letters already on the board.
+    let opening = !board.has_committed();
+    if opening {
+        if rules.first_crosses_center && !board.tentative().contains(&board.center_pos()) {
+            return Err(PlacementError::MissesCenter);
+        }
+    } else if !board.is_connected() {
+        return Err(PlacementError::NotConnected);
+    }
+
+    let words = match board.tentative_alignment() {
+        Some(Alignment::Invalid) => return Err(PlacementError::NotAligned),
+        Some(alignment) => {
+            let main = board.run_through(&first, alignment);
+            if !board.tentative().iter().all(|pos| main.contains(pos)) {
+                return Err(PlacementError::NotContiguous);
+            }
+            let mut words = main
+                .iter()
+                .filter(|pos| board.tentative().contains(pos))
+                .map(|pos| board.run_through(pos, alignment.perpendicular()))
+                .filter(|cross| cross.len() > 1)
+                .collect::<Vec<_>>();
+            words.push(main);
+            words
+        }
+        // A single letter forms a word in each direction it has neighbors in.
+        None => {
+            let words = [Alignment::Horizontal, Alignment::Vertical]
+                .iter()
+                .map(|&alignment| board.run_through(&first, alignment))
+                .filter(|word| word.len() > 1)
+                .collect::<Vec<Vec<Vec2>>>();
+            if words.is_empty() {
+                vec![vec![first]]
+            } else {
+                words
+            }
+        }
+    };
+
+    let main = words.last().map_or(0, Vec::len);
+    if opening && main < rules.min_first_word {
+        return Err(PlacementError::FirstWordTooShort(rules.min_first_word));
+    }
+
+    Ok(words
+        .iter()
+        .map(|word| word.iter().map(|pos| board.scoring_cell(pos)).collect())
+        .collect())
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::NoLetters => "No letters placed.",
+            Self::SquareOccupied => "Cell occupied.",
+            Self::NotAligned => "Letters not aligned.",
+            Self::NotContiguous => "Letters must form a single word.",
+            Self::NotConnected => "Letters not connected to existing grid.",
+            Self::MissesCenter => "First placement must contain center square.",
+            Self::FirstWordTooShort(min) => {
+                return write!(f, "First word must have at least {min} letters.")
+            }
+            Self::SingleTile => "Placements of a single letter are not allowed.",
+        })
+    }
+}
+
+impl Error for PlacementError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gaddag::Gaddag, tile::Tile};
+
+    fn place(board: &mut Board, letters: &[(usize, usize, char)]) {
+        for &(x, y, letter) in letters {
+            board.place_at(Tile::new(letter, 1), &Vec2::new(x, y));
+        }
+    }
+
+    fn lengths(board: &Board, rules: &PlacementRules) -> Result<Vec<usize>, PlacementError> {
+        validate(board, rules).map(|words| words.iter().map(Vec::len).collect())
+    }
+
+    // A board with AT across the center.
+    fn opened() -> Board {
+        let dict = Gaddag::from_words(["AT"].map(String::from)).unwrap();
+        let mut board = Board::new(15);
+        place(&mut board, &[(7, 7, 'A'), (8, 7, 'T')]);
+        board.commit_tentative(&dict);
+        board
+    }
+
+    #[test]
+    fn first_placements_cover_the_center_with_a_word() {
+        let rules = PlacementRules::default();
+        let mut board = Board::new(15);
+        assert_eq!(lengths(&board, &rules), Err(PlacementError::NoLetters));
+        place(&mut board, &[(7, 7, 'A')]);
+        assert_eq!(
+            lengths(&board, &rules),
+            Err(PlacementError::FirstWordTooShort(2))
+        );
+        place(&mut board, &[(8, 7, 'T')]);
+        assert_eq!(lengths(&board, &rules), Ok(vec![2]));
+
+        let mut board = Board::new(15);
+        place(&mut board, &[(0, 0, 'A'), (1, 0, 'T')]);
+        assert_eq!(lengths(&board, &rules), Err(PlacementError::MissesCenter));
+        let anywhere = PlacementRules {
+            first_crosses_center: false,
+            ..rules
+        };
+        assert_eq!(lengths(&board, &anywhere), Ok(vec![2]));
+    }
+
+    #[test]
+    fn letters_form_a_single_connected_line() {
+        let rules = PlacementRules::default();
+        let mut board = opened();
+        place(&mut board, &[(6, 7, 'A'), (7, 8, 'T')]);
+        assert_eq!(lengths(&board, &rules), Err(PlacementError::NotAligned));
+
+        let mut board = opened();
+        place(&mut board, &[(6, 8, 'A'), (6, 9, 'T')]);
+        assert_eq!(lengths(&board, &rules), Err(PlacementError::NotConnected));
+
+        // Down the side of TAT, with a square left open between.
+        let dict = Gaddag::from_words(["AT", "TAT"].map(String::from)).unwrap();
+        let mut board = opened();
+        place(&mut board, &[(8, 8, 'A'), (8, 9, 'T')]);
+        board.commit_tentative(&dict);
+        place(&mut board, &[(9, 7, 'A'), (9, 9, 'A')]);
+        assert_eq!(lengths(&board, &rules), Err(PlacementError::NotContiguous));
+    }
+
+    #[test]
+    fn words_formed_across_come_before_the_main_word() {
+        let rules = PlacementRules::default();
+        let mut board = opened();
+        place(&mut board, &[(7, 8, 'T'), (8, 8, 'A')]);
+        assert_eq!(lengths(&board, &rules), Ok(vec![2, 2, 2]));
+
+        let mut board = opened();
+        place(&mut board, &[(9, 7, 'E')]);
+        assert_eq!(lengths(&board, &rules), Ok(vec![3]));
+        let no_singles = PlacementRules {
+            single_tiles: false,
+            ..rules
+        };
+        assert_eq!(
+            lengths(&board, &no_singles),
+            Err(PlacementError::SingleTile)
+        );
+    }
+}
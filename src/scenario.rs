@@ -0,0 +1,159 @@
+//! Practice scenarios: named positions with a rack to find a play for and a goal to
+//! read before starting, prepared in a TOML file of `[[scenario]]` tables.
+
+use std::{fs, path::Path};
+
+use cursive::Vec2;
+use serde_derive::Deserialize;
+
+use crate::error::{Result, ScrabbleError};
+
+#[derive(Clone, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    /// What to look for, shown when the scenario starts.
+    pub goal: String,
+    /// The tiles on the board, a line per row from the top: '.' for an empty square, a
+    /// letter for a tile and a lower case letter for a blank. Rows and columns left out
+    /// are empty.
+    #[serde(default)]
+    pub board: String,
+    /// The premium squares, for a board of a shape of its own, see `Board::from_layout`.
+    #[serde(default)]
+    pub layout: String,
+    /// The letters of the player to move, '?' for a blank.
+    pub rack: String,
+    /// Leaves nothing in the bag once the racks are dealt, for endgames.
+    #[serde(default)]
+    pub empty_bag: bool,
+}
+
+#[derive(Deserialize)]
+struct Scenarios {
+    #[serde(default, rename = "scenario")]
+    scenarios: Vec<Scenario>,
+}
+
+pub fn load(path: &Path) -> Result<Vec<Scenario>> {
+    let scenarios: Scenarios = toml::from_str(&fs::read_to_string(path)?)?;
+    Ok(scenarios.scenarios)
+}
+
+impl Scenario {
+    /// The tiles of the board diagram, upper case, with whether each is a blank.
+    pub fn tiles(&self, size: Vec2) -> Result<Vec<(Vec2, char, bool)>> {
+        let mut tiles = Vec::new();
+        for (y, row) in self.board.lines().map(str::trim).enumerate() {
+            for (x, square) in row.chars().enumerate() {
+                if square == '.' {
+                    continue;
+                }
+                if !square.is_alphabetic() {
+                    return Err(self.invalid(format!("'{square}' on row {} of the board", y + 1)));
+                }
+                if x >= size.x || y >= size.y {
+                    return Err(
+                        self.invalid(format!("the board is larger than {}x{}", size.x, size.y))
+                    );
+                }
+                tiles.push((
+                    Vec2::new(x, y),
+                    square.to_uppercase().next().unwrap_or(square),
+                    square.is_lowercase(),
+                ));
+            }
+        }
+        Ok(tiles)
+    }
+
+    /// The rack as the bag holds its tiles, a blank being a space.
+    pub fn rack(&self) -> Result<Vec<char>> {
+        self.rack
+            .trim()
+            .chars()
+            .map(|letter| match letter {
+                '?' => Ok(' '),
+                letter if letter.is_alphabetic() => {
+                    Ok(letter.to_uppercase().next().unwrap_or(letter))
+                }
+                letter => Err(self.invalid(format!("'{letter}' on the rack"))),
+            })
+            .collect()
+    }
+
+    pub fn invalid(&self, reason: String) -> ScrabbleError {
+        ScrabbleError::Scenario {
+            name: self.name.clone(),
+            reason,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const SCENARIOS: &str = r#"
+[[scenario]]
+name = "Through the Q"
+goal = "Find the best play through QI."
+board = """
+...
+.Qi
+"""
+rack = "aeinrs?"
+
+[[scenario]]
+name = "Endgame"
+goal = "Go out in two."
+rack = "AB"
+empty_bag = true
+"#;
+
+    // The scenarios above, loaded from a file of their own named after `test`.
+    fn scenarios(test: &str) -> Vec<Scenario> {
+        let file = format!("scrabbler-{test}-{}.toml", std::process::id());
+        let path = env::temp_dir().join(file);
+        fs::write(&path, SCENARIOS).unwrap();
+        let scenarios = load(&path);
+        fs::remove_file(&path).unwrap();
+        scenarios.unwrap()
+    }
+
+    #[test]
+    fn scenarios_are_read_from_their_tables() {
+        let scenarios = scenarios("read");
+        let names = scenarios.iter().map(|scenario| scenario.name.as_str());
+        assert_eq!(names.collect::<Vec<_>>(), ["Through the Q", "Endgame"]);
+        assert!(!scenarios[0].empty_bag && scenarios[1].empty_bag);
+        assert!(scenarios[1].tiles(Vec2::new(15, 15)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn boards_and_racks_are_read_in_capitals() {
+        let scenario = &scenarios("capitals")[0];
+        assert_eq!(
+            scenario.tiles(Vec2::new(15, 15)).unwrap(),
+            [(Vec2::new(1, 1), 'Q', false), (Vec2::new(2, 1), 'I', true)]
+        );
+        assert_eq!(
+            scenario.rack().unwrap(),
+            "AEINRS ".chars().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn diagrams_that_dont_fit_are_refused() {
+        let mut scenario = scenarios("refused").remove(0);
+        assert!(matches!(
+            scenario.tiles(Vec2::new(2, 2)),
+            Err(ScrabbleError::Scenario { name, .. }) if name == "Through the Q"
+        ));
+        scenario.board = "..\n.1".to_string();
+        assert!(scenario.tiles(Vec2::new(15, 15)).is_err());
+        scenario.rack = "AB-".to_string();
+        assert!(scenario.rack().is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use rhai::{Array, Dynamic, Engine, FuncArgs, Scope, AST};
+
+use crate::error::{Result, ScrabbleError};
+
+/// House rules written in rhai, for trying out variants without rebuilding. A script
+/// defines any of these functions, and the ones it leaves out don't change the game:
+///
+/// - `validate_placement(words, tiles)`, with the words a placement forms and the number
+///   of tiles placed, returns a reason to turn it down, or nothing to allow it.
+/// - `score_word(word, score)` returns what a word formed by a play scores instead of
+///   `score`, which includes its premiums.
+/// - `turn_end(player, score, turn)` returns points added to the score of the player
+///   whose turn just ended, or nothing.
+///
+/// Bots don't know about house rules, and choose their moves by the usual scores.
+pub struct HouseRules {
+    engine: Engine,
+    ast: AST,
+}
+
+impl HouseRules {
+    pub fn load(path: &Path) -> Result<Self> {
+        let engine = Engine::new();
+        let ast = engine
+            .compile_file(path.to_path_buf())
+            .map_err(|e| ScrabbleError::Script(e.to_string()))?;
+        Ok(Self { engine, ast })
+    }
+
+    pub fn validate_placement(&self, words: &[String], tiles: usize) -> Result<Option<String>> {
+        let words = words.iter().cloned().map(Dynamic::from).collect::<Array>();
+        let Some(verdict) = self.call("validate_placement", (words, tiles as i64))? else {
+            return Ok(None);
+        };
+        if verdict.is_unit() {
+            return Ok(None);
+        }
+        verdict
+            .into_string()
+            .map(Some)
+            .map_err(|kind| Self::mismatch("validate_placement", "a string", kind))
+    }
+
+    pub fn score_word(&self, word: &str, score: usize) -> Result<usize> {
+        let Some(scored) = self.call("score_word", (word.to_string(), score as i64))? else {
+            return Ok(score);
+        };
+        scored
+            .as_int()
+            .map(|scored| scored.max(0) as usize)
+            .map_err(|kind| Self::mismatch("score_word", "a number", kind))
+    }
+
+    pub fn turn_end(&self, player: &str, score: isize, turn: usize) -> Result<isize> {
+        let args = (player.to_string(), score as i64, turn as i64);
+        match self.call("turn_end", args)? {
+            Some(bonus) if !bonus.is_unit() => bonus
+                .as_int()
+                .map(|bonus| bonus as isize)
+                .map_err(|kind| Self::mismatch("turn_end", "a number", kind)),
+            _ => Ok(0),
+        }
+    }
+
+    // Calls the script's function `name`, if it has one.
+    fn call(&self, name: &str, args: impl FuncArgs) -> Result<Option<Dynamic>> {
+        if !self.ast.iter_functions().any(|f| f.name == name) {
+            return Ok(None);
+        }
+        self.engine
+            .call_fn::<Dynamic>(&mut Scope::new(), &self.ast, name, args)
+            .map(Some)
+            .map_err(|e| ScrabbleError::Script(format!("{name}: {e}")))
+    }
+
+    fn mismatch(name: &str, expected: &str, found: &str) -> ScrabbleError {
+        ScrabbleError::Script(format!("{name} should return {expected}, not {found}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    // The house rules of `source`, loaded from a file of their own named after `test`.
+    fn rules(test: &str, source: &str) -> Result<HouseRules> {
+        let path = env::temp_dir().join(format!("scrabbler-{test}-{}.rhai", std::process::id()));
+        fs::write(&path, source).unwrap();
+        let rules = HouseRules::load(&path);
+        fs::remove_file(&path).unwrap();
+        rules
+    }
+
+    const RULES: &str = r#"
+        fn validate_placement(words, tiles) {
+            if tiles == 1 { return "Play at least two tiles."; }
+            if words.contains("QAT") { "No QAT at home." }
+        }
+        fn score_word(word, score) { if word.len() > 5 { score * 2 } else { score - 10 } }
+        fn turn_end(player, score, turn) { if player == "Ann" { turn } }
+    "#;
+
+    #[test]
+    fn scripts_change_what_they_define() {
+        let rules = rules("defined", RULES).unwrap();
+        let words = |words: &[&str]| {
+            words
+                .iter()
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            rules
+                .validate_placement(&words(&["AT"]), 1)
+                .unwrap()
+                .as_deref(),
+            Some("Play at least two tiles.")
+        );
+        assert_eq!(
+            rules
+                .validate_placement(&words(&["QAT", "AT"]), 2)
+                .unwrap()
+                .as_deref(),
+            Some("No QAT at home.")
+        );
+        assert_eq!(rules.validate_placement(&words(&["AT"]), 2).unwrap(), None);
+        assert_eq!(rules.score_word("NASTIER", 9).unwrap(), 18);
+        assert_eq!(rules.score_word("AT", 2).unwrap(), 0);
+        assert_eq!(rules.turn_end("Ann", 10, 4).unwrap(), 4);
+        assert_eq!(rules.turn_end("Bo", 10, 4).unwrap(), 0);
+    }
+
+    #[test]
+    fn rules_left_out_keep_the_game_as_it_is() {
+        let rules = rules("undefined", "fn unrelated() { 1 }").unwrap();
+        assert_eq!(rules.validate_placement(&[], 3).unwrap(), None);
+        assert_eq!(rules.score_word("AT", 2).unwrap(), 2);
+        assert_eq!(rules.turn_end("Ann", 10, 4).unwrap(), 0);
+    }
+
+    #[test]
+    fn broken_scripts_are_reported() {
+        assert!(matches!(
+            rules("syntax", "fn turn_end( {"),
+            Err(ScrabbleError::Script(_))
+        ));
+        let rules = rules("mismatch", "fn score_word(word, score) { \"many\" }").unwrap();
+        assert!(matches!(
+            rules.score_word("AT", 2),
+            Err(ScrabbleError::Script(reason)) if reason.starts_with("score_word should return a number")
+        ));
+    }
+}
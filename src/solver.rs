@@ -0,0 +1,490 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::Instant,
+};
+
+use cursive::Vec2;
+use itertools::Itertools;
+
+use crate::{
+    board::{Alignment, Board, Multiplier},
+    dictionary::{Dictionary, SEP},
+    gaddag::Gaddag,
+    game::{can_exchange, N_LETTERS},
+    leaves::Leaves,
+    tile::Tile,
+};
+
+const BINGO_BONUS: usize = 50;
+
+/// A complete placement found by the solver.
+#[derive(Clone)]
+pub struct Move {
+    pub alignment: Alignment,
+    pub score: usize,
+    pub start: Vec2,
+    pub tiles: Vec<(Vec2, Tile)>,
+    pub word: String,
+}
+
+impl Move {
+    /// The starting square in standard notation: row then column for plays across
+    /// ("8H"), column then row for plays down ("H8").
+    pub fn coordinate(&self) -> String {
+        let column = (b'A' + self.start.x as u8) as char;
+        let row = self.start.y + 1;
+        match self.alignment {
+            Alignment::Vertical => format!("{column}{row}"),
+            _ => format!("{row}{column}"),
+        }
+    }
+
+    /// The move as written in GCG files and by Quackle: the coordinate followed by the
+    /// word, with letters already on the board replaced by '.' and blanks in lower case.
+    pub fn notation(&self) -> String {
+        let (dx, dy) = match self.alignment {
+            Alignment::Vertical => (0, 1),
+            _ => (1, 0),
+        };
+        let word = self
+            .word
+            .chars()
+            .enumerate()
+            .map(|(i, letter)| {
+                let pos = Vec2::new(self.start.x + i * dx, self.start.y + i * dy);
+                match self.tiles.iter().find(|(p, _)| *p == pos) {
+                    Some((_, tile)) => tile.played_as(letter).label(),
+                    None => '.',
+                }
+            })
+            .collect::<String>();
+        format!("{} {word}", self.coordinate())
+    }
+
+    // The same play along the other alignment, mirrored about the diagonal.
+    fn transposed(&self) -> Self {
+        let flip = |pos: &Vec2| Vec2::new(pos.y, pos.x);
+        Self {
+            alignment: self.alignment.perpendicular(),
+            score: self.score,
+            start: flip(&self.start),
+            tiles: self
+                .tiles
+                .iter()
+                .map(|(pos, tile)| (flip(pos), *tile))
+                .collect(),
+            word: self.word.clone(),
+        }
+    }
+
+    /// The tiles left on `rack` after playing the move.
+    pub fn leave(&self, rack: &[Tile]) -> Vec<Tile> {
+        let mut leave = rack.to_vec();
+        for (_, tile) in &self.tiles {
+            if let Some(idx) = leave.iter().position(|&t| t == tile.drawn()) {
+                leave.swap_remove(idx);
+            }
+        }
+        leave
+    }
+}
+
+/// Anything a player can do with their turn.
+#[derive(Clone)]
+pub enum Candidate {
+    Play(Move),
+    /// Trading the letters for as many from the bag.
+    Exchange(Vec<Tile>),
+    Pass,
+}
+
+/// A candidate and its equity: the points it scores plus the worth of the letters it
+/// keeps on the rack.
+#[derive(Clone)]
+pub struct Evaluated {
+    pub candidate: Candidate,
+    pub equity: f64,
+}
+
+/// Move generator following Gordon's GADDAG algorithm: every play is grown outwards
+/// from an anchor square, first leftwards (upwards) and then, past the separator,
+/// rightwards (downwards).
+pub struct Solver<'a, D: Dictionary = Gaddag> {
+    board: &'a Board,
+    deadline: Option<Instant>,
+    dict: &'a D,
+    openings: Option<&'a Openings>,
+}
+
+/// Opening plays by rack, for a single dictionary. On the empty board only the rack
+/// matters, so each rack's plays are worked out once and shared by the solvers given
+/// the cache with `Solver::with_openings`.
+#[derive(Default)]
+pub struct Openings {
+    plays: Mutex<HashMap<Vec<Tile>, Vec<Move>>>,
+}
+
+impl<'a, D: Dictionary> Solver<'a, D> {
+    pub fn new(dict: &'a D, board: &'a Board) -> Self {
+        Self {
+            board,
+            deadline: None,
+            dict,
+            openings: None,
+        }
+    }
+
+    /// Looks opening plays up in `openings`, adding the ones worked out.
+    pub fn with_openings(mut self, openings: &'a Openings) -> Self {
+        self.openings = Some(openings);
+        self
+    }
+
+    /// Stops the search once the deadline passes, keeping the moves found so far.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Whether `rack` can play all of its seven tiles anywhere.
+    pub fn has_bingo(&self, rack: &[Tile]) -> bool {
+        self.moves(rack)
+            .iter()
+            .any(|play| play.tiles.len() >= N_LETTERS)
+    }
+
+    /// The best score of the moves of `rack` putting a tile on each square.
+    pub fn best_by_square(&self, rack: &[Tile]) -> HashMap<Vec2, usize> {
+        let mut best = HashMap::new();
+        for play in self.moves(rack) {
+            for &(pos, _) in &play.tiles {
+                let score = best.entry(pos).or_insert(0);
+                *score = play.score.max(*score);
+            }
+        }
+        best
+    }
+
+    /// Returns every legal placement of letters from `rack`, highest scoring first.
+    /// Tentatively placed letters on the board are treated as vacant squares.
+    pub fn moves(&self, rack: &[Tile]) -> Vec<Move> {
+        if self.board.has_committed() {
+            let anchors = self.board.anchors().iter().cloned().collect();
+            return self.generate(anchors, &[Alignment::Horizontal, Alignment::Vertical], rack);
+        }
+        let Some(openings) = self.openings else {
+            return self.opening(rack);
+        };
+        let key = rack.iter().copied().sorted_unstable().collect::<Vec<_>>();
+        if let Some(plays) = openings.plays.lock().unwrap().get(&key) {
+            return plays.clone();
+        }
+        let plays = self.opening(rack);
+        // Plays cut short by the deadline aren't all there is to the rack.
+        if self
+            .deadline
+            .is_none_or(|deadline| Instant::now() < deadline)
+        {
+            openings.plays.lock().unwrap().insert(key, plays.clone());
+        }
+        plays
+    }
+
+    // The board is symmetric about its diagonal, so the plays down through the center
+    // are the plays across it turned over.
+    fn opening(&self, rack: &[Tile]) -> Vec<Move> {
+        let across = self.generate(
+            vec![self.board.center_pos()],
+            &[Alignment::Horizontal],
+            rack,
+        );
+        let down = across.iter().map(Move::transposed).collect::<Vec<_>>();
+        let mut seen = HashSet::new();
+        let mut moves = across
+            .into_iter()
+            .chain(down)
+            .filter(|m| {
+                seen.insert(
+                    m.tiles
+                        .iter()
+                        .cloned()
+                        .sorted_by_key(|(p, _)| (p.y, p.x))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        moves.sort_by_key(|m| Reverse(m.score));
+        moves
+    }
+
+    // Every placement through `anchors` along `alignments`, highest scoring first.
+    fn generate(&self, anchors: Vec<Vec2>, alignments: &[Alignment], rack: &[Tile]) -> Vec<Move> {
+        let mut moves = Vec::new();
+        let mut seen = HashSet::new();
+        for anchor in anchors {
+            for &alignment in alignments {
+                let mut search = Search {
+                    anchor,
+                    alignment,
+                    rack: rack.to_vec(),
+                    placed: Vec::new(),
+                };
+                self.gen(&mut search, 0, String::new(), self.dict.root(), &mut |m| {
+                    let key = m.tiles.iter().cloned().sorted_by_key(|(p, _)| (p.y, p.x));
+                    if seen.insert(key.collect::<Vec<_>>()) {
+                        moves.push(m);
+                    }
+                });
+            }
+        }
+
+        moves.sort_by_key(|m| Reverse(m.score));
+        moves
+    }
+
+    /// Every placement, exchange and pass open to `rack` with `bag` letters left in the
+    /// bag, best equity first, exchanges only while the bag allows them. The leaves are only counted while there's a bag to
+    /// draw from, as once it's empty no letters are drawn to go with them.
+    pub fn candidates(&self, rack: &[Tile], leaves: &Leaves, bag: usize) -> Vec<Evaluated> {
+        let leave_value = |kept: &[Tile]| if bag > 0 { leaves.value(kept) } else { 0.0 };
+        let mut candidates = self
+            .moves(rack)
+            .into_iter()
+            .map(|play| Evaluated {
+                equity: play.score as f64 + leave_value(&play.leave(rack)),
+                candidate: Candidate::Play(play),
+            })
+            .collect::<Vec<_>>();
+        candidates.push(Evaluated {
+            candidate: Candidate::Pass,
+            equity: leave_value(rack),
+        });
+
+        let mut seen = HashSet::new();
+        for kept in 0..(1u32 << rack.len()) - 1 {
+            let (keep, trade): (Vec<_>, Vec<_>) = (0..rack.len())
+                .map(|i| (kept & (1 << i) != 0, rack[i]))
+                .partition(|(keep, _)| *keep);
+            let trade = trade
+                .into_iter()
+                .map(|(_, tile)| tile)
+                .sorted_unstable()
+                .collect::<Vec<_>>();
+            if !can_exchange(trade.len(), bag) || !seen.insert(trade.clone()) {
+                continue;
+            }
+            let keep = keep.into_iter().map(|(_, tile)| tile).collect::<Vec<_>>();
+            candidates.push(Evaluated {
+                candidate: Candidate::Exchange(trade),
+                equity: leave_value(&keep),
+            });
+        }
+
+        candidates.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+        candidates
+    }
+
+    fn gen(
+        &self,
+        search: &mut Search,
+        offset: isize,
+        word: String,
+        node: D::Node,
+        record: &mut impl FnMut(Move),
+    ) {
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return;
+        }
+        let Some(pos) = self.board.offset(&search.anchor, search.alignment, offset) else {
+            return;
+        };
+
+        if let Some(letter) = self.board.committed_letter_at(&pos) {
+            self.go_on(search, offset, letter, word, node, record);
+            return;
+        }
+
+        let crosscheck = self.board.crosscheck(&pos, search.alignment);
+        for tile in search.rack.iter().cloned().unique().collect::<Vec<_>>() {
+            // A blank is tried as every letter the dictionary goes on with.
+            let letters = if tile.is_blank {
+                self.dict
+                    .transitions(&node)
+                    .into_iter()
+                    .map(|(letter, _)| letter)
+                    .collect()
+            } else {
+                vec![tile.letter]
+            };
+            for letter in letters {
+                if !crosscheck.allows(letter) {
+                    continue;
+                }
+                let idx = search.rack.iter().position(|&t| t == tile).unwrap();
+                search.rack.swap_remove(idx);
+                search.placed.push((pos, tile.played_as(letter)));
+                self.go_on(search, offset, letter, word.clone(), node, record);
+                search.placed.pop();
+                search.rack.push(tile);
+            }
+        }
+    }
+
+    fn go_on(
+        &self,
+        search: &mut Search,
+        offset: isize,
+        letter: char,
+        word: String,
+        node: D::Node,
+        record: &mut impl FnMut(Move),
+    ) {
+        let Some(next) = self.dict.next_node(&node, letter) else {
+            return;
+        };
+
+        if offset <= 0 {
+            let word = format!("{letter}{word}");
+            let left_vacant = self.is_vacant(search, offset - 1);
+            if self.dict.is_final(&next) && left_vacant && self.is_vacant(search, 1) {
+                record(self.make_move(search, offset, &word));
+            }
+            self.gen(search, offset - 1, word.clone(), next, record);
+            if left_vacant {
+                if let Some(sep) = self.dict.next_node(&next, SEP) {
+                    self.gen(search, 1, word, sep, record);
+                }
+            }
+        } else {
+            let word = format!("{word}{letter}");
+            if self.dict.is_final(&next) && self.is_vacant(search, offset + 1) {
+                let start = offset + 1 - word.chars().count() as isize;
+                record(self.make_move(search, start, &word));
+            }
+            self.gen(search, offset + 1, word, next, record);
+        }
+    }
+
+    // Off-board squares count as vacant, since they can't extend the word.
+    fn is_vacant(&self, search: &Search, offset: isize) -> bool {
+        self.board
+            .offset(&search.anchor, search.alignment, offset)
+            .is_none_or(|pos| self.board.committed_letter_at(&pos).is_none())
+    }
+
+    fn make_move(&self, search: &Search, start: isize, word: &str) -> Move {
+        // The word was built over these squares.
+        let squares = (0..word.chars().count() as isize)
+            .filter_map(|i| {
+                self.board
+                    .offset(&search.anchor, search.alignment, start + i)
+            })
+            .collect::<Vec<_>>();
+        Move {
+            alignment: search.alignment,
+            score: self.score(search.alignment, &search.placed, &squares),
+            start: squares[0],
+            tiles: search.placed.clone(),
+            word: word.to_string(),
+        }
+    }
+
+    // Premiums only count for the squares covered by newly placed tiles.
+    fn score(&self, alignment: Alignment, placed: &[(Vec2, Tile)], squares: &[Vec2]) -> usize {
+        let mut main_score = squares
+            .iter()
+            .filter_map(|pos| {
+                self.board
+                    .committed_tile_at(pos)
+                    .or_else(|| placed.iter().find(|(p, _)| p == pos).map(|&(_, tile)| tile))
+            })
+            .map(|tile| tile.value)
+            .sum::<usize>();
+        let mut main_factor = 1;
+        let mut cross_total = 0;
+        for (pos, tile) in placed {
+            let letter_score = tile.value;
+            let (letter_factor, word_factor) = match self.board.mult_at(pos.x, pos.y) {
+                Some(mult @ (Multiplier::Dl | Multiplier::Tl)) => (mult.as_factor(), 1),
+                Some(mult @ (Multiplier::Dw | Multiplier::Tw)) => (1, mult.as_factor()),
+                None => (1, 1),
+            };
+            main_score += letter_score * (letter_factor - 1);
+            main_factor *= word_factor;
+
+            if let Some(cross_sum) = self.board.cross_sum(pos, alignment) {
+                cross_total += (cross_sum + letter_score * letter_factor) * word_factor;
+            }
+        }
+
+        let bingo = if placed.len() == N_LETTERS {
+            BINGO_BONUS
+        } else {
+            0
+        };
+        main_score * main_factor + cross_total + bingo
+    }
+}
+
+// State threaded through the recursive generation from a single anchor.
+struct Search {
+    anchor: Vec2,
+    alignment: Alignment,
+    rack: Vec<Tile>,
+    placed: Vec<(Vec2, Tile)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bag::Distribution, reference};
+
+    #[test]
+    fn candidates_exchange_only_with_a_full_rack_in_the_bag() {
+        let dict = Gaddag::from_words(["AT", "TA"].map(String::from)).unwrap();
+        let board = Board::new(15);
+        let distribution = Distribution::default();
+        let rack = "AEINRST"
+            .chars()
+            .map(|letter| distribution.tile(letter))
+            .collect::<Vec<_>>();
+        let exchanges = |bag| {
+            Solver::new(&dict, &board)
+                .candidates(&rack, &Leaves::default(), bag)
+                .into_iter()
+                .filter(|evaluated| matches!(evaluated.candidate, Candidate::Exchange(_)))
+                .count()
+        };
+        assert_eq!(exchanges(N_LETTERS - 1), 0);
+        assert_eq!(exchanges(N_LETTERS), (1 << N_LETTERS) - 1);
+    }
+
+    #[test]
+    fn blanks_stand_in_for_any_letter() {
+        let dict = Gaddag::from_words(["CAT", "CAB", "AT"].map(String::from)).unwrap();
+        let board = Board::new(15);
+        let distribution = Distribution::default();
+        let rack = [distribution.tile('C'), distribution.tile('A'), Tile::BLANK];
+        let moves = Solver::new(&dict, &board).moves(&rack);
+        let words = moves
+            .iter()
+            .filter(|play| play.tiles.iter().any(|(_, tile)| tile.is_blank))
+            .map(|play| play.word.as_str())
+            .collect::<HashSet<_>>();
+        assert_eq!(words, HashSet::from(["CAT", "CAB", "AT"]));
+        let cat = moves.iter().find(|play| play.word == "CAT").unwrap();
+        assert!(cat
+            .tiles
+            .iter()
+            .any(|&(_, tile)| tile == Tile::BLANK.played_as('T')));
+        assert!(cat.leave(&rack).is_empty());
+
+        let words = dict.iter_words().collect::<Vec<_>>();
+        assert!(reference::compare(&dict, &words, &board, &rack).is_empty());
+    }
+}
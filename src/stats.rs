@@ -0,0 +1,171 @@
+//! How likely tiles are to be drawn: words from a full bag, for telling the rare finds
+//! of a game from the common ones, and letters from the unseen tiles, for weighing a
+//! short play against an exchange.
+
+use std::collections::HashMap;
+
+use crate::{dictionary::Dictionary, gaddag::Gaddag, tile::Tile};
+
+/// The odds of a word against the others of its length in the lexicon.
+pub struct Rarity {
+    /// Chance of the first tiles drawn from a full bag spelling the word, blanks aside.
+    pub probability: f64,
+    /// Position of the word among the words of its length, the likeliest first.
+    pub rank: usize,
+    pub of: usize,
+}
+
+/// Draw probabilities for the words of a lexicon, given the tiles of the bag.
+pub struct Odds<'a, D: Dictionary = Gaddag> {
+    dict: &'a D,
+    counts: HashMap<char, usize>,
+    total: usize,
+    // The probabilities of every word of a length, most likely first, worked out the
+    // first time a word of that length is looked up.
+    by_length: HashMap<usize, Vec<f64>>,
+}
+
+impl<'a, D: Dictionary> Odds<'a, D> {
+    pub fn new(dict: &'a D, tiles: &[Tile]) -> Self {
+        let mut counts = HashMap::new();
+        for tile in tiles {
+            *counts.entry(tile.letter).or_default() += 1;
+        }
+        Self {
+            dict,
+            counts,
+            total: tiles.len(),
+            by_length: HashMap::new(),
+        }
+    }
+
+    /// Chance of drawing the letters of `word` as the first tiles from the bag.
+    pub fn probability(&self, word: &str) -> f64 {
+        let mut needed: HashMap<char, usize> = HashMap::new();
+        for letter in word.chars() {
+            *needed.entry(letter.to_ascii_uppercase()).or_default() += 1;
+        }
+        let draws = needed
+            .iter()
+            .map(|(letter, &k)| choose(self.counts.get(letter).copied().unwrap_or(0), k))
+            .product::<f64>();
+        draws / choose(self.total, word.chars().count())
+    }
+
+    pub fn rarity(&mut self, word: &str) -> Rarity {
+        let probability = self.probability(word);
+        let len = word.chars().count();
+        if !self.by_length.contains_key(&len) {
+            let mut probabilities = self
+                .dict
+                .words(len)
+                .iter()
+                .map(|word| self.probability(word))
+                .collect::<Vec<_>>();
+            probabilities.sort_by(|a, b| b.total_cmp(a));
+            self.by_length.insert(len, probabilities);
+        }
+        let probabilities = &self.by_length[&len];
+        Rarity {
+            probability,
+            rank: probabilities.partition_point(|&p| p > probability) + 1,
+            of: probabilities.len(),
+        }
+    }
+}
+
+/// Chance that `draws` tiles drawn from `pool` include at least one of `letters`.
+pub fn chance_of_any(pool: &[Tile], letters: &[char], draws: usize) -> f64 {
+    let draws = draws.min(pool.len());
+    let others = pool
+        .iter()
+        .filter(|tile| !letters.contains(&tile.letter))
+        .count();
+    1.0 - choose(others, draws) / choose(pool.len(), draws)
+}
+
+/// Chance that `draws` tiles drawn from `pool` include all of `wanted`, repeated letters
+/// as often as they're repeated.
+pub fn chance_of_all(pool: &[Tile], wanted: &[char], draws: usize) -> f64 {
+    let draws = draws.min(pool.len());
+    let mut needs: HashMap<char, usize> = HashMap::new();
+    for &letter in wanted {
+        *needs.entry(letter).or_default() += 1;
+    }
+    let needs = needs
+        .iter()
+        .map(|(letter, &need)| {
+            let count = pool.iter().filter(|tile| tile.letter == *letter).count();
+            (count, need)
+        })
+        .collect::<Vec<_>>();
+    let others = pool.len() - needs.iter().map(|&(count, _)| count).sum::<usize>();
+    ways(&needs, others, draws) / choose(pool.len(), draws)
+}
+
+// Draws of `draws` tiles with at least the needed number of each letter, given as its
+// count in the pool and the number needed, and any of the `others` to fill up with.
+fn ways(needs: &[(usize, usize)], others: usize, draws: usize) -> f64 {
+    match needs.split_first() {
+        None => choose(others, draws),
+        Some((&(count, need), rest)) => (need..=count.min(draws))
+            .map(|drawn| choose(count, drawn) * ways(rest, others, draws - drawn))
+            .fold(0.0, |sum, ways| sum + ways),
+    }
+}
+
+// Binomial coefficient, in floating point as the counts for long words overflow.
+fn choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (0..k).map(|i| (n - i) as f64 / (i + 1) as f64).product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two A's, a B and a C.
+    fn pool() -> Vec<Tile> {
+        "AABC".chars().map(|letter| Tile::new(letter, 1)).collect()
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn words_are_ranked_by_their_odds() {
+        let dict = Gaddag::from_words(["AA", "AB", "BC", "CC"].map(String::from)).unwrap();
+        let mut odds = Odds::new(&dict, &pool());
+        assert!(close(odds.probability("ab"), 1.0 / 3.0));
+        assert!(close(odds.probability("AA"), 1.0 / 6.0));
+        assert_eq!(odds.probability("CC"), 0.0);
+
+        let ab = odds.rarity("AB");
+        assert_eq!((ab.rank, ab.of), (1, 4));
+        let aa = odds.rarity("AA");
+        assert_eq!((aa.rank, aa.of), (2, 4));
+        assert_eq!(odds.rarity("CC").rank, 4);
+    }
+
+    #[test]
+    fn draws_are_counted_without_replacement() {
+        let pool = pool();
+        assert!(close(chance_of_any(&pool, &['C'], 2), 0.5));
+        assert!(close(chance_of_any(&pool, &['A'], 3), 1.0));
+        assert_eq!(chance_of_any(&pool, &['Z'], 4), 0.0);
+        assert!(close(chance_of_all(&pool, &['A', 'A'], 2), 1.0 / 6.0));
+        assert!(close(chance_of_all(&pool, &['A', 'B'], 2), 1.0 / 3.0));
+        assert!(close(chance_of_all(&pool, &['A', 'B', 'C'], 9), 1.0));
+        assert_eq!(chance_of_all(&pool, &['B', 'B'], 4), 0.0);
+    }
+
+    #[test]
+    fn choose_counts_subsets() {
+        assert_eq!(choose(5, 2), 10.0);
+        assert_eq!(choose(5, 0), 1.0);
+        assert_eq!(choose(2, 3), 0.0);
+    }
+}
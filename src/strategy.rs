@@ -0,0 +1,223 @@
+//! How bots choose their moves. A move is worth its score, less the premium squares it
+//! brings within the opponent's reach.
+
+use std::collections::HashMap;
+
+use cursive::Vec2;
+use serde_derive::Deserialize;
+
+use crate::{
+    board::{Alignment, Board, CrossCheck, Multiplier},
+    dictionary::Dictionary,
+    game::N_LETTERS,
+    solver::{Move, Openings, Solver},
+    tile::Tile,
+    wordlists::VOWELS,
+};
+
+/// How hard a bot looks for its moves, for trading strength for speed on slow machines.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct Effort {
+    /// The highest scoring moves weighed for what they open up, as each costs a board
+    /// update.
+    pub candidates: usize,
+    /// The highest scoring plays looked into in the endgame at every ply but the last.
+    pub endgame_candidates: usize,
+    /// Plays looked ahead in the endgame, the bot's own included: 1 only counts the
+    /// letters left on the racks, 2 weighs every play against the best reply.
+    pub endgame_depth: usize,
+}
+
+impl Default for Effort {
+    fn default() -> Self {
+        Self {
+            candidates: 20,
+            endgame_candidates: 30,
+            endgame_depth: 2,
+        }
+    }
+}
+
+/// The move a bot with `rack` plays, if it has any, looking its opening plays up in
+/// `openings` if they were worked out for `dict`.
+pub fn choose(
+    dict: &impl Dictionary,
+    board: &Board,
+    rack: &[Tile],
+    openings: Option<&Openings>,
+    effort: Effort,
+) -> Option<Move> {
+    let open = premiums_in_reach(board);
+    let mut solver = Solver::new(dict, board);
+    if let Some(openings) = openings {
+        solver = solver.with_openings(openings);
+    }
+    solver
+        .moves(rack)
+        .into_iter()
+        .take(effort.candidates.max(1))
+        .map(|play| {
+            (
+                play.score as f64 - openness(dict, board, &open, &play),
+                play,
+            )
+        })
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, play)| play)
+}
+
+/// What `play` gives away by opening premium squares that no play could reach before.
+pub fn openness(
+    dict: &impl Dictionary,
+    board: &Board,
+    open: &HashMap<Vec2, Multiplier>,
+    play: &Move,
+) -> f64 {
+    let mut after = board.clone();
+    for (pos, letter) in &play.tiles {
+        after.place_at(*letter, pos);
+    }
+    after.commit_tentative(dict);
+    premiums_in_reach(&after)
+        .iter()
+        .filter(|(pos, _)| !open.contains_key(pos))
+        .map(|(_, &mult)| penalty(mult))
+        .sum()
+}
+
+/// The vacant premium squares a play through some anchor could cover, as far as the
+/// letters of one rack stretch from it.
+pub fn premiums_in_reach(board: &Board) -> HashMap<Vec2, Multiplier> {
+    let mut premiums = HashMap::new();
+    for anchor in board.anchors() {
+        for alignment in [Alignment::Horizontal, Alignment::Vertical] {
+            if board.crosscheck(anchor, alignment) == CrossCheck::NONE {
+                continue;
+            }
+            for delta in -(N_LETTERS as isize - 1)..N_LETTERS as isize {
+                let Some(pos) = board.offset(anchor, alignment, delta) else {
+                    continue;
+                };
+                if let (None, Some(mult)) = (board.letter_at(&pos), board.mult_at(pos.x, pos.y)) {
+                    premiums.insert(pos, mult);
+                }
+            }
+        }
+    }
+    premiums
+}
+
+/// How open the board is, for telling when the game is getting dangerous: the lanes to
+/// triple word squares some play could reach, and the premium squares in reach next to
+/// a vowel, the easiest letters to hook and play alongside.
+#[derive(Clone, Copy, Default)]
+pub struct Volatility {
+    pub lanes: usize,
+    pub hotspots: usize,
+}
+
+impl Volatility {
+    pub fn of(board: &Board) -> Self {
+        let open = premiums_in_reach(board);
+        let lanes = open
+            .values()
+            .filter(|&&mult| mult == Multiplier::Tw)
+            .count();
+        let hotspots = open
+            .keys()
+            .filter(|pos| {
+                [Alignment::Horizontal, Alignment::Vertical]
+                    .into_iter()
+                    .flat_map(|alignment| [-1, 1].map(|step| (alignment, step)))
+                    .filter_map(|(alignment, step)| board.offset(pos, alignment, step))
+                    .filter_map(|next| board.committed_letter_at(&next))
+                    .any(|letter| VOWELS.contains(&letter))
+            })
+            .count();
+        Volatility { lanes, hotspots }
+    }
+
+    /// Rough worth of the board being this open to whoever moves next, in points, on
+    /// the scale of `openness`, so it can be weighed against scores as well.
+    pub fn danger(&self) -> f64 {
+        self.lanes as f64 * penalty(Multiplier::Tw) + self.hotspots as f64
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self.danger() {
+            d if d < 12.0 => "closed",
+            d if d < 30.0 => "open",
+            _ => "dangerous",
+        }
+    }
+}
+
+// Rough worth of a premium square to the opponent, in points.
+fn penalty(mult: Multiplier) -> f64 {
+    match mult {
+        Multiplier::Tw => 6.0,
+        Multiplier::Dw => 2.0,
+        Multiplier::Tl => 1.5,
+        Multiplier::Dl => 0.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gaddag::Gaddag;
+
+    // A triple word two squares left of AT in the middle of the board, a double letter
+    // next to its A, and a triple word out of reach in the corner of the second row.
+    const LAYOUT: &str = "
+        .......
+        T......
+        .......
+        T.d....
+        .......
+        .......
+        .......
+    ";
+
+    fn setup() -> (Gaddag, Board) {
+        let dict = Gaddag::from_words(["AT", "TA", "ATA"].map(String::from)).unwrap();
+        let mut board = Board::from_layout(LAYOUT).unwrap();
+        board.place_at(Tile::new('A', 1), &Vec2::new(3, 3));
+        board.place_at(Tile::new('T', 1), &Vec2::new(4, 3));
+        board.commit_tentative(&dict);
+        (dict, board)
+    }
+
+    #[test]
+    fn premiums_in_reach_of_an_anchor_are_open() {
+        let (_, board) = setup();
+        let open = premiums_in_reach(&board);
+        assert!(
+            open == HashMap::from([
+                (Vec2::new(0, 3), Multiplier::Tw),
+                (Vec2::new(2, 3), Multiplier::Dl)
+            ])
+        );
+        let volatility = Volatility::of(&board);
+        assert_eq!((volatility.lanes, volatility.hotspots), (1, 1));
+        assert_eq!(volatility.danger(), 7.0);
+        assert_eq!(volatility.label(), "closed");
+    }
+
+    #[test]
+    fn plays_pay_for_the_premiums_they_open() {
+        let (dict, board) = setup();
+        let open = premiums_in_reach(&board);
+        let play = |pos: Vec2| Move {
+            alignment: Alignment::Vertical,
+            score: 2,
+            start: pos,
+            tiles: vec![(pos, Tile::new('T', 1))],
+            word: "TA".to_string(),
+        };
+        // Over the A, from where ATA reaches along the second row.
+        assert_eq!(openness(&dict, &board, &open, &play(Vec2::new(3, 2))), 6.0);
+        assert_eq!(openness(&dict, &board, &open, &play(Vec2::new(3, 4))), 0.0);
+    }
+}
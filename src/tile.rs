@@ -0,0 +1,107 @@
+//! The tiles of a game as they're drawn, held on racks and played: a letter with what
+//! it's worth, which the bag's distribution decides, and whether it's a blank.
+
+use std::fmt;
+
+/// A tile. A blank is worth nothing and has a space for its letter until it's played,
+/// when it takes the letter it stands for.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Tile {
+    pub letter: char,
+    pub value: usize,
+    pub is_blank: bool,
+}
+
+impl Tile {
+    pub const BLANK: Self = Self {
+        letter: ' ',
+        value: 0,
+        is_blank: true,
+    };
+
+    pub fn new(letter: char, value: usize) -> Self {
+        Self {
+            letter,
+            value,
+            is_blank: false,
+        }
+    }
+
+    /// This tile played as `letter`, which only a blank can stand for.
+    pub fn played_as(self, letter: char) -> Self {
+        if self.is_blank {
+            Self { letter, ..self }
+        } else {
+            self
+        }
+    }
+
+    /// The tile as it was drawn, a blank going back to a space.
+    pub fn drawn(self) -> Self {
+        if self.is_blank {
+            Self::BLANK
+        } else {
+            self
+        }
+    }
+
+    /// Whether this tile, as drawn, is the one written `label`, see `label`.
+    pub fn is_written(self, label: char) -> bool {
+        if self.is_blank {
+            label == '?' || label.is_lowercase()
+        } else {
+            !label.is_lowercase() && self.letter == label
+        }
+    }
+
+    /// The tile as written in GCG files and scenarios: '?' for a blank on a rack, and
+    /// the letter in lower case for a blank that's been played.
+    pub fn label(self) -> char {
+        match (self.is_blank, self.letter) {
+            (true, ' ') => '?',
+            (true, letter) => letter.to_lowercase().next().unwrap_or(letter),
+            (false, letter) => letter,
+        }
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blanks_take_a_letter_when_played_and_give_it_back_when_drawn() {
+        let played = Tile::BLANK.played_as('Q');
+        assert!(played.is_blank);
+        assert_eq!((played.letter, played.value), ('Q', 0));
+        assert_eq!(played.drawn(), Tile::BLANK);
+
+        let tile = Tile::new('Q', 10);
+        assert_eq!(tile.played_as('Z'), tile);
+        assert_eq!(tile.drawn(), tile);
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(Tile::new('Q', 10).label(), 'Q');
+        assert_eq!(Tile::BLANK.label(), '?');
+        assert_eq!(Tile::BLANK.played_as('Q').to_string(), "q");
+    }
+
+    #[test]
+    fn is_written() {
+        let tile = Tile::new('Q', 10);
+        assert!(tile.is_written('Q'));
+        assert!(!tile.is_written('q'));
+        assert!(!tile.is_written('?'));
+        assert!(Tile::BLANK.is_written('?'));
+        assert!(Tile::BLANK.is_written('q'));
+        assert!(!Tile::BLANK.is_written('Q'));
+    }
+}
@@ -0,0 +1,100 @@
+//! The tracking sheet kept on paper at tournaments: every tile of the game letter by
+//! letter, with the ones played crossed off, which leaves the ones still in the bag or
+//! on a rack.
+//!
+//! ```text
+//! A  --AAAAAAA     7
+//! B  BB            2
+//! E  -EEEEEEEEEEE  11
+//! ```
+
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::tile::Tile;
+
+pub struct TrackingSheet {
+    // Each kind of tile, blanks last, with how many the game has and how many are played.
+    rows: Vec<(Tile, usize, usize)>,
+}
+
+impl TrackingSheet {
+    /// The sheet of a game of `tiles`, of which `unplayed` are in the bag or on a rack.
+    pub fn new(tiles: &[Tile], unplayed: &[Tile]) -> Self {
+        let rows = tiles
+            .iter()
+            .copied()
+            .sorted_by_key(|&tile| (tile.is_blank, tile))
+            .dedup_with_count()
+            .map(|(count, tile)| {
+                let left = unplayed.iter().filter(|&&l| l == tile).count();
+                (tile, count, count.saturating_sub(left))
+            })
+            .collect();
+        Self { rows }
+    }
+
+    fn unplayed(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|(_, count, played)| count - played)
+            .sum()
+    }
+}
+
+impl fmt::Display for TrackingSheet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.rows.iter().map(|(_, count, _)| *count).max();
+        for &(tile, count, played) in &self.rows {
+            let label = tile.label();
+            let tiles = format!(
+                "{}{}",
+                "-".repeat(played),
+                label.to_string().repeat(count - played)
+            );
+            writeln!(
+                f,
+                "{label}  {tiles:width$}  {}",
+                count - played,
+                width = width.unwrap_or_default()
+            )?;
+        }
+        write!(f, "{} tiles not yet played", self.unplayed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiles(labels: &str) -> Vec<Tile> {
+        labels
+            .chars()
+            .map(|label| match label {
+                '?' => Tile::BLANK,
+                letter => Tile::new(letter, 1),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn played_tiles_are_crossed_off() {
+        let sheet = TrackingSheet::new(&tiles("?BAAAE?"), &tiles("A?B"));
+        assert_eq!(
+            sheet.to_string(),
+            "A  --A  1\n\
+             B  B    1\n\
+             E  -    0\n\
+             ?  -?   1\n\
+             3 tiles not yet played"
+        );
+    }
+
+    #[test]
+    fn nothing_played() {
+        let all = tiles("AB");
+        let sheet = TrackingSheet::new(&all, &all);
+        assert_eq!(sheet.unplayed(), 2);
+    }
+}
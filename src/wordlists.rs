@@ -0,0 +1,130 @@
+//! Lists of words worth knowing by heart, drawn from the lexicon: the two-letter words,
+//! the Q words without a U, the words to dump vowels with and so on.
+
+use itertools::Itertools;
+
+use crate::dictionary::Dictionary;
+
+pub const VOWELS: &[char] = &['A', 'E', 'I', 'O', 'U'];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum List {
+    TwoLetters,
+    QWithoutU,
+    /// Four letters or more with at most one consonant.
+    VowelDumps,
+    /// Words without A, E, I, O or U.
+    NoVowels,
+    /// Up to four letters, with a J, Q, X or Z.
+    ShortJqxz,
+}
+
+/// Every list, computed in one pass over the words of a lexicon.
+#[derive(Clone)]
+pub struct WordLists {
+    lists: Vec<(List, Vec<String>)>,
+}
+
+impl List {
+    pub const ALL: [Self; 5] = [
+        Self::TwoLetters,
+        Self::QWithoutU,
+        Self::VowelDumps,
+        Self::NoVowels,
+        Self::ShortJqxz,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::TwoLetters => "Two-letter words",
+            Self::QWithoutU => "Q without U",
+            Self::VowelDumps => "Vowel dumps",
+            Self::NoVowels => "No vowels",
+            Self::ShortJqxz => "Short JQXZ words",
+        }
+    }
+
+    fn includes(self, word: &str) -> bool {
+        let len = word.chars().count();
+        let consonants = word.chars().filter(|ch| !VOWELS.contains(ch)).count();
+        match self {
+            Self::TwoLetters => len == 2,
+            Self::QWithoutU => word.contains('Q') && !word.contains('U'),
+            Self::VowelDumps => len >= 4 && consonants <= 1,
+            Self::NoVowels => consonants == len,
+            Self::ShortJqxz => len <= 4 && word.contains(['J', 'Q', 'X', 'Z']),
+        }
+    }
+}
+
+impl WordLists {
+    pub fn new(dict: &impl Dictionary) -> Self {
+        let mut lists = List::ALL.map(|list| (list, Vec::new())).to_vec();
+        for word in dict.all_words() {
+            for (list, words) in &mut lists {
+                if list.includes(&word) {
+                    words.push(word.clone());
+                }
+            }
+        }
+        for (_, words) in &mut lists {
+            words.sort_unstable_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+        }
+        Self { lists }
+    }
+
+    /// The words of `list`, shortest first and alphabetically.
+    pub fn words(&self, list: List) -> &[String] {
+        self.lists
+            .iter()
+            .find(|(kind, _)| *kind == list)
+            .map_or(&[], |(_, words)| words)
+    }
+
+    /// The words of `list` laid out in rows of `per_row`.
+    pub fn table(&self, list: List, per_row: usize) -> String {
+        match self.words(list) {
+            [] => "No such words in this lexicon.".to_string(),
+            words => words
+                .chunks(per_row)
+                .map(|row| row.iter().join(" "))
+                .join("\n"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gaddag::Gaddag;
+
+    fn lists() -> WordLists {
+        let words = [
+            "QI", "ZA", "AT", "QAT", "QUA", "AIOLI", "EUOI", "AUE", "HYMN", "NTH", "JAB", "ZEBRA",
+            "CWM",
+        ];
+        WordLists::new(&Gaddag::from_words(words.map(String::from)).unwrap())
+    }
+
+    #[test]
+    fn words_are_sorted_into_every_list_they_belong_to() {
+        let lists = lists();
+        let words = |list| lists.words(list).join(" ");
+        assert_eq!(words(List::TwoLetters), "AT QI ZA");
+        assert_eq!(words(List::QWithoutU), "QI QAT");
+        assert_eq!(words(List::VowelDumps), "EUOI AIOLI");
+        assert_eq!(words(List::NoVowels), "CWM NTH HYMN");
+        assert_eq!(words(List::ShortJqxz), "QI ZA JAB QAT QUA");
+    }
+
+    #[test]
+    fn tables_lay_the_words_out_in_rows() {
+        let lists = lists();
+        assert_eq!(lists.table(List::ShortJqxz, 2), "QI ZA\nJAB QAT\nQUA");
+        let empty = WordLists::new(&Gaddag::from_words(["AT"].map(String::from)).unwrap());
+        assert_eq!(
+            empty.table(List::NoVowels, 2),
+            "No such words in this lexicon."
+        );
+    }
+}
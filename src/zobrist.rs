@@ -0,0 +1,81 @@
+//! Zobrist keys for positions. Every letter on every square, and every copy of a letter
+//! on either player's rack, has a key of its own, and a position hashes to the XOR of
+//! the keys of what's in it. Placing or lifting a letter is then a single XOR, which
+//! lets the board keep its hash up to date as it goes (see `Board::hash`).
+//!
+//! The keys are worked out from what they stand for rather than drawn at random, so a
+//! position hashes the same in every build and on every machine.
+
+use cursive::Vec2;
+
+use crate::tile::Tile;
+
+/// The key of `tile` on the square at `pos`, a blank keyed apart from the letter it
+/// stands for.
+pub fn square_key(pos: &Vec2, tile: Tile) -> u64 {
+    mix(((pos.x as u64) << 40) | ((pos.y as u64) << 24) | tile.label() as u64)
+}
+
+/// The hash of `rack` as held by player `holder`, the same whatever order the letters
+/// are in. The racks of a position are told apart by their holders.
+pub fn rack_hash(rack: &[Tile], holder: usize) -> u64 {
+    let mut letters = rack.iter().map(|tile| tile.label()).collect::<Vec<_>>();
+    letters.sort_unstable();
+    let mut hash = 0;
+    let mut copy = 0;
+    for (i, &letter) in letters.iter().enumerate() {
+        copy = if i > 0 && letters[i - 1] == letter {
+            copy + 1
+        } else {
+            0
+        };
+        hash ^= mix((1 << 63) | ((holder as u64) << 40) | ((copy as u64) << 24) | letter as u64);
+    }
+    hash
+}
+
+// SplitMix64's finalizer, which spreads the fields of `n` over all of its bits.
+fn mix(n: u64) -> u64 {
+    let mut z = n.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rack(letters: &str) -> Vec<Tile> {
+        letters
+            .chars()
+            .map(|letter| match letter {
+                '?' => Tile::BLANK,
+                letter => Tile::new(letter, 1),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn square_keys_differ_by_square_and_tile() {
+        let tile = Tile::new('A', 1);
+        let key = square_key(&Vec2::new(7, 7), tile);
+        assert_eq!(key, square_key(&Vec2::new(7, 7), tile));
+        assert_ne!(key, square_key(&Vec2::new(7, 8), tile));
+        assert_ne!(key, square_key(&Vec2::new(8, 7), tile));
+        assert_ne!(key, square_key(&Vec2::new(7, 7), Tile::new('B', 3)));
+        assert_ne!(
+            key,
+            square_key(&Vec2::new(7, 7), Tile::BLANK.played_as('A'))
+        );
+    }
+
+    #[test]
+    fn rack_hash_ignores_order_but_not_copies_or_holder() {
+        assert_eq!(rack_hash(&rack("AAB?"), 0), rack_hash(&rack("?BAA"), 0));
+        assert_ne!(rack_hash(&rack("AAB"), 0), rack_hash(&rack("AB"), 0));
+        assert_ne!(rack_hash(&rack("AA"), 0), 0);
+        assert_ne!(rack_hash(&rack("AB"), 0), rack_hash(&rack("AB"), 1));
+        assert_eq!(rack_hash(&[], 1), 0);
+    }
+}